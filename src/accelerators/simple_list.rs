@@ -8,7 +8,7 @@ use crate::core::ray::Ray;
 /////////////////////
 
 pub struct SimpleList<'a> {
-    primitives: &'a Vec<&'a dyn Primitive>,
+    primitives: &'a [&'a dyn Primitive],
 }
 
 //////////////////////////
@@ -17,18 +17,18 @@ pub struct SimpleList<'a> {
 //////////////////////////
 
 impl<'a> SimpleList<'a> {
-    pub fn new(primitives: &'a Vec<&'a dyn Primitive>) -> Self {
+    pub fn new(primitives: &'a [&'a dyn Primitive]) -> Self {
         Self { primitives }
     }
 }
 
 impl<'a> Accelerator<'a> for SimpleList<'a> {
-    fn build(&mut self, primitives: &'a Vec<&'a dyn Primitive>) {
+    fn build(&mut self, primitives: &'a [&'a dyn Primitive]) {
         self.primitives = primitives;
     }
 
-    fn test(&self, ray: &Ray) -> Option<Interaction> {
-        let mut closest_interaction: Option<Interaction> = None;
+    fn test(&self, ray: &Ray) -> Option<Interaction<'a>> {
+        let mut closest_interaction: Option<Interaction<'a>> = None;
 
         for primitive in self.primitives {
             if let Some(interaction) = primitive.test(ray) {
@@ -40,6 +40,11 @@ impl<'a> Accelerator<'a> for SimpleList<'a> {
 
         closest_interaction
     }
+
+    /// Every primitive is a node, and all of them are tested.
+    fn nodes_visited(&self, _ray: &Ray) -> usize {
+        self.primitives.len()
+    }
 }
 
 ////////////////////////
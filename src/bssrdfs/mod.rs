@@ -0,0 +1 @@
+pub mod random_walk_bssrdf;
@@ -0,0 +1,326 @@
+use std::ptr;
+
+use rand::{Rng, RngCore};
+
+use crate::bxdfs::dielectric_bxdf::DielectricBxDF;
+use crate::bxdfs::lambertian_bxdf::LambertianBxDF;
+use crate::core::accelerator::Accelerator;
+use crate::core::bsdf::Bsdf;
+use crate::core::bssrdf::{Bssrdf, BssrdfSample};
+use crate::core::bxdf::{abs_cos_theta, BxDF};
+use crate::core::frame::Frame;
+use crate::core::interaction::Interaction;
+use crate::core::microfacet::MicrofacetDistribution;
+use crate::core::ray::Ray;
+use crate::core::vector::{Color3f, Point2f, Vec3f};
+use crate::math::sampling::sample_uniform_sphere;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Subsurface scattering by a brute force random walk through a homogeneous medium
+/// filling a closed surface, such as skin, marble, wax or milk.
+/// Scattering is isotropic, and the walk leaves through the dielectric boundary
+/// wherever it reaches the surface, as found by the [Accelerator].
+/// Other primitives inside the medium are passed through rather than taken for its surface.
+/// Light leaving is spread out diffusely rather than along the refracted direction,
+/// so that integrators can light the exit from point lights and other lights
+/// that paths only reach through sampling them directly.
+/// Color channels are handled together, sampling distances with a random channel
+/// and weighting by the average density over all of them.
+pub struct RandomWalkBssrdf<D> {
+    boundary: DielectricBxDF<D>,
+
+    /// The absorption coefficient of the medium, per unit distance
+    sigma_a: Color3f,
+
+    /// The scattering coefficient of the medium, per unit distance
+    sigma_s: Color3f,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// The number of events along a walk before it is considered absorbed.
+const MAX_DEPTH: usize = 256;
+
+/// Keeps rays leaving the surface from hitting it again.
+const RAY_EPSILON: f64 = 1e-6;
+
+/// How far past a primitive inside the medium the search for its surface goes on.
+const SKIP_EPSILON: f64 = 1e-9;
+
+impl<D: MicrofacetDistribution> RandomWalkBssrdf<D> {
+    pub fn new(boundary: DielectricBxDF<D>, sigma_a: Color3f, sigma_s: Color3f) -> Self {
+        Self {
+            boundary,
+            sigma_a,
+            sigma_s,
+        }
+    }
+}
+
+/// Finds the absorption and scattering coefficients of a medium that appears as `albedo`
+/// once light has scattered many times, with light travelling about `mean_free_path`
+/// beneath the surface (Chiang et al. 2016).
+/// Returns `(sigma_a, sigma_s)`.
+pub fn coefficients_from_albedo(albedo: &Color3f, mean_free_path: &Color3f) -> (Color3f, Color3f) {
+    let single_scattering = |a: f64| {
+        let a = a.clamp(0.0, 1.0);
+        1.0 - (-5.09406 * a + 2.61188 * a * a - 4.31805 * a * a * a).exp()
+    };
+    let extinction = |a: f64, d: f64| {
+        let a = a.clamp(0.0, 1.0);
+        let s = 1.9 - a + 3.5 * (a - 0.8) * (a - 0.8);
+        1.0 / (d * s).max(1e-16)
+    };
+
+    let sigma_t = Color3f::new(
+        extinction(albedo.x, mean_free_path.x),
+        extinction(albedo.y, mean_free_path.y),
+        extinction(albedo.z, mean_free_path.z),
+    );
+    let sigma_s = sigma_t * albedo.map(single_scattering);
+    (sigma_t - sigma_s, sigma_s)
+}
+
+fn channel(c: &Color3f, i: usize) -> f64 {
+    match i {
+        0 => c.x,
+        1 => c.y,
+        _ => c.z,
+    }
+}
+
+/// Finds where `ray` reaches the surface of the medium entered at `entry`, passing through
+/// other primitives inside it. Surfaces with the entry's material, such as the rest of
+/// a mesh, bound the same medium. Entries off any material accept every surface.
+fn boundary_hit<'a>(
+    entry: &Interaction,
+    accelerator: &dyn Accelerator<'a>,
+    mut ray: Ray,
+) -> Option<Interaction<'a>> {
+    let Some(material) = entry.material() else {
+        return accelerator.test(&ray);
+    };
+    loop {
+        let hit = accelerator.test(&ray)?;
+        if hit
+            .material()
+            .is_some_and(|other| ptr::addr_eq(other, material))
+        {
+            return Some(hit);
+        }
+        let min_t = hit.t + SKIP_EPSILON * hit.t.abs().max(1.0);
+        ray = Ray::new(ray.o, ray.d, min_t, ray.max_t());
+    }
+}
+
+fn sample_point(rng: &mut dyn RngCore) -> Point2f {
+    Point2f::new(rng.gen(), rng.gen())
+}
+
+impl<D: MicrofacetDistribution> Bssrdf for RandomWalkBssrdf<D> {
+    fn sample_exit<'a>(
+        &self,
+        entry: &Interaction,
+        w: &Vec3f,
+        accelerator: &dyn Accelerator<'a>,
+        rng: &mut dyn RngCore,
+    ) -> Option<BssrdfSample<'a>> {
+        let sigma_t = self.sigma_a + self.sigma_s;
+        let mut weight = Color3f::new(1.0, 1.0, 1.0);
+        let mut p = entry.p;
+        let mut w = w.normalize();
+
+        for depth in 0..MAX_DEPTH {
+            let max = weight.max_component();
+            if depth > 3 && max < 0.25 {
+                let q = (1.0 - max).max(0.0);
+                if rng.gen::<f64>() < q {
+                    return None;
+                }
+                weight /= 1.0 - q;
+            }
+
+            let sigma = channel(&sigma_t, rng.gen_range(0..3));
+            let distance = -(1.0 - rng.gen::<f64>()).ln() / sigma;
+            let ray = Ray::new(p, w, RAY_EPSILON, distance);
+
+            let Some(hit) = boundary_hit(entry, accelerator, ray) else {
+                if distance.is_infinite() {
+                    // Escaped through a hole in the surface
+                    return None;
+                }
+
+                // Scatter inside the medium
+                let transmittance = sigma_t.map(|s| (-s * distance).exp());
+                let pdf = (sigma_t * transmittance).average();
+                weight = weight * self.sigma_s * transmittance / pdf;
+                p += w * distance;
+                // Isotropic, so the phase function cancels with its density
+                w = sample_uniform_sphere(&sample_point(rng));
+                continue;
+            };
+
+            // Reached the boundary first
+            let transmittance = sigma_t.map(|s| (-s * hit.t).exp());
+            weight = weight * transmittance / transmittance.average();
+
+            let n = hit.n?;
+            let n = if n.dot(&w) < 0.0 { -n } else { n };
+            let frame = Frame::from_normal(n.normalize());
+            let wo = frame.world_to_local(&-w);
+
+            let bs = self
+                .boundary
+                .sample_f(&wo, rng.gen(), &sample_point(rng))
+                .filter(|bs| bs.pdf > 0.0 && !bs.f.is_black())?;
+            weight = weight * bs.f * (abs_cos_theta(&bs.wi) / bs.pdf);
+            let wi = frame.local_to_world(&bs.wi);
+
+            if bs.wi.z > 0.0 {
+                // The refracted direction only decides that the walk leaves, its Fresnel
+                // transmission already being in the weight. By the time light has scattered
+                // its way out it has lost track of where it came from, so it leaves through
+                // the diffuse lobe instead, which looks the same from any `wo` on the outside
+                let white = Color3f::new(1.0, 1.0, 1.0);
+                return Some(BssrdfSample {
+                    interaction: Interaction { wo: n, ..hit },
+                    bsdf: Bsdf::new(frame, Box::new(LambertianBxDF::new(white))),
+                    weight,
+                });
+            }
+
+            // Reflected back inside
+            p = hit.p;
+            w = wi;
+        }
+
+        None
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accelerators::simple_list::SimpleList;
+    use crate::core::primitive::Primitive;
+    use crate::core::vector::Point3f;
+    use crate::microfacets::trowbridge_reitz_distribution::TrowbridgeReitzDistribution;
+    use crate::primitives::sphere::Sphere;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const WALKS: usize = 10_000;
+
+    fn boundary(eta: f64) -> DielectricBxDF<TrowbridgeReitzDistribution> {
+        DielectricBxDF::new(eta, TrowbridgeReitzDistribution::new(0.0, 0.0))
+    }
+
+    /// Walks into the top of the unit sphere in `accelerator`, returning every exit.
+    fn walk<'a>(
+        bssrdf: &dyn Bssrdf,
+        accelerator: &SimpleList<'a>,
+    ) -> Vec<Option<BssrdfSample<'a>>> {
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let entry = Interaction::new_on_surface(Point3f::new(0.0, 0.0, 1.0), 1.0, n, n);
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+
+        (0..WALKS)
+            .map(|_| bssrdf.sample_exit(&entry, &-n, accelerator, &mut rng))
+            .collect()
+    }
+
+    fn mean_weight(exits: &[Option<BssrdfSample>]) -> Color3f {
+        let total = exits
+            .iter()
+            .flatten()
+            .fold(Color3f::default(), |total, exit| total + exit.weight);
+        total / exits.len() as f64
+    }
+
+    #[test]
+    fn lossless_medium_conserves_energy() {
+        let sphere = Sphere::new(Point3f::new(0.0, 0.0, 0.0), 1.0);
+        let primitives = vec![&sphere as &dyn Primitive];
+        let sigma_s = Color3f::new(1.0, 2.0, 4.0);
+        let bssrdf = RandomWalkBssrdf::new(boundary(1.0), Color3f::default(), sigma_s);
+        let exits = walk(&bssrdf, &SimpleList::new(&primitives));
+
+        let mean = mean_weight(&exits);
+        assert!(
+            (mean - Color3f::new(1.0, 1.0, 1.0)).abs().max_component() < 2e-2,
+            "mean {:?}",
+            mean
+        );
+        for exit in exits.iter().flatten() {
+            let n = exit.interaction.n.unwrap();
+            assert!((exit.interaction.p.length() - 1.0).abs() < 1e-6);
+            let wo = exit.interaction.wo;
+            assert!(wo.dot(&n) > 0.0);
+            // Leaves outwards, diffusely
+            let leaving = exit
+                .bsdf
+                .sample_f(&wo, 0.5, &Point2f::new(0.3, 0.6))
+                .unwrap();
+            assert!(leaving.wi.dot(&n) > 0.0);
+            assert!(!leaving.specular);
+        }
+    }
+
+    #[test]
+    fn boundary_reflects_inside() {
+        let sphere = Sphere::new(Point3f::new(0.0, 0.0, 0.0), 1.0);
+        let primitives = vec![&sphere as &dyn Primitive];
+        // Leaving undoes the compression of radiance on the way in
+        let eta = 1.33;
+        let bssrdf = RandomWalkBssrdf::new(
+            boundary(eta),
+            Color3f::default(),
+            Color3f::new(4.0, 4.0, 4.0),
+        );
+        let mean = mean_weight(&walk(&bssrdf, &SimpleList::new(&primitives)));
+        assert!((mean.x / (eta * eta) - 1.0).abs() < 3e-2, "mean {:?}", mean);
+    }
+
+    #[test]
+    fn absorption_tints() {
+        let sphere = Sphere::new(Point3f::new(0.0, 0.0, 0.0), 1.0);
+        let primitives = vec![&sphere as &dyn Primitive];
+        let albedo = Color3f::new(0.9, 0.5, 0.1);
+        let (sigma_a, sigma_s) = coefficients_from_albedo(&albedo, &Color3f::new(0.2, 0.2, 0.2));
+        let bssrdf = RandomWalkBssrdf::new(boundary(1.0), sigma_a, sigma_s);
+        let mean = mean_weight(&walk(&bssrdf, &SimpleList::new(&primitives)));
+
+        assert!(mean.x > mean.y && mean.y > mean.z, "mean {:?}", mean);
+        assert!(mean.x < 1.0 && mean.z > 0.0);
+    }
+
+    #[test]
+    fn albedo_inversion() {
+        let mean_free_path = Color3f::new(1.0, 0.5, 0.1);
+        let (sigma_a, sigma_s) =
+            coefficients_from_albedo(&Color3f::new(1.0, 1.0, 1.0), &mean_free_path);
+        // A white medium barely absorbs
+        assert!((sigma_a / (sigma_a + sigma_s)).max_component() < 2e-3);
+        // Shorter paths mean denser media
+        let sigma_t = sigma_a + sigma_s;
+        assert!(sigma_t.x < sigma_t.y && sigma_t.y < sigma_t.z);
+
+        let (dark_a, _) = coefficients_from_albedo(&Color3f::new(0.2, 0.2, 0.2), &mean_free_path);
+        assert!(dark_a.x > sigma_a.x);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,217 @@
+use crate::core::bxdf::{abs_cos_theta, flip_z, reflect, same_hemisphere, BsdfSample, BxDF};
+use crate::core::fresnel::{fr_conductor, fr_thin_film_conductor, ComplexIor, ThinFilm};
+use crate::core::microfacet::MicrofacetDistribution;
+use crate::core::vector::{Color3f, Point2f, Vec3f};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Reflection off of a metal, with color coming entirely from the Fresnel term.
+/// With a smooth distribution this is a perfect mirror,
+/// otherwise light scatters off of microfacets.
+/// Both sides of the surface reflect the same way.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConductorBxDF<D> {
+    ior: ComplexIor,
+    distribution: D,
+
+    /// An optional film on top of the metal, such as an oxide layer
+    film: Option<ThinFilm>,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<D: MicrofacetDistribution> ConductorBxDF<D> {
+    pub fn new(ior: ComplexIor, distribution: D) -> Self {
+        Self {
+            ior,
+            distribution,
+            film: None,
+        }
+    }
+
+    pub fn with_thin_film(self, film: ThinFilm) -> Self {
+        Self {
+            film: Some(film),
+            ..self
+        }
+    }
+
+    fn fresnel(&self, cos_theta_i: f64) -> Color3f {
+        match &self.film {
+            Some(film) => fr_thin_film_conductor(cos_theta_i, &self.ior, film),
+            None => fr_conductor(cos_theta_i, &self.ior),
+        }
+    }
+}
+
+impl<D: MicrofacetDistribution> BxDF for ConductorBxDF<D> {
+    fn f(&self, wo: &Vec3f, wi: &Vec3f) -> Color3f {
+        if !same_hemisphere(wo, wi) || self.distribution.effectively_smooth() {
+            return Color3f::default();
+        }
+        let flip = wo.z < 0.0;
+        let (wo, wi) = (flip_z(wo, flip), flip_z(wi, flip));
+
+        let wm = wo + wi;
+        if wm.length_sq() == 0.0 {
+            return Color3f::default();
+        }
+        let wm = wm.normalize();
+
+        let fresnel = self.fresnel(wo.dot(&wm).abs());
+        fresnel * (self.distribution.d(&wm) * self.distribution.g(&wo, &wi) / (4.0 * wo.z * wi.z))
+    }
+
+    fn sample_f(&self, wo: &Vec3f, _uc: f64, u: &Point2f) -> Option<BsdfSample> {
+        if self.distribution.effectively_smooth() {
+            let wi = Vec3f::new(-wo.x, -wo.y, wo.z);
+            let f = self.fresnel(abs_cos_theta(&wi)) / abs_cos_theta(&wi);
+            return Some(BsdfSample::new_specular(f, wi, 1.0));
+        }
+
+        let flip = wo.z < 0.0;
+        let wo = flip_z(wo, flip);
+        if wo.z == 0.0 {
+            return None;
+        }
+
+        let wm = self.distribution.sample_wm(&wo, u);
+        let wi = reflect(&wo, &wm);
+        if !same_hemisphere(&wo, &wi) {
+            return None;
+        }
+
+        let pdf = self.distribution.pdf(&wo, &wm) / (4.0 * wo.dot(&wm).abs());
+        let fresnel = self.fresnel(wo.dot(&wm).abs());
+        let f = fresnel
+            * (self.distribution.d(&wm) * self.distribution.g(&wo, &wi) / (4.0 * wo.z * wi.z));
+        Some(BsdfSample::new(f, flip_z(&wi, flip), pdf))
+    }
+
+    fn pdf(&self, wo: &Vec3f, wi: &Vec3f) -> f64 {
+        if !same_hemisphere(wo, wi) || self.distribution.effectively_smooth() {
+            return 0.0;
+        }
+        let flip = wo.z < 0.0;
+        let (wo, wi) = (flip_z(wo, flip), flip_z(wi, flip));
+
+        let wm = wo + wi;
+        if wm.length_sq() == 0.0 {
+            return 0.0;
+        }
+        let wm = wm.normalize();
+        self.distribution.pdf(&wo, &wm) / (4.0 * wo.dot(&wm).abs())
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bxdfs::test_utils::{
+        assert_chi_square, assert_samples_consistent, directional_albedo, test_directions,
+    };
+    use crate::microfacets::beckmann_distribution::BeckmannDistribution;
+    use crate::microfacets::trowbridge_reitz_distribution::TrowbridgeReitzDistribution;
+
+    #[test]
+    fn smooth_mirror() {
+        let bxdf = ConductorBxDF::new(
+            ComplexIor::SILVER,
+            TrowbridgeReitzDistribution::new(0.0, 0.0),
+        );
+        let wo = Vec3f::new(0.6, 0.0, 0.8);
+
+        let sample = bxdf.sample_f(&wo, 0.5, &Point2f::new(0.5, 0.5)).unwrap();
+        assert!(sample.specular);
+        assert_eq!(sample.wi, Vec3f::new(-0.6, 0.0, 0.8));
+        let throughput = sample.f * (sample.wi.z / sample.pdf);
+        assert!((throughput - fr_conductor(0.8, &ComplexIor::SILVER)).length() < 1e-12);
+        assert!(bxdf.f(&wo, &sample.wi).is_black());
+    }
+
+    #[test]
+    fn rough_conserves_energy() {
+        for ior in [
+            ComplexIor::GOLD,
+            ComplexIor::COPPER,
+            ComplexIor::ALUMINUM,
+            ComplexIor::SILVER,
+        ] {
+            let bxdf = ConductorBxDF::new(ior, TrowbridgeReitzDistribution::new(0.5, 0.5));
+            for wo in test_directions() {
+                let albedo = directional_albedo(&bxdf, &wo, 10_000);
+                assert!(albedo.max_component() <= 1.0, "albedo {:?}", albedo);
+            }
+        }
+    }
+
+    #[test]
+    fn thin_film_shifts_with_angle() {
+        // A titanium dioxide layer, as on anodized metal
+        let film = ThinFilm::new(250.0, 2.4);
+        let bxdf = ConductorBxDF::new(
+            ComplexIor::ALUMINUM,
+            TrowbridgeReitzDistribution::new(0.0, 0.0),
+        )
+        .with_thin_film(film);
+
+        let throughput = |wo: Vec3f| {
+            let sample = bxdf.sample_f(&wo, 0.5, &Point2f::new(0.5, 0.5)).unwrap();
+            sample.f * (sample.wi.z / sample.pdf)
+        };
+        let head_on = throughput(Vec3f::new(0.0, 0.0, 1.0));
+        let oblique = throughput(Vec3f::new(0.8, 0.0, 0.6));
+        assert!(head_on.max_component() <= 1.0 && oblique.max_component() <= 1.0);
+        assert!((head_on - oblique).abs().max_component() > 1e-2);
+
+        let rough =
+            ConductorBxDF::new(ComplexIor::GOLD, TrowbridgeReitzDistribution::new(0.3, 0.3))
+                .with_thin_film(film);
+        for wo in test_directions() {
+            assert_samples_consistent(&rough, &wo);
+            assert!(directional_albedo(&rough, &wo, 10_000).max_component() <= 1.0);
+        }
+    }
+
+    #[test]
+    fn rough_samples_consistent() {
+        let bxdf = ConductorBxDF::new(ComplexIor::GOLD, TrowbridgeReitzDistribution::new(0.3, 0.3));
+        for wo in test_directions() {
+            assert_samples_consistent(&bxdf, &wo);
+        }
+    }
+
+    #[test]
+    fn beckmann_samples_consistent() {
+        let bxdf = ConductorBxDF::new(ComplexIor::ALUMINUM, BeckmannDistribution::new(0.2, 0.5));
+        for wo in test_directions() {
+            assert_samples_consistent(&bxdf, &wo);
+            assert_chi_square(&bxdf, &wo);
+        }
+    }
+
+    #[test]
+    fn rough_chi_square() {
+        let bxdf = ConductorBxDF::new(
+            ComplexIor::COPPER,
+            TrowbridgeReitzDistribution::new(0.3, 0.3),
+        );
+        for wo in test_directions() {
+            assert_chi_square(&bxdf, &wo);
+        }
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,457 @@
+use crate::core::bxdf::{
+    abs_cos_theta, cos_theta, reflect, refract, same_hemisphere, BsdfSample, BxDF, ScatterLobes,
+    TransportMode,
+};
+use crate::core::fresnel::{fr_dielectric, fr_thin_film_dielectric, ThinFilm};
+use crate::core::microfacet::MicrofacetDistribution;
+use crate::core::vector::{Color3f, Point2f, Vec3f};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Reflection and transmission at the boundary between two dielectrics, such as glass.
+/// The surface normal is taken to point outside, so directions with a negative z
+/// are inside the object.
+/// With a smooth distribution this is a perfect specular interface,
+/// otherwise light scatters off of microfacets (Walter et al. 2007).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DielectricBxDF<D> {
+    /// The index of refraction inside relative to outside
+    eta: f64,
+    distribution: D,
+
+    /// An optional film on the outside of the interface
+    film: Option<ThinFilm>,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<D: MicrofacetDistribution> DielectricBxDF<D> {
+    pub fn new(eta: f64, distribution: D) -> Self {
+        Self {
+            eta,
+            distribution,
+            film: None,
+        }
+    }
+
+    pub fn with_thin_film(self, film: ThinFilm) -> Self {
+        Self {
+            film: Some(film),
+            ..self
+        }
+    }
+
+    pub fn is_specular(&self) -> bool {
+        self.eta == 1.0 || self.distribution.effectively_smooth()
+    }
+
+    /// Finds the microfacet normal that scatters `wo` into `wi`,
+    /// along with the relative index of refraction along `wi`.
+    fn generalized_half_vector(&self, wo: &Vec3f, wi: &Vec3f) -> Option<(Vec3f, f64)> {
+        let cos_theta_o = cos_theta(wo);
+        let cos_theta_i = cos_theta(wi);
+        if cos_theta_o == 0.0 || cos_theta_i == 0.0 {
+            return None;
+        }
+
+        let reflected = cos_theta_o * cos_theta_i > 0.0;
+        let etap = match (reflected, cos_theta_o > 0.0) {
+            (true, _) => 1.0,
+            (false, true) => self.eta,
+            (false, false) => 1.0 / self.eta,
+        };
+
+        let wm = *wi * etap + *wo;
+        if wm.length_sq() == 0.0 {
+            return None;
+        }
+        let mut wm = wm.normalize();
+        if wm.z < 0.0 {
+            wm = -wm;
+        }
+
+        // Discard back facing microfacets
+        if wm.dot(wi) * cos_theta_i < 0.0 || wm.dot(wo) * cos_theta_o < 0.0 {
+            return None;
+        }
+        Some((wm, etap))
+    }
+
+    /// The reflectance for a given cosine with the normal, colored only by a film.
+    fn fresnel(&self, cos_theta_i: f64) -> Color3f {
+        match &self.film {
+            Some(film) => fr_thin_film_dielectric(cos_theta_i, self.eta, film),
+            None => Color3f::new(1.0, 1.0, 1.0) * fr_dielectric(cos_theta_i, self.eta),
+        }
+    }
+
+    /// The probabilities of choosing reflection and transmission, given the reflectance,
+    /// or [None] if none of the allowed lobes can scatter.
+    fn lobe_probabilities(r: f64, lobes: ScatterLobes) -> Option<(f64, f64)> {
+        let r = if lobes.reflection() { r } else { 0.0 };
+        let t = if lobes.transmission() { 1.0 - r } else { 0.0 };
+        if r + t == 0.0 {
+            return None;
+        }
+        Some((r / (r + t), t / (r + t)))
+    }
+
+    /// The scale applied to transmitted values along `wi` with relative index `etap`.
+    fn transmission_scale(etap: f64, mode: TransportMode) -> f64 {
+        match mode {
+            // Radiance is compressed into the smaller solid angle on the denser side
+            TransportMode::Radiance => 1.0 / (etap * etap),
+            TransportMode::Importance => 1.0,
+        }
+    }
+
+    fn sample_specular(
+        &self,
+        wo: &Vec3f,
+        uc: f64,
+        mode: TransportMode,
+        lobes: ScatterLobes,
+    ) -> Option<BsdfSample> {
+        let r = self.fresnel(cos_theta(wo));
+        let t = Color3f::new(1.0, 1.0, 1.0) - r;
+        let (pr, pt) = Self::lobe_probabilities(r.average(), lobes)?;
+
+        if uc < pr {
+            let wi = Vec3f::new(-wo.x, -wo.y, wo.z);
+            let f = r / abs_cos_theta(&wi);
+            return Some(BsdfSample::new_specular(f, wi, pr));
+        }
+
+        let (wi, etap) = refract(wo, &Vec3f::new(0.0, 0.0, 1.0), self.eta)?;
+        let f = t * (Self::transmission_scale(etap, mode) / abs_cos_theta(&wi));
+        let mut sample = BsdfSample::new_specular(f, wi, pt);
+        sample.eta = etap;
+        Some(sample)
+    }
+
+    /// [BxDF::f] for the given kind of transport.
+    pub fn f_with(&self, wo: &Vec3f, wi: &Vec3f, mode: TransportMode) -> Color3f {
+        if self.is_specular() {
+            return Color3f::default();
+        }
+        let Some((wm, etap)) = self.generalized_half_vector(wo, wi) else {
+            return Color3f::default();
+        };
+
+        let cos_theta_o = cos_theta(wo);
+        let cos_theta_i = cos_theta(wi);
+        let fresnel = self.fresnel(wo.dot(&wm));
+        let d = self.distribution.d(&wm);
+        let g = self.distribution.g(wo, wi);
+
+        if same_hemisphere(wo, wi) {
+            fresnel * (d * g / (4.0 * cos_theta_i * cos_theta_o).abs())
+        } else {
+            let denom = (wi.dot(&wm) + wo.dot(&wm) / etap).powi(2) * cos_theta_i * cos_theta_o;
+            (Color3f::new(1.0, 1.0, 1.0) - fresnel)
+                * (d * g
+                    * (wi.dot(&wm) * wo.dot(&wm) / denom).abs()
+                    * Self::transmission_scale(etap, mode))
+        }
+    }
+
+    /// [BxDF::sample_f] for the given kind of transport, only sampling the allowed lobes.
+    pub fn sample_f_with(
+        &self,
+        wo: &Vec3f,
+        uc: f64,
+        u: &Point2f,
+        mode: TransportMode,
+        lobes: ScatterLobes,
+    ) -> Option<BsdfSample> {
+        if self.is_specular() {
+            return self.sample_specular(wo, uc, mode, lobes);
+        }
+
+        let wm = self.distribution.sample_wm(wo, u);
+        let r = self.fresnel(wo.dot(&wm));
+        let (pr, pt) = Self::lobe_probabilities(r.average(), lobes)?;
+        let microfacet_pdf = self.distribution.pdf(wo, &wm);
+
+        if uc < pr {
+            let wi = reflect(wo, &wm);
+            if !same_hemisphere(wo, &wi) {
+                return None;
+            }
+
+            let pdf = microfacet_pdf / (4.0 * wo.dot(&wm).abs()) * pr;
+            let f = r
+                * (self.distribution.d(&wm) * self.distribution.g(wo, &wi)
+                    / (4.0 * cos_theta(&wi) * cos_theta(wo)));
+            return Some(BsdfSample::new(f, wi, pdf));
+        }
+
+        let (wi, etap) = refract(wo, &wm, self.eta)?;
+        if same_hemisphere(wo, &wi) || wi.z == 0.0 {
+            return None;
+        }
+
+        let denom = (wi.dot(&wm) + wo.dot(&wm) / etap).powi(2);
+        let dwm_dwi = wi.dot(&wm).abs() / denom;
+        let pdf = microfacet_pdf * dwm_dwi * pt;
+
+        let f = (Color3f::new(1.0, 1.0, 1.0) - r)
+            * (self.distribution.d(&wm)
+                * self.distribution.g(wo, &wi)
+                * (wi.dot(&wm) * wo.dot(&wm) / (cos_theta(&wi) * cos_theta(wo) * denom)).abs()
+                * Self::transmission_scale(etap, mode));
+
+        let mut sample = BsdfSample::new(f, wi, pdf);
+        sample.eta = etap;
+        Some(sample)
+    }
+
+    /// The density of [DielectricBxDF::sample_f_with] restricted to `lobes`.
+    pub fn pdf_with(&self, wo: &Vec3f, wi: &Vec3f, lobes: ScatterLobes) -> f64 {
+        if self.is_specular() {
+            return 0.0;
+        }
+        let Some((wm, etap)) = self.generalized_half_vector(wo, wi) else {
+            return 0.0;
+        };
+
+        let r = self.fresnel(wo.dot(&wm)).average();
+        let Some((pr, pt)) = Self::lobe_probabilities(r, lobes) else {
+            return 0.0;
+        };
+        let microfacet_pdf = self.distribution.pdf(wo, &wm);
+
+        if same_hemisphere(wo, wi) {
+            microfacet_pdf / (4.0 * wo.dot(&wm).abs()) * pr
+        } else {
+            let denom = (wi.dot(&wm) + wo.dot(&wm) / etap).powi(2);
+            microfacet_pdf * wi.dot(&wm).abs() / denom * pt
+        }
+    }
+}
+
+impl<D: MicrofacetDistribution> BxDF for DielectricBxDF<D> {
+    fn f(&self, wo: &Vec3f, wi: &Vec3f) -> Color3f {
+        self.f_with(wo, wi, TransportMode::Radiance)
+    }
+
+    fn sample_f(&self, wo: &Vec3f, uc: f64, u: &Point2f) -> Option<BsdfSample> {
+        self.sample_f_with(wo, uc, u, TransportMode::Radiance, ScatterLobes::All)
+    }
+
+    fn pdf(&self, wo: &Vec3f, wi: &Vec3f) -> f64 {
+        self.pdf_with(wo, wi, ScatterLobes::All)
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bxdfs::test_utils::{
+        assert_chi_square, assert_samples_consistent, directional_albedo, rng, test_directions,
+    };
+    use crate::microfacets::beckmann_distribution::BeckmannDistribution;
+    use crate::microfacets::trowbridge_reitz_distribution::TrowbridgeReitzDistribution;
+    use rand::Rng;
+
+    fn smooth_glass() -> DielectricBxDF<TrowbridgeReitzDistribution> {
+        DielectricBxDF::new(1.5, TrowbridgeReitzDistribution::new(0.0, 0.0))
+    }
+
+    fn rough_glass() -> DielectricBxDF<TrowbridgeReitzDistribution> {
+        DielectricBxDF::new(1.5, TrowbridgeReitzDistribution::new(0.3, 0.3))
+    }
+
+    #[test]
+    fn smooth_entering_splits_by_fresnel() {
+        let bxdf = smooth_glass();
+        let wo = Vec3f::new(0.6, 0.0, 0.8);
+        let r = fr_dielectric(0.8, 1.5);
+
+        let reflected = bxdf.sample_f(&wo, 0.0, &Point2f::default()).unwrap();
+        assert!(reflected.specular);
+        assert!((reflected.wi - Vec3f::new(-0.6, 0.0, 0.8)).length() < 1e-12);
+        assert!((reflected.pdf - r).abs() < 1e-12);
+        assert_eq!(reflected.eta, 1.0);
+
+        let transmitted = bxdf.sample_f(&wo, 0.999, &Point2f::default()).unwrap();
+        assert!(transmitted.specular);
+        assert!(transmitted.wi.z < 0.0);
+        assert!((transmitted.pdf - (1.0 - r)).abs() < 1e-12);
+        assert_eq!(transmitted.eta, 1.5);
+
+        // Throughput is R + T / eta^2 when entering the denser medium
+        let albedo = directional_albedo(&bxdf, &wo, 10_000);
+        let expected = r + (1.0 - r) / (1.5 * 1.5);
+        assert!((albedo.x - expected).abs() < 1e-2, "albedo {:?}", albedo);
+    }
+
+    #[test]
+    fn smooth_exiting() {
+        let bxdf = smooth_glass();
+        let wo = Vec3f::new(0.3, 0.0, -(1.0 - 0.3 * 0.3_f64).sqrt());
+
+        let transmitted = bxdf.sample_f(&wo, 0.999, &Point2f::default()).unwrap();
+        assert!(transmitted.wi.z > 0.0);
+        assert_eq!(transmitted.eta, 1.0 / 1.5);
+        // Snell's law: sin(theta_t) = eta * sin(theta_i)
+        let sin_theta_t = (1.0 - transmitted.wi.z * transmitted.wi.z).sqrt();
+        assert!((sin_theta_t - 1.5 * 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn smooth_total_internal_reflection() {
+        let bxdf = smooth_glass();
+        let wo = Vec3f::new(0.9, 0.0, -0.1).normalize();
+
+        for uc in [0.0, 0.5, 0.999] {
+            let sample = bxdf.sample_f(&wo, uc, &Point2f::default()).unwrap();
+            assert!(sample.wi.z < 0.0);
+            assert_eq!(sample.pdf, 1.0);
+        }
+    }
+
+    #[test]
+    fn smooth_has_no_density() {
+        let bxdf = smooth_glass();
+        let wo = Vec3f::new(0.0, 0.0, 1.0);
+        assert!(bxdf.f(&wo, &wo).is_black());
+        assert_eq!(bxdf.pdf(&wo, &wo), 0.0);
+    }
+
+    #[test]
+    fn rough_samples_consistent() {
+        let bxdf = rough_glass();
+        for wo in test_directions() {
+            assert_samples_consistent(&bxdf, &wo);
+        }
+    }
+
+    #[test]
+    fn rough_chi_square() {
+        let bxdf = rough_glass();
+        for wo in test_directions() {
+            assert_chi_square(&bxdf, &wo);
+        }
+    }
+
+    #[test]
+    fn beckmann_chi_square() {
+        let bxdf = DielectricBxDF::new(1.33, BeckmannDistribution::new(0.4, 0.4));
+        for wo in test_directions() {
+            assert_samples_consistent(&bxdf, &wo);
+            assert_chi_square(&bxdf, &wo);
+        }
+    }
+
+    #[test]
+    fn restricted_lobes() {
+        let wo = Vec3f::new(0.3, 0.2, 0.9).normalize();
+        for bxdf in [smooth_glass(), rough_glass()] {
+            for uc in [0.0, 0.5, 0.999] {
+                let u = Point2f::new(0.3, 0.7);
+                let reflected = bxdf
+                    .sample_f_with(
+                        &wo,
+                        uc,
+                        &u,
+                        TransportMode::Radiance,
+                        ScatterLobes::Reflection,
+                    )
+                    .unwrap();
+                assert!(reflected.wi.z > 0.0);
+
+                let transmitted = bxdf
+                    .sample_f_with(
+                        &wo,
+                        uc,
+                        &u,
+                        TransportMode::Importance,
+                        ScatterLobes::Transmission,
+                    )
+                    .unwrap();
+                assert!(transmitted.wi.z < 0.0);
+                // Importance is not scaled by the relative index
+                let radiance = bxdf
+                    .sample_f_with(
+                        &wo,
+                        uc,
+                        &u,
+                        TransportMode::Radiance,
+                        ScatterLobes::Transmission,
+                    )
+                    .unwrap();
+                assert!((transmitted.f.x / (1.5 * 1.5) - radiance.f.x).abs() < 1e-9);
+            }
+        }
+
+        // Total internal reflection leaves nothing to transmit
+        let inside = Vec3f::new(0.9, 0.0, -0.1).normalize();
+        let sample = smooth_glass().sample_f_with(
+            &inside,
+            0.5,
+            &Point2f::default(),
+            TransportMode::Radiance,
+            ScatterLobes::Transmission,
+        );
+        assert!(sample.is_none());
+    }
+
+    #[test]
+    fn thin_film_tints_without_creating_energy() {
+        let film = ThinFilm::new(400.0, 1.33);
+        for bxdf in [
+            smooth_glass().with_thin_film(film),
+            rough_glass().with_thin_film(film),
+        ] {
+            for wo in test_directions() {
+                // Importance is not rescaled by refraction, so it can at most be conserved
+                let mut rng = rng();
+                let mut albedo = Color3f::default();
+                for _ in 0..10_000 {
+                    let u = Point2f::new(rng.gen(), rng.gen());
+                    let mode = TransportMode::Importance;
+                    if let Some(sample) =
+                        bxdf.sample_f_with(&wo, rng.gen(), &u, mode, ScatterLobes::All)
+                    {
+                        if sample.pdf > 0.0 {
+                            albedo += sample.f * (abs_cos_theta(&sample.wi) / sample.pdf);
+                        }
+                    }
+                }
+                albedo /= 10_000.0;
+                assert!(albedo.max_component() <= 1.0 + 1e-2, "albedo {:?}", albedo);
+            }
+            assert_samples_consistent(&bxdf, &Vec3f::new(0.3, 0.2, 0.9).normalize());
+        }
+
+        let wo = Vec3f::new(0.6, 0.0, 0.8);
+        let reflected = smooth_glass()
+            .with_thin_film(film)
+            .sample_f(&wo, 0.0, &Point2f::default())
+            .unwrap();
+        assert!((reflected.f.x - reflected.f.z).abs() > 1e-3);
+    }
+
+    #[test]
+    fn rough_is_reciprocal_in_reflection() {
+        let bxdf = rough_glass();
+        let wo = Vec3f::new(0.3, 0.2, 0.9).normalize();
+        let wi = Vec3f::new(-0.5, 0.1, 0.6).normalize();
+        assert!((bxdf.f(&wo, &wi) - bxdf.f(&wi, &wo)).length() < 1e-9);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,101 @@
+use std::f64::consts::FRAC_1_PI;
+
+use crate::core::bxdf::{abs_cos_theta, same_hemisphere, BsdfSample, BxDF};
+use crate::core::vector::{Color3f, Point2f, Vec3f};
+use crate::math::sampling::{cosine_hemisphere_pdf, sample_cosine_hemisphere};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Ideal diffuse reflection, scattering equally in every direction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LambertianBxDF {
+    albedo: Color3f,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl LambertianBxDF {
+    pub fn new(albedo: Color3f) -> Self {
+        Self { albedo }
+    }
+}
+
+impl BxDF for LambertianBxDF {
+    fn f(&self, wo: &Vec3f, wi: &Vec3f) -> Color3f {
+        if !same_hemisphere(wo, wi) {
+            return Color3f::default();
+        }
+        self.albedo * FRAC_1_PI
+    }
+
+    fn sample_f(&self, wo: &Vec3f, _uc: f64, u: &Point2f) -> Option<BsdfSample> {
+        let mut wi = sample_cosine_hemisphere(u);
+        if wo.z < 0.0 {
+            wi.z = -wi.z;
+        }
+
+        let pdf = cosine_hemisphere_pdf(abs_cos_theta(&wi));
+        Some(BsdfSample::new(self.albedo * FRAC_1_PI, wi, pdf))
+    }
+
+    fn pdf(&self, wo: &Vec3f, wi: &Vec3f) -> f64 {
+        if !same_hemisphere(wo, wi) {
+            return 0.0;
+        }
+        cosine_hemisphere_pdf(abs_cos_theta(wi))
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bxdfs::test_utils::{
+        assert_chi_square, assert_samples_consistent, directional_albedo, test_directions,
+    };
+
+    #[test]
+    fn conserves_energy() {
+        let bxdf = LambertianBxDF::new(Color3f::new(1.0, 1.0, 1.0));
+        for wo in test_directions() {
+            let albedo = directional_albedo(&bxdf, &wo, 10_000);
+            assert!((albedo.x - 1.0).abs() < 1e-9, "albedo {:?}", albedo);
+        }
+    }
+
+    #[test]
+    fn scales_with_albedo() {
+        let bxdf = LambertianBxDF::new(Color3f::new(0.2, 0.5, 0.8));
+        let albedo = directional_albedo(&bxdf, &Vec3f::new(0.0, 0.0, 1.0), 1_000);
+        assert!((albedo - Color3f::new(0.2, 0.5, 0.8)).length() < 1e-9);
+    }
+
+    #[test]
+    fn samples_consistent() {
+        let bxdf = LambertianBxDF::new(Color3f::new(0.5, 0.5, 0.5));
+        for wo in test_directions() {
+            assert_samples_consistent(&bxdf, &wo);
+        }
+    }
+
+    #[test]
+    fn chi_square() {
+        let bxdf = LambertianBxDF::new(Color3f::new(0.5, 0.5, 0.5));
+        for wo in test_directions() {
+            assert_chi_square(&bxdf, &wo);
+        }
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
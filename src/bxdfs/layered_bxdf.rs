@@ -0,0 +1,429 @@
+use std::collections::hash_map::DefaultHasher;
+use std::f64::consts::PI;
+use std::hash::{Hash, Hasher};
+
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+use crate::bxdfs::dielectric_bxdf::DielectricBxDF;
+use crate::core::bxdf::{
+    abs_cos_theta, flip_z, same_hemisphere, BsdfSample, BxDF, ScatterLobes, TransportMode,
+};
+use crate::core::microfacet::MicrofacetDistribution;
+use crate::core::vector::{Color3f, Point2f, Vec3f};
+use crate::math::lerp;
+use crate::math::sampling::power_heuristic;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A dielectric coating over an opaque base [BxDF], such as varnish or car paint clearcoat.
+/// Light bouncing between the two interfaces is followed with a random walk
+/// that ignores where on the surface it travels (Guo et al. 2018, as in pbrt-v4),
+/// so [BxDF::f] and [BxDF::pdf] are unbiased but noisy estimates.
+/// The coating is two sided, and anything the base transmits is treated as absorbed.
+pub struct LayeredBxDF<D> {
+    top: DielectricBxDF<D>,
+    bottom: Box<dyn BxDF>,
+
+    /// The thickness of the coating
+    thickness: f64,
+
+    /// The absorption coefficient of the coating, per unit thickness
+    absorption: Color3f,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// The number of bounces between the interfaces before the walk is cut short.
+const MAX_DEPTH: usize = 10;
+
+/// The number of random walks averaged by each estimate.
+const SAMPLES: usize = 1;
+
+impl<D: MicrofacetDistribution> LayeredBxDF<D> {
+    pub fn new(
+        top: DielectricBxDF<D>,
+        bottom: Box<dyn BxDF>,
+        thickness: f64,
+        absorption: Color3f,
+    ) -> Self {
+        Self {
+            top,
+            bottom,
+            thickness,
+            absorption,
+        }
+    }
+
+    /// The fraction of light surviving a crossing of the coating along `w`.
+    fn transmittance(&self, w: &Vec3f) -> Color3f {
+        let distance = self.thickness / abs_cos_theta(w);
+        self.absorption.map(|sigma| (-sigma * distance).exp())
+    }
+
+    /// Whether the walk should go on, scaling `beta` to keep the estimate unbiased if so.
+    fn russian_roulette(depth: usize, beta: &mut Color3f, rng: &mut SmallRng) -> bool {
+        let max = beta.max_component();
+        if depth <= 3 || max >= 0.25 {
+            return true;
+        }
+        let q = (1.0 - max).max(0.0);
+        if rng.gen::<f64>() < q {
+            return false;
+        }
+        *beta /= 1.0 - q;
+        true
+    }
+}
+
+/// A generator seeded from the arguments of an estimate,
+/// so that repeated evaluations with the same arguments agree.
+fn seeded_rng(values: &[f64]) -> SmallRng {
+    let mut hasher = DefaultHasher::new();
+    for value in values {
+        value.to_bits().hash(&mut hasher);
+    }
+    SmallRng::seed_from_u64(hasher.finish())
+}
+
+fn sample_point(rng: &mut SmallRng) -> Point2f {
+    Point2f::new(rng.gen(), rng.gen())
+}
+
+fn is_valid(sample: &BsdfSample) -> bool {
+    !sample.f.is_black() && sample.pdf > 0.0 && sample.wi.z != 0.0
+}
+
+impl<D: MicrofacetDistribution> BxDF for LayeredBxDF<D> {
+    fn f(&self, wo: &Vec3f, wi: &Vec3f) -> Color3f {
+        let flip = wo.z < 0.0;
+        let wo = flip_z(wo, flip);
+        let wi = flip_z(wi, flip);
+        if !same_hemisphere(&wo, &wi) {
+            return Color3f::default();
+        }
+
+        let mut f = self.top.f(&wo, &wi) * SAMPLES as f64;
+        let mut rng = seeded_rng(&[wo.x, wo.y, wo.z, wi.x, wi.y, wi.z]);
+
+        for _ in 0..SAMPLES {
+            let (uc, u) = (rng.gen(), sample_point(&mut rng));
+            let Some(wos) = self
+                .top
+                .sample_f_with(
+                    &wo,
+                    uc,
+                    &u,
+                    TransportMode::Radiance,
+                    ScatterLobes::Transmission,
+                )
+                .filter(is_valid)
+            else {
+                continue;
+            };
+
+            // Also enter along wi, for connections from the base out towards it.
+            // That crossing is traced backwards, so it carries importance
+            let (uc, u) = (rng.gen(), sample_point(&mut rng));
+            let Some(wis) = self
+                .top
+                .sample_f_with(
+                    &wi,
+                    uc,
+                    &u,
+                    TransportMode::Importance,
+                    ScatterLobes::Transmission,
+                )
+                .filter(is_valid)
+            else {
+                continue;
+            };
+
+            let mut beta = wos.f * (abs_cos_theta(&wos.wi) / wos.pdf);
+            let mut w = wos.wi;
+            for depth in 0..MAX_DEPTH {
+                if !Self::russian_roulette(depth, &mut beta, &mut rng) {
+                    break;
+                }
+                beta *= self.transmittance(&w);
+
+                if w.z > 0.0 {
+                    // Back at the coating, reflect inside
+                    let (uc, u) = (rng.gen(), sample_point(&mut rng));
+                    let Some(bs) = self
+                        .top
+                        .sample_f_with(
+                            &-w,
+                            uc,
+                            &u,
+                            TransportMode::Radiance,
+                            ScatterLobes::Reflection,
+                        )
+                        .filter(is_valid)
+                    else {
+                        break;
+                    };
+                    beta *= bs.f * (abs_cos_theta(&bs.wi) / bs.pdf);
+                    w = bs.wi;
+                    continue;
+                }
+
+                // At the base, connect to the exit along wi
+                let wt = if self.top.is_specular() {
+                    1.0
+                } else {
+                    power_heuristic(1, wis.pdf, 1, self.bottom.pdf(&-w, &-wis.wi))
+                };
+                f += beta
+                    * self.bottom.f(&-w, &-wis.wi)
+                    * self.transmittance(&wis.wi)
+                    * wis.f
+                    * (abs_cos_theta(&wis.wi) * wt / wis.pdf);
+
+                // Then scatter off of the base
+                let (uc, u) = (rng.gen(), sample_point(&mut rng));
+                let Some(bs) = self
+                    .bottom
+                    .sample_f(&-w, uc, &u)
+                    .filter(|bs| is_valid(bs) && bs.wi.z > 0.0)
+                else {
+                    break;
+                };
+                beta *= bs.f * (abs_cos_theta(&bs.wi) / bs.pdf);
+                w = bs.wi;
+
+                // And connect the scattered direction through a rough coating
+                if !self.top.is_specular() {
+                    let f_exit = self.top.f(&-w, &wi);
+                    if !f_exit.is_black() {
+                        let wt = if bs.specular {
+                            1.0
+                        } else {
+                            let exit_pdf = self.top.pdf_with(&-w, &wi, ScatterLobes::Transmission);
+                            power_heuristic(1, bs.pdf, 1, exit_pdf)
+                        };
+                        f += beta * self.transmittance(&bs.wi) * f_exit * wt;
+                    }
+                }
+            }
+        }
+
+        f / SAMPLES as f64
+    }
+
+    fn sample_f(&self, wo: &Vec3f, uc: f64, u: &Point2f) -> Option<BsdfSample> {
+        let flip = wo.z < 0.0;
+        let wo = flip_z(wo, flip);
+
+        let mut bs = self.top.sample_f(&wo, uc, u).filter(is_valid)?;
+        if bs.wi.z > 0.0 {
+            bs.wi = flip_z(&bs.wi, flip);
+            bs.pdf_is_proportional = true;
+            return Some(bs);
+        }
+
+        let mut rng = seeded_rng(&[wo.x, wo.y, wo.z, uc, u.x, u.y]);
+        let mut f = bs.f * abs_cos_theta(&bs.wi);
+        let mut pdf = bs.pdf;
+        let mut specular = bs.specular;
+        let mut w = bs.wi;
+
+        for depth in 0..MAX_DEPTH {
+            let rr_beta = f.max_component() / pdf;
+            if depth > 3 && rr_beta < 0.25 {
+                let q = (1.0 - rr_beta).max(0.0);
+                if rng.gen::<f64>() < q {
+                    return None;
+                }
+                pdf *= 1.0 - q;
+            }
+            f *= self.transmittance(&w);
+
+            let at_base = w.z < 0.0;
+            let (uc, u) = (rng.gen(), sample_point(&mut rng));
+            let bs = if at_base {
+                self.bottom
+                    .sample_f(&-w, uc, &u)
+                    .filter(|bs| is_valid(bs) && bs.wi.z > 0.0)?
+            } else {
+                self.top.sample_f(&-w, uc, &u).filter(is_valid)?
+            };
+
+            f *= bs.f;
+            pdf *= bs.pdf;
+            specular &= bs.specular;
+            w = bs.wi;
+
+            // Transmitted out through the coating
+            if !at_base && w.z > 0.0 {
+                let mut sample = BsdfSample::new(f, flip_z(&w, flip), pdf);
+                sample.specular = specular;
+                sample.pdf_is_proportional = true;
+                return Some(sample);
+            }
+            f = f * abs_cos_theta(&w);
+        }
+        None
+    }
+
+    fn pdf(&self, wo: &Vec3f, wi: &Vec3f) -> f64 {
+        let flip = wo.z < 0.0;
+        let wo = flip_z(wo, flip);
+        let wi = flip_z(wi, flip);
+        if !same_hemisphere(&wo, &wi) {
+            return 0.0;
+        }
+
+        let mut pdf_sum = self.top.pdf(&wo, &wi) * SAMPLES as f64;
+        let mut rng = seeded_rng(&[wi.x, wi.y, wi.z, wo.x, wo.y, wo.z]);
+
+        let mode = TransportMode::Radiance;
+        for _ in 0..SAMPLES {
+            // Estimate the density of transmission, reflection off the base, transmission
+            let (uc, u) = (rng.gen(), sample_point(&mut rng));
+            let wos = self
+                .top
+                .sample_f_with(&wo, uc, &u, mode, ScatterLobes::Transmission)
+                .filter(is_valid);
+            let (uc, u) = (rng.gen(), sample_point(&mut rng));
+            let wis = self
+                .top
+                .sample_f_with(&wi, uc, &u, mode, ScatterLobes::Transmission)
+                .filter(is_valid);
+            let (Some(wos), Some(wis)) = (wos, wis) else {
+                continue;
+            };
+
+            if self.top.is_specular() {
+                pdf_sum += self.bottom.pdf(&-wos.wi, &-wis.wi);
+                continue;
+            }
+
+            let (uc, u) = (rng.gen(), sample_point(&mut rng));
+            let Some(rs) = self.bottom.sample_f(&-wos.wi, uc, &u).filter(is_valid) else {
+                continue;
+            };
+            if rs.specular {
+                pdf_sum += self.top.pdf_with(&-rs.wi, &wi, ScatterLobes::Transmission);
+                continue;
+            }
+
+            // Combine the connections from either side
+            let r_pdf = self.bottom.pdf(&-wos.wi, &-wis.wi);
+            pdf_sum += power_heuristic(1, wis.pdf, 1, r_pdf) * r_pdf;
+            let t_pdf = self.top.pdf_with(&-rs.wi, &wi, ScatterLobes::Transmission);
+            pdf_sum += power_heuristic(1, rs.pdf, 1, t_pdf) * t_pdf;
+        }
+
+        // Mix in a uniform density to account for the paths the estimate misses
+        lerp(0.9, 1.0 / (4.0 * PI), pdf_sum / SAMPLES as f64)
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bxdfs::lambertian_bxdf::LambertianBxDF;
+    use crate::bxdfs::test_utils::{
+        assert_samples_consistent, directional_albedo, rng, test_directions,
+    };
+    use crate::math::sampling::sample_cosine_hemisphere;
+    use crate::microfacets::trowbridge_reitz_distribution::TrowbridgeReitzDistribution;
+
+    fn coated(
+        alpha: f64,
+        albedo: f64,
+        thickness: f64,
+        absorption: Color3f,
+    ) -> LayeredBxDF<TrowbridgeReitzDistribution> {
+        LayeredBxDF::new(
+            DielectricBxDF::new(1.5, TrowbridgeReitzDistribution::new(alpha, alpha)),
+            Box::new(LambertianBxDF::new(Color3f::new(albedo, albedo, albedo))),
+            thickness,
+            absorption,
+        )
+    }
+
+    /// Estimates the albedo by integrating [BxDF::f] rather than sampling it.
+    fn integrated_albedo(bxdf: &dyn BxDF, wo: &Vec3f, samples: usize) -> Color3f {
+        let mut rng = rng();
+        let mut total = Color3f::default();
+        for _ in 0..samples {
+            let wi = sample_cosine_hemisphere(&Point2f::new(rng.gen(), rng.gen()));
+            let wi = flip_z(&wi, wo.z < 0.0);
+            // The cosine cancels against the density
+            total += bxdf.f(wo, &wi) * PI;
+        }
+        total / samples as f64
+    }
+
+    #[test]
+    fn conserves_energy() {
+        // A lossless smooth coating over a white base only loses the truncated walks,
+        // a rough one also loses what its microfacets scatter more than once,
+        // which adds up near total internal reflection
+        for (alpha, min_albedo) in [(0.0, 0.85), (0.3, 0.6)] {
+            let bxdf = coated(alpha, 1.0, 0.1, Color3f::default());
+            for wo in test_directions() {
+                let albedo = directional_albedo(&bxdf, &wo, 20_000);
+                assert!(albedo.max_component() <= 1.0 + 1e-2, "albedo {:?}", albedo);
+                assert!(albedo.min_component() > min_albedo, "albedo {:?}", albedo);
+            }
+        }
+    }
+
+    #[test]
+    fn sampling_matches_evaluation() {
+        let bxdf = coated(0.3, 0.5, 0.1, Color3f::new(0.5, 1.0, 2.0));
+        for wo in test_directions() {
+            assert_samples_consistent(&bxdf, &wo);
+
+            let sampled = directional_albedo(&bxdf, &wo, 50_000);
+            let integrated = integrated_albedo(&bxdf, &wo, 50_000);
+            assert!(
+                (sampled - integrated).abs().max_component() < 2e-2,
+                "sampled {:?} != integrated {:?} for wo {:?}",
+                sampled,
+                integrated,
+                wo
+            );
+        }
+    }
+
+    #[test]
+    fn absorption_tints_with_thickness() {
+        let absorption = Color3f::new(0.0, 1.0, 4.0);
+        let wo = Vec3f::new(0.3, 0.2, 0.9).normalize();
+        let thin = directional_albedo(&coated(0.0, 0.8, 0.05, absorption), &wo, 20_000);
+        let thick = directional_albedo(&coated(0.0, 0.8, 0.5, absorption), &wo, 20_000);
+
+        assert!(thick.x > thick.y && thick.y > thick.z, "albedo {:?}", thick);
+        assert!(thick.y < thin.y && thick.z < thin.z);
+        // Red is not absorbed, so only the walk differs
+        assert!((thick.x - thin.x).abs() < 2e-2);
+    }
+
+    #[test]
+    fn estimates_are_repeatable() {
+        let bxdf = coated(0.3, 0.8, 0.1, Color3f::default());
+        let wo = Vec3f::new(0.3, 0.2, 0.9).normalize();
+        let wi = Vec3f::new(-0.5, 0.1, 0.6).normalize();
+        assert_eq!(bxdf.f(&wo, &wi), bxdf.f(&wo, &wi));
+        assert_eq!(bxdf.pdf(&wo, &wi), bxdf.pdf(&wo, &wi));
+        assert!(bxdf.f(&wo, &-wi).is_black());
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,9 @@
+pub mod conductor_bxdf;
+pub mod dielectric_bxdf;
+pub mod lambertian_bxdf;
+pub mod layered_bxdf;
+pub mod oren_nayar_bxdf;
+pub mod principled_bxdf;
+
+#[cfg(test)]
+pub mod test_utils;
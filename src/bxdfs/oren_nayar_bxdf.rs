@@ -0,0 +1,148 @@
+use std::f64::consts::FRAC_1_PI;
+
+use crate::core::bxdf::{
+    abs_cos_theta, cos_phi, same_hemisphere, sin_phi, sin_theta, BsdfSample, BxDF,
+};
+use crate::core::vector::{Color3f, Point2f, Vec3f};
+use crate::math::sampling::{cosine_hemisphere_pdf, sample_cosine_hemisphere};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Rough diffuse reflection, modeling the surface as a collection of
+/// V-shaped Lambertian microfacets (Oren and Nayar 1994, qualitative model).
+/// Retro-reflects more than [LambertianBxDF](super::lambertian_bxdf::LambertianBxDF),
+/// which suits clay, plaster and similar dusty surfaces.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrenNayarBxDF {
+    albedo: Color3f,
+    a: f64,
+    b: f64,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl OrenNayarBxDF {
+    /// `sigma` is the standard deviation of the microfacet angle, in degrees.
+    pub fn new(albedo: Color3f, sigma: f64) -> Self {
+        let sigma = sigma.to_radians();
+        let sigma2 = sigma * sigma;
+
+        Self {
+            albedo,
+            a: 1.0 - sigma2 / (2.0 * (sigma2 + 0.33)),
+            b: 0.45 * sigma2 / (sigma2 + 0.09),
+        }
+    }
+}
+
+impl BxDF for OrenNayarBxDF {
+    fn f(&self, wo: &Vec3f, wi: &Vec3f) -> Color3f {
+        if !same_hemisphere(wo, wi) {
+            return Color3f::default();
+        }
+
+        let sin_theta_i = sin_theta(wi);
+        let sin_theta_o = sin_theta(wo);
+
+        let max_cos = if sin_theta_i > 1e-4 && sin_theta_o > 1e-4 {
+            let d_cos = cos_phi(wi) * cos_phi(wo) + sin_phi(wi) * sin_phi(wo);
+            d_cos.max(0.0)
+        } else {
+            0.0
+        };
+
+        let (sin_alpha, tan_beta) = if abs_cos_theta(wi) > abs_cos_theta(wo) {
+            (sin_theta_o, sin_theta_i / abs_cos_theta(wi))
+        } else {
+            (sin_theta_i, sin_theta_o / abs_cos_theta(wo))
+        };
+
+        self.albedo * FRAC_1_PI * (self.a + self.b * max_cos * sin_alpha * tan_beta)
+    }
+
+    fn sample_f(&self, wo: &Vec3f, _uc: f64, u: &Point2f) -> Option<BsdfSample> {
+        let mut wi = sample_cosine_hemisphere(u);
+        if wo.z < 0.0 {
+            wi.z = -wi.z;
+        }
+
+        let pdf = cosine_hemisphere_pdf(abs_cos_theta(&wi));
+        Some(BsdfSample::new(self.f(wo, &wi), wi, pdf))
+    }
+
+    fn pdf(&self, wo: &Vec3f, wi: &Vec3f) -> f64 {
+        if !same_hemisphere(wo, wi) {
+            return 0.0;
+        }
+        cosine_hemisphere_pdf(abs_cos_theta(wi))
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bxdfs::lambertian_bxdf::LambertianBxDF;
+    use crate::bxdfs::test_utils::{
+        assert_chi_square, assert_samples_consistent, directional_albedo, test_directions,
+    };
+
+    #[test]
+    fn smooth_matches_lambertian() {
+        let albedo = Color3f::new(0.3, 0.6, 0.9);
+        let oren_nayar = OrenNayarBxDF::new(albedo, 0.0);
+        let lambertian = LambertianBxDF::new(albedo);
+
+        let wo = Vec3f::new(0.3, -0.2, 0.8).normalize();
+        let wi = Vec3f::new(-0.5, 0.1, 0.4).normalize();
+        assert!((oren_nayar.f(&wo, &wi) - lambertian.f(&wo, &wi)).length() < 1e-12);
+    }
+
+    #[test]
+    fn conserves_energy() {
+        for sigma in [10.0, 20.0, 45.0, 90.0] {
+            let bxdf = OrenNayarBxDF::new(Color3f::new(1.0, 1.0, 1.0), sigma);
+            for wo in test_directions() {
+                let albedo = directional_albedo(&bxdf, &wo, 20_000);
+                // The qualitative model gains a little over 1% at grazing angles
+                assert!(
+                    albedo.max_component() <= 1.02,
+                    "sigma {} wo {:?} albedo {:?}",
+                    sigma,
+                    wo,
+                    albedo
+                );
+                assert!(albedo.min_component() > 0.5, "albedo {:?}", albedo);
+            }
+        }
+    }
+
+    #[test]
+    fn samples_consistent() {
+        let bxdf = OrenNayarBxDF::new(Color3f::new(0.5, 0.5, 0.5), 30.0);
+        for wo in test_directions() {
+            assert_samples_consistent(&bxdf, &wo);
+        }
+    }
+
+    #[test]
+    fn chi_square() {
+        let bxdf = OrenNayarBxDF::new(Color3f::new(0.5, 0.5, 0.5), 30.0);
+        for wo in test_directions() {
+            assert_chi_square(&bxdf, &wo);
+        }
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,540 @@
+use std::f64::consts::{FRAC_1_PI, PI};
+
+use crate::bxdfs::dielectric_bxdf::DielectricBxDF;
+use crate::core::bxdf::{flip_z, reflect, same_hemisphere, BsdfSample, BxDF};
+use crate::core::microfacet::MicrofacetDistribution;
+use crate::core::vector::{Color3f, Point2f, Vec3f};
+use crate::math::sampling::{cosine_hemisphere_pdf, sample_cosine_hemisphere};
+use crate::microfacets::trowbridge_reitz_distribution::TrowbridgeReitzDistribution;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// The inputs of the principled model, every scalar in [0.0, 1.0].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrincipledParameters {
+    pub base_color: Color3f,
+    pub metallic: f64,
+    pub roughness: f64,
+
+    /// Scales the dielectric reflectance at normal incidence, 0.5 being 4%
+    pub specular: f64,
+
+    /// Tints the dielectric reflectance towards the base color
+    pub specular_tint: f64,
+
+    /// Stretches highlights along the tangent, 0.0 being isotropic
+    pub anisotropic: f64,
+
+    /// A soft retro-reflective rim, for cloth
+    pub sheen: f64,
+    pub sheen_tint: f64,
+
+    /// A second, white specular lobe on top of everything
+    pub clearcoat: f64,
+    pub clearcoat_gloss: f64,
+
+    /// The fraction of the dielectric base that refracts light instead of scattering diffusely
+    pub transmission: f64,
+}
+
+/// The Disney principled BSDF (Burley 2012, 2015), an artist friendly blend of
+/// a diffuse lobe with retro-reflection and sheen, an anisotropic specular lobe,
+/// a clearcoat lobe and a rough dielectric transmission lobe.
+/// Sampling first picks a lobe in proportion to its estimated reflectance.
+pub struct PrincipledBxDF {
+    lobes: Vec<Lobe>,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl Default for PrincipledParameters {
+    fn default() -> Self {
+        Self {
+            base_color: Color3f::new(0.8, 0.8, 0.8),
+            metallic: 0.0,
+            roughness: 0.5,
+            specular: 0.5,
+            specular_tint: 0.0,
+            anisotropic: 0.0,
+            sheen: 0.0,
+            sheen_tint: 0.5,
+            clearcoat: 0.0,
+            clearcoat_gloss: 1.0,
+            transmission: 0.0,
+        }
+    }
+}
+
+struct Lobe {
+    bxdf: Box<dyn BxDF>,
+    probability: f64,
+}
+
+fn mix(t: f64, a: Color3f, b: Color3f) -> Color3f {
+    a * (1.0 - t) + b * t
+}
+
+/// Schlick's weight (1 - cos)^5
+fn schlick_weight(cos_theta: f64) -> f64 {
+    (1.0 - cos_theta).clamp(0.0, 1.0).powi(5)
+}
+
+fn schlick_fresnel(f0: Color3f, cos_theta: f64) -> Color3f {
+    mix(schlick_weight(cos_theta), f0, Color3f::new(1.0, 1.0, 1.0))
+}
+
+/// Disney's approximate luminance
+fn luminance(color: &Color3f) -> f64 {
+    0.3 * color.x + 0.6 * color.y + 0.1 * color.z
+}
+
+/// Burley's diffuse with retro-reflection at grazing angles, plus the sheen rim.
+struct DiffuseLobe {
+    base_color: Color3f,
+    roughness: f64,
+    sheen: Color3f,
+    weight: f64,
+}
+
+impl BxDF for DiffuseLobe {
+    fn f(&self, wo: &Vec3f, wi: &Vec3f) -> Color3f {
+        if !same_hemisphere(wo, wi) {
+            return Color3f::default();
+        }
+        let flip = wo.z < 0.0;
+        let (wo, wi) = (flip_z(wo, flip), flip_z(wi, flip));
+
+        let wh = wo + wi;
+        if wh.length_sq() == 0.0 {
+            return Color3f::default();
+        }
+        let cos_theta_d = wi.dot(&wh.normalize());
+
+        let fl = schlick_weight(wi.z);
+        let fv = schlick_weight(wo.z);
+        let diffuse = (1.0 - 0.5 * fl) * (1.0 - 0.5 * fv);
+
+        let rr = 2.0 * self.roughness * cos_theta_d * cos_theta_d;
+        let retro = rr * (fl + fv + fl * fv * (rr - 1.0));
+
+        let sheen = self.sheen * schlick_weight(cos_theta_d);
+        (self.base_color * ((diffuse + retro) * FRAC_1_PI) + sheen) * self.weight
+    }
+
+    fn sample_f(&self, wo: &Vec3f, _uc: f64, u: &Point2f) -> Option<BsdfSample> {
+        let wi = flip_z(&sample_cosine_hemisphere(u), wo.z < 0.0);
+        Some(BsdfSample::new(self.f(wo, &wi), wi, self.pdf(wo, &wi)))
+    }
+
+    fn pdf(&self, wo: &Vec3f, wi: &Vec3f) -> f64 {
+        if !same_hemisphere(wo, wi) {
+            return 0.0;
+        }
+        cosine_hemisphere_pdf(wi.z.abs())
+    }
+}
+
+/// Microfacet reflection with Schlick's Fresnel approximation.
+struct SpecularLobe {
+    distribution: TrowbridgeReitzDistribution,
+    f0: Color3f,
+    weight: f64,
+}
+
+impl BxDF for SpecularLobe {
+    fn f(&self, wo: &Vec3f, wi: &Vec3f) -> Color3f {
+        if !same_hemisphere(wo, wi) {
+            return Color3f::default();
+        }
+        let flip = wo.z < 0.0;
+        let (wo, wi) = (flip_z(wo, flip), flip_z(wi, flip));
+
+        let wm = wo + wi;
+        if wm.length_sq() == 0.0 {
+            return Color3f::default();
+        }
+        let wm = wm.normalize();
+
+        let fresnel = schlick_fresnel(self.f0, wi.dot(&wm));
+        fresnel
+            * (self.weight * self.distribution.d(&wm) * self.distribution.g(&wo, &wi)
+                / (4.0 * wo.z * wi.z))
+    }
+
+    fn sample_f(&self, wo: &Vec3f, _uc: f64, u: &Point2f) -> Option<BsdfSample> {
+        let flip = wo.z < 0.0;
+        let wo_up = flip_z(wo, flip);
+        let wm = self.distribution.sample_wm(&wo_up, u);
+        let wi = flip_z(&reflect(&wo_up, &wm), flip);
+        if !same_hemisphere(wo, &wi) {
+            return None;
+        }
+        Some(BsdfSample::new(self.f(wo, &wi), wi, self.pdf(wo, &wi)))
+    }
+
+    fn pdf(&self, wo: &Vec3f, wi: &Vec3f) -> f64 {
+        if !same_hemisphere(wo, wi) {
+            return 0.0;
+        }
+        let flip = wo.z < 0.0;
+        let (wo, wi) = (flip_z(wo, flip), flip_z(wi, flip));
+
+        let wm = wo + wi;
+        if wm.length_sq() == 0.0 {
+            return 0.0;
+        }
+        let wm = wm.normalize();
+        self.distribution.pdf(&wo, &wm) / (4.0 * wo.dot(&wm).abs())
+    }
+}
+
+/// The clearcoat layer, using Burley's GTR1 distribution for its long tail
+/// and a fixed 4% reflectance.
+struct ClearcoatLobe {
+    alpha: f64,
+    weight: f64,
+}
+
+impl ClearcoatLobe {
+    fn d(&self, cos_theta_m: f64) -> f64 {
+        let alpha2 = self.alpha * self.alpha;
+        let t = 1.0 + (alpha2 - 1.0) * cos_theta_m * cos_theta_m;
+        (alpha2 - 1.0) / (PI * alpha2.ln() * t)
+    }
+
+    /// Separable Smith masking for a fixed GGX roughness of 0.25
+    fn g1(w: &Vec3f) -> f64 {
+        let alpha2 = 0.25 * 0.25;
+        let cos2_theta = w.z * w.z;
+        2.0 / (1.0 + (1.0 + alpha2 * (1.0 - cos2_theta) / cos2_theta).sqrt())
+    }
+}
+
+impl BxDF for ClearcoatLobe {
+    fn f(&self, wo: &Vec3f, wi: &Vec3f) -> Color3f {
+        if !same_hemisphere(wo, wi) {
+            return Color3f::default();
+        }
+        let flip = wo.z < 0.0;
+        let (wo, wi) = (flip_z(wo, flip), flip_z(wi, flip));
+
+        let wm = wo + wi;
+        if wm.length_sq() == 0.0 {
+            return Color3f::default();
+        }
+        let wm = wm.normalize();
+
+        let fresnel = 0.04 + 0.96 * schlick_weight(wi.dot(&wm));
+        let value = self.weight * self.d(wm.z) * fresnel * Self::g1(&wo) * Self::g1(&wi)
+            / (4.0 * wo.z * wi.z);
+        Color3f::new(value, value, value)
+    }
+
+    fn sample_f(&self, wo: &Vec3f, _uc: f64, u: &Point2f) -> Option<BsdfSample> {
+        let alpha2 = self.alpha * self.alpha;
+        let cos_theta = ((1.0 - alpha2.powf(1.0 - u.x)) / (1.0 - alpha2))
+            .max(0.0)
+            .sqrt();
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let phi = 2.0 * PI * u.y;
+        let wm = Vec3f::new(sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta);
+
+        let flip = wo.z < 0.0;
+        let wi = flip_z(&reflect(&flip_z(wo, flip), &wm), flip);
+        if !same_hemisphere(wo, &wi) {
+            return None;
+        }
+        Some(BsdfSample::new(self.f(wo, &wi), wi, self.pdf(wo, &wi)))
+    }
+
+    fn pdf(&self, wo: &Vec3f, wi: &Vec3f) -> f64 {
+        if !same_hemisphere(wo, wi) {
+            return 0.0;
+        }
+        let flip = wo.z < 0.0;
+        let (wo, wi) = (flip_z(wo, flip), flip_z(wi, flip));
+
+        let wm = wo + wi;
+        if wm.length_sq() == 0.0 {
+            return 0.0;
+        }
+        let wm = wm.normalize();
+        self.d(wm.z) * wm.z / (4.0 * wo.dot(&wm))
+    }
+}
+
+/// A rough dielectric whose refracted light is tinted by the base color.
+struct TransmissionLobe {
+    dielectric: DielectricBxDF<TrowbridgeReitzDistribution>,
+    tint: Color3f,
+    weight: f64,
+}
+
+impl TransmissionLobe {
+    fn scale(&self, wo: &Vec3f, wi: &Vec3f) -> Color3f {
+        if same_hemisphere(wo, wi) {
+            Color3f::new(self.weight, self.weight, self.weight)
+        } else {
+            self.tint * self.weight
+        }
+    }
+}
+
+impl BxDF for TransmissionLobe {
+    fn f(&self, wo: &Vec3f, wi: &Vec3f) -> Color3f {
+        self.dielectric.f(wo, wi) * self.scale(wo, wi)
+    }
+
+    fn sample_f(&self, wo: &Vec3f, uc: f64, u: &Point2f) -> Option<BsdfSample> {
+        let mut sample = self.dielectric.sample_f(wo, uc, u)?;
+        sample.f *= self.scale(wo, &sample.wi);
+        Some(sample)
+    }
+
+    fn pdf(&self, wo: &Vec3f, wi: &Vec3f) -> f64 {
+        self.dielectric.pdf(wo, wi)
+    }
+}
+
+impl PrincipledBxDF {
+    pub fn new(parameters: &PrincipledParameters) -> Self {
+        let p = parameters;
+        let base_color = p.base_color;
+        let white = Color3f::new(1.0, 1.0, 1.0);
+
+        let base_luminance = luminance(&base_color);
+        let tint = if base_luminance > 0.0 {
+            base_color / base_luminance
+        } else {
+            white
+        };
+
+        let transmission_weight = p.transmission * (1.0 - p.metallic);
+        let diffuse_weight = (1.0 - p.metallic) * (1.0 - p.transmission);
+
+        let aspect = (1.0 - 0.9 * p.anisotropic).sqrt();
+        let alpha = p.roughness * p.roughness;
+        let distribution = TrowbridgeReitzDistribution::new(
+            (alpha / aspect).max(1e-3),
+            (alpha * aspect).max(1e-3),
+        );
+
+        let specular_f0 = mix(
+            p.metallic,
+            mix(p.specular_tint, white, tint) * (0.08 * p.specular),
+            base_color,
+        );
+
+        let mut lobes = Vec::new();
+        if diffuse_weight > 0.0 {
+            lobes.push(Lobe {
+                bxdf: Box::new(DiffuseLobe {
+                    base_color,
+                    roughness: p.roughness,
+                    sheen: mix(p.sheen_tint, white, tint) * p.sheen,
+                    weight: diffuse_weight,
+                }),
+                probability: diffuse_weight * (base_color.average() + p.sheen * 0.1),
+            });
+        }
+
+        if transmission_weight < 1.0 {
+            let weight = 1.0 - transmission_weight;
+            lobes.push(Lobe {
+                bxdf: Box::new(SpecularLobe {
+                    distribution,
+                    f0: specular_f0,
+                    weight,
+                }),
+                // Fresnel brightens grazing angles, so never starve this lobe
+                probability: weight * specular_f0.average().max(0.1),
+            });
+        }
+
+        if p.clearcoat > 0.0 {
+            lobes.push(Lobe {
+                bxdf: Box::new(ClearcoatLobe {
+                    alpha: 0.1 * (1.0 - p.clearcoat_gloss) + 0.001 * p.clearcoat_gloss,
+                    weight: 0.25 * p.clearcoat,
+                }),
+                probability: 0.25 * p.clearcoat * 0.1,
+            });
+        }
+
+        if transmission_weight > 0.0 {
+            // Invert the normal incidence reflectance of the specular parameter
+            let sqrt_f0 = (0.08 * p.specular).sqrt().min(0.99);
+            let eta = (1.0 + sqrt_f0) / (1.0 - sqrt_f0);
+            lobes.push(Lobe {
+                bxdf: Box::new(TransmissionLobe {
+                    dielectric: DielectricBxDF::new(eta, distribution),
+                    tint: base_color,
+                    weight: transmission_weight,
+                }),
+                probability: transmission_weight,
+            });
+        }
+
+        let total: f64 = lobes.iter().map(|lobe| lobe.probability).sum();
+        for lobe in lobes.iter_mut() {
+            lobe.probability /= total;
+        }
+
+        Self { lobes }
+    }
+}
+
+impl BxDF for PrincipledBxDF {
+    fn f(&self, wo: &Vec3f, wi: &Vec3f) -> Color3f {
+        let mut f = Color3f::default();
+        for lobe in self.lobes.iter() {
+            f += lobe.bxdf.f(wo, wi);
+        }
+        f
+    }
+
+    fn sample_f(&self, wo: &Vec3f, uc: f64, u: &Point2f) -> Option<BsdfSample> {
+        // Pick a lobe, then stretch uc back over [0.0, 1.0) for the lobe's own use
+        let mut uc = uc;
+        let mut chosen = self.lobes.last()?;
+        for lobe in self.lobes.iter() {
+            if uc < lobe.probability {
+                chosen = lobe;
+                uc /= lobe.probability;
+                break;
+            }
+            uc -= lobe.probability;
+        }
+
+        let mut sample = chosen.bxdf.sample_f(wo, uc.min(1.0 - f64::EPSILON), u)?;
+        if sample.specular {
+            sample.pdf *= chosen.probability;
+            return Some(sample);
+        }
+
+        sample.f = self.f(wo, &sample.wi);
+        sample.pdf = self.pdf(wo, &sample.wi);
+        Some(sample)
+    }
+
+    fn pdf(&self, wo: &Vec3f, wi: &Vec3f) -> f64 {
+        self.lobes
+            .iter()
+            .map(|lobe| lobe.probability * lobe.bxdf.pdf(wo, wi))
+            .sum()
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bxdfs::test_utils::{
+        assert_chi_square, assert_samples_consistent, directional_albedo, test_directions,
+    };
+
+    fn presets() -> Vec<PrincipledParameters> {
+        let default = PrincipledParameters::default();
+        vec![
+            default,
+            PrincipledParameters {
+                base_color: Color3f::new(0.9, 0.6, 0.2),
+                metallic: 1.0,
+                roughness: 0.3,
+                anisotropic: 0.8,
+                ..default
+            },
+            PrincipledParameters {
+                roughness: 0.9,
+                sheen: 1.0,
+                clearcoat: 1.0,
+                clearcoat_gloss: 0.7,
+                ..default
+            },
+            PrincipledParameters {
+                base_color: Color3f::new(0.7, 0.9, 1.0),
+                roughness: 0.4,
+                transmission: 0.8,
+                ..default
+            },
+        ]
+    }
+
+    #[test]
+    fn samples_consistent() {
+        for parameters in presets() {
+            let bxdf = PrincipledBxDF::new(&parameters);
+            for wo in test_directions() {
+                assert_samples_consistent(&bxdf, &wo);
+            }
+        }
+    }
+
+    #[test]
+    fn chi_square() {
+        for parameters in presets() {
+            let bxdf = PrincipledBxDF::new(&parameters);
+            for wo in test_directions() {
+                assert_chi_square(&bxdf, &wo);
+            }
+        }
+    }
+
+    #[test]
+    fn conserves_energy() {
+        // Diffuse and specular are summed rather than layered, so like the original model
+        // a perfectly white base gains a few percent; realistic albedos stay below one
+        for roughness in [0.2, 0.5, 1.0] {
+            for metallic in [0.0, 1.0] {
+                let parameters = PrincipledParameters {
+                    metallic,
+                    roughness,
+                    ..PrincipledParameters::default()
+                };
+                let bxdf = PrincipledBxDF::new(&parameters);
+                for wo in test_directions().iter().filter(|wo| wo.z.abs() > 0.1) {
+                    let albedo = directional_albedo(&bxdf, wo, 20_000);
+                    assert!(
+                        albedo.max_component() <= 1.0,
+                        "roughness {} metallic {} wo {:?} albedo {:?}",
+                        roughness,
+                        metallic,
+                        wo,
+                        albedo
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn metal_is_tinted_by_base_color() {
+        let parameters = PrincipledParameters {
+            base_color: Color3f::new(1.0, 0.5, 0.1),
+            metallic: 1.0,
+            roughness: 0.3,
+            ..PrincipledParameters::default()
+        };
+        let bxdf = PrincipledBxDF::new(&parameters);
+        let wo = Vec3f::new(0.0, 0.0, 1.0);
+        let albedo = directional_albedo(&bxdf, &wo, 10_000);
+        assert!(
+            albedo.x > albedo.y && albedo.y > albedo.z,
+            "albedo {:?}",
+            albedo
+        );
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,185 @@
+//! Statistical checks shared by the [BxDF] tests.
+
+use std::f64::consts::PI;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::core::bxdf::BxDF;
+use crate::core::vector::{Color3f, Point2f, Vec3f};
+
+const THETA_RES: usize = 10;
+const PHI_RES: usize = 2 * THETA_RES;
+const CHI_SQUARE_SAMPLES: usize = 200_000;
+const INTEGRATION_RES: usize = 32;
+
+/// The standard normal quantile for the significance level of the chi-square test (1e-4).
+const SIGNIFICANCE_Z: f64 = 3.719;
+
+pub fn rng() -> StdRng {
+    StdRng::seed_from_u64(0x5eed)
+}
+
+/// A spread of outgoing directions, from head on to grazing and below the surface.
+pub fn test_directions() -> Vec<Vec3f> {
+    vec![
+        Vec3f::new(0.0, 0.0, 1.0),
+        Vec3f::new(0.3, 0.2, 0.9).normalize(),
+        Vec3f::new(-0.7, 0.1, 0.3).normalize(),
+        Vec3f::new(0.2, -0.95, 0.05).normalize(),
+        Vec3f::new(0.4, 0.4, -0.6).normalize(),
+    ]
+}
+
+/// Monte Carlo estimate of the fraction of light arriving along `wo` that is scattered.
+/// Anything above one means the [BxDF] creates energy.
+pub fn directional_albedo(bxdf: &dyn BxDF, wo: &Vec3f, samples: usize) -> Color3f {
+    let mut rng = rng();
+    let mut total = Color3f::default();
+
+    for _ in 0..samples {
+        let u = Point2f::new(rng.gen(), rng.gen());
+        if let Some(sample) = bxdf.sample_f(wo, rng.gen(), &u) {
+            if sample.pdf > 0.0 {
+                total += sample.f * (sample.wi.z.abs() / sample.pdf);
+            }
+        }
+    }
+
+    total / samples as f64
+}
+
+/// Checks that sampled values and densities agree with [BxDF::f] and [BxDF::pdf].
+pub fn assert_samples_consistent(bxdf: &dyn BxDF, wo: &Vec3f) {
+    let mut rng = rng();
+
+    for _ in 0..1_000 {
+        let u = Point2f::new(rng.gen(), rng.gen());
+        let Some(sample) = bxdf.sample_f(wo, rng.gen(), &u) else {
+            continue;
+        };
+        if sample.specular || sample.pdf == 0.0 {
+            continue;
+        }
+
+        if sample.pdf_is_proportional {
+            continue;
+        }
+
+        let pdf = bxdf.pdf(wo, &sample.wi);
+        assert!(
+            (pdf - sample.pdf).abs() <= 1e-6 * pdf.max(1.0),
+            "sampled pdf {} != evaluated pdf {} for wi {:?}",
+            sample.pdf,
+            pdf,
+            sample.wi
+        );
+
+        let f = bxdf.f(wo, &sample.wi);
+        assert!(
+            (f - sample.f).length() <= 1e-6 * f.length().max(1.0),
+            "sampled f {:?} != evaluated f {:?} for wi {:?}",
+            sample.f,
+            f,
+            sample.wi
+        );
+    }
+}
+
+fn spherical_direction(theta: f64, phi: f64) -> Vec3f {
+    Vec3f::new(
+        theta.sin() * phi.cos(),
+        theta.sin() * phi.sin(),
+        theta.cos(),
+    )
+}
+
+/// Pearson's chi-square test between a histogram of sampled directions
+/// and the histogram predicted by integrating [BxDF::pdf].
+/// Cells are a regular (theta, phi) grid over the whole sphere, so that
+/// narrow lobes around the poles are still resolved by the integration.
+pub fn assert_chi_square(bxdf: &dyn BxDF, wo: &Vec3f) {
+    let cell_theta = PI / THETA_RES as f64;
+    let cell_phi = 2.0 * PI / PHI_RES as f64;
+
+    let mut rng = rng();
+    let mut observed = vec![0.0; THETA_RES * PHI_RES];
+    for _ in 0..CHI_SQUARE_SAMPLES {
+        let u = Point2f::new(rng.gen(), rng.gen());
+        let Some(sample) = bxdf.sample_f(wo, rng.gen(), &u) else {
+            continue;
+        };
+        if sample.pdf == 0.0 || sample.f.is_black() {
+            continue;
+        }
+
+        let wi = sample.wi.normalize();
+        let phi = wi.y.atan2(wi.x).rem_euclid(2.0 * PI);
+        let theta = wi.z.clamp(-1.0, 1.0).acos();
+        let theta_cell = ((theta / cell_theta) as usize).min(THETA_RES - 1);
+        let phi_cell = ((phi / cell_phi) as usize).min(PHI_RES - 1);
+        observed[theta_cell * PHI_RES + phi_cell] += 1.0;
+    }
+
+    let mut cells = Vec::with_capacity(THETA_RES * PHI_RES);
+    for theta_cell in 0..THETA_RES {
+        for phi_cell in 0..PHI_RES {
+            let mut integral = 0.0;
+            for i in 0..INTEGRATION_RES {
+                for j in 0..INTEGRATION_RES {
+                    let theta = cell_theta
+                        * (theta_cell as f64 + (i as f64 + 0.5) / INTEGRATION_RES as f64);
+                    let phi =
+                        cell_phi * (phi_cell as f64 + (j as f64 + 0.5) / INTEGRATION_RES as f64);
+                    integral += bxdf.pdf(wo, &spherical_direction(theta, phi)) * theta.sin();
+                }
+            }
+            integral *= cell_theta * cell_phi / (INTEGRATION_RES * INTEGRATION_RES) as f64;
+
+            let expected = integral * CHI_SQUARE_SAMPLES as f64;
+            let observed = observed[theta_cell * PHI_RES + phi_cell];
+            assert!(
+                expected > 0.0 || observed == 0.0,
+                "{} samples in a cell with zero expected density for wo {:?}",
+                observed,
+                wo
+            );
+            cells.push((expected, observed));
+        }
+    }
+
+    // Pool cells with small expected counts so the statistic stays well behaved
+    cells.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    let mut pooled = (0.0, 0.0);
+    let mut chi_square = 0.0;
+    let mut dof = 0;
+    for (expected, observed) in cells {
+        if expected == 0.0 {
+            continue;
+        }
+        if expected < 5.0 || pooled.0 > 0.0 && pooled.0 < 5.0 {
+            pooled.0 += expected;
+            pooled.1 += observed;
+            continue;
+        }
+        chi_square += (observed - expected) * (observed - expected) / expected;
+        dof += 1;
+    }
+    if pooled.0 > 0.0 {
+        chi_square += (pooled.1 - pooled.0) * (pooled.1 - pooled.0) / pooled.0;
+        dof += 1;
+    }
+    let dof = (dof - 1) as f64;
+
+    // Wilson-Hilferty approximation of the critical value
+    let h = 2.0 / (9.0 * dof);
+    let critical = dof * (1.0 - h + SIGNIFICANCE_Z * h.sqrt()).powi(3);
+    assert!(
+        chi_square < critical,
+        "chi-square {} exceeds critical value {} ({} dof) for wo {:?}",
+        chi_square,
+        critical,
+        dof,
+        wo
+    );
+}
@@ -6,7 +6,7 @@ use std::f64::consts::PI;
 
 use crate::core::{
     camera::Camera,
-    ray::Ray,
+    ray::{Ray, RayDifferential},
     sample::Sample,
     vector::{self, Point3f, Vec3f},
 };
@@ -19,6 +19,9 @@ pub struct PerspectiveCamera {
     u: Vec3f,
     v: Vec3f,
     lens_radius: f64,
+
+    /// The size of a pixel in sample coordinates, set when rays should carry differentials
+    pixel_size: Option<(f64, f64)>,
 }
 
 //////////////////////////
@@ -61,6 +64,16 @@ impl PerspectiveCamera {
             u,
             v,
             lens_radius,
+            pixel_size: None,
+        }
+    }
+
+    /// Makes rays carry [RayDifferential]s through the neighbouring pixels
+    /// of an image `width` by `height` pixels large.
+    pub fn with_ray_differentials(self, width: u32, height: u32) -> Self {
+        Self {
+            pixel_size: Some((1.0 / width as f64, 1.0 / height as f64)),
+            ..self
         }
     }
 }
@@ -70,14 +83,24 @@ impl Camera for PerspectiveCamera {
         let random = vector::random_in_unit_disk() * self.lens_radius;
         let focus_blur_offset = self.u * random.x + self.v * random.y;
 
-        Ray::new(
-            self.origin + focus_blur_offset,
-            self.top_left + self.horizontal * sample.x + self.vertical * sample.y
+        // Every ray passes through the same point on the lens, so they converge in focus
+        let o = self.origin + focus_blur_offset;
+        let direction = |x: f64, y: f64| {
+            self.top_left + self.horizontal * x + self.vertical * y
                 - self.origin
-                - focus_blur_offset,
-            0.0,
-            1000.0,
-        )
+                - focus_blur_offset
+        };
+
+        let ray = Ray::new(o, direction(sample.x, sample.y), 0.0, 1000.0);
+        match self.pixel_size {
+            None => ray,
+            Some((dx, dy)) => ray.with_differentials(RayDifferential {
+                rx_o: o,
+                rx_d: direction(sample.x + dx, sample.y),
+                ry_o: o,
+                ry_d: direction(sample.x, sample.y + dy),
+            }),
+        }
     }
 }
 
@@ -86,6 +109,41 @@ impl Camera for PerspectiveCamera {
 // BEGIN TESTS        //
 ////////////////////////
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera() -> PerspectiveCamera {
+        PerspectiveCamera::new(
+            Point3f::new(0.0, 0.0, 0.0),
+            Point3f::new(1.0, 0.0, 0.0),
+            Vec3f::new(0.0, 1.0, 0.0),
+            70.0,
+            1.0,
+            0.0,
+            1.0,
+        )
+    }
+
+    #[test]
+    fn differentials_reach_neighbouring_pixels() {
+        assert!(camera()
+            .get_ray(&Sample::new(0.5, 0.5))
+            .differentials
+            .is_none());
+
+        let camera = camera().with_ray_differentials(100, 50);
+        let ray = camera.get_ray(&Sample::new(0.3, 0.6));
+        let rd = ray.differentials.unwrap();
+
+        let right = camera.get_ray(&Sample::new(0.31, 0.6));
+        let below = camera.get_ray(&Sample::new(0.3, 0.62));
+        assert_eq!((rd.rx_o, rd.ry_o), (ray.o, ray.o));
+        assert!((rd.rx_d - right.d).length() < 1e-12);
+        assert!((rd.ry_d - below.d).length() < 1e-12);
+    }
+}
+
 ///////////////
 // END TESTS //
 ///////////////
@@ -10,8 +10,12 @@ use crate::core::ray::Ray;
 /// The most basic implementation would test every [Primitive] in the collection
 /// and return the closest.
 pub trait Accelerator<'a> {
-    fn build(&mut self, primitives: &'a Vec<&'a dyn Primitive>);
-    fn test(&self, ray: &Ray) -> Option<Interaction>;
+    fn build(&mut self, primitives: &'a [&'a dyn Primitive]);
+    fn test(&self, ray: &Ray) -> Option<Interaction<'a>>;
+
+    /// How many nodes of the structure the search for the closest hit along `ray` visits,
+    /// to show where traversal is expensive.
+    fn nodes_visited(&self, ray: &Ray) -> usize;
 }
 
 ///////////////////
@@ -1,6 +1,9 @@
+use std::f64::consts::PI;
+
 use num_traits::Float;
 
-use crate::core::vector::Point3;
+use crate::core::frame::Frame;
+use crate::core::vector::{Point3, Point3f, Vec3f};
 
 /////////////////////
 // BEGIN INTERFACE //
@@ -8,7 +11,7 @@ use crate::core::vector::Point3;
 
 /// An axis-aligned box,
 /// often used to represent the minimum enclosing space of a primitive.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Bounds3<T> {
     min: Point3<T>,
     max: Point3<T>,
@@ -17,6 +20,13 @@ pub struct Bounds3<T> {
 pub type Bounds3f = Bounds3<f64>;
 pub type Bounds3i = Bounds3<i32>;
 
+/// A cone of directions around a central axis `w`, bounding where normals or emission point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DirectionCone {
+    pub w: Vec3f,
+    pub cos_theta: f64,
+}
+
 //////////////////////////
 // END INTERFACE        //
 // BEGIN IMPLEMENTATION //
@@ -28,6 +38,16 @@ impl<T> Bounds3<T> {
     }
 }
 
+impl<T: Copy> Bounds3<T> {
+    pub fn min(&self) -> Point3<T> {
+        self.min
+    }
+
+    pub fn max(&self) -> Point3<T> {
+        self.max
+    }
+}
+
 impl<T: Float> Bounds3<T> {
     pub fn union(&self, other: &Self) -> Self {
         let min = Point3::new(
@@ -38,14 +58,96 @@ impl<T: Float> Bounds3<T> {
 
         let max = Point3::new(
             self.max.x.max(other.max.x),
-            self.max.x.max(other.max.x),
-            self.max.x.max(other.max.x),
+            self.max.y.max(other.max.y),
+            self.max.z.max(other.max.z),
         );
 
         Self::new(min, max)
     }
 }
 
+impl Bounds3f {
+    /// The box holding just `p`.
+    pub fn from_point(p: Point3f) -> Self {
+        Self::new(p, p)
+    }
+
+    pub fn centroid(&self) -> Point3f {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn diagonal(&self) -> Vec3f {
+        self.max - self.min
+    }
+
+    pub fn surface_area(&self) -> f64 {
+        let d = self.diagonal();
+        2.0 * (d.x * d.y + d.x * d.z + d.y * d.z)
+    }
+
+    pub fn contains(&self, p: &Point3f) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x)
+            && (self.min.y..=self.max.y).contains(&p.y)
+            && (self.min.z..=self.max.z).contains(&p.z)
+    }
+
+    /// The center and radius of a sphere enclosing the box.
+    pub fn bounding_sphere(&self) -> (Point3f, f64) {
+        let center = self.centroid();
+        (center, (self.max - center).length())
+    }
+
+    /// The cone of directions from `p` towards the box, all of them when `p` is inside.
+    pub fn subtended_directions(&self, p: &Point3f) -> DirectionCone {
+        let (center, radius) = self.bounding_sphere();
+        let distance_sq = (center - *p).length_sq();
+        if self.contains(p) || distance_sq < radius * radius {
+            return DirectionCone::entire_sphere();
+        }
+        let sin2_theta_max = radius * radius / distance_sq;
+        DirectionCone::new(center - *p, (1.0 - sin2_theta_max).max(0.0).sqrt())
+    }
+}
+
+impl DirectionCone {
+    /// `w` needn't be normalized.
+    pub fn new(w: Vec3f, cos_theta: f64) -> Self {
+        Self {
+            w: w.normalize(),
+            cos_theta,
+        }
+    }
+
+    pub fn entire_sphere() -> Self {
+        Self::new(Vec3f::new(0.0, 0.0, 1.0), -1.0)
+    }
+
+    /// The smallest cone holding both `self` and `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        let theta_a = self.cos_theta.clamp(-1.0, 1.0).acos();
+        let theta_b = other.cos_theta.clamp(-1.0, 1.0).acos();
+        let theta_d = self.w.dot(&other.w).clamp(-1.0, 1.0).acos();
+        if (theta_d + theta_b).min(PI) <= theta_a {
+            return *self;
+        }
+        if (theta_d + theta_a).min(PI) <= theta_b {
+            return *other;
+        }
+
+        // Spread evenly past both cones, turning the axis from `self` towards `other`
+        let theta_o = (theta_a + theta_d + theta_b) / 2.0;
+        if theta_o >= PI {
+            return Self::entire_sphere();
+        }
+        let axis = self.w.cross(&other.w);
+        if axis.length_sq() == 0.0 {
+            return Self::entire_sphere();
+        }
+        let rotation = Frame::from_rotation(axis, (theta_o - theta_a).to_degrees());
+        Self::new(rotation.local_to_world(&self.w), theta_o.cos())
+    }
+}
+
 ////////////////////////
 // END IMPLEMENTATION //
 // BEGIN TESTS        //
@@ -79,6 +181,54 @@ mod tests {
         let res_max = Point3::new(3.0, 3.0, 3.0);
         assert_eq!(union, Bounds3::new(res_min, res_max));
     }
+
+    #[test]
+    fn union_takes_each_axis() {
+        let a = Bounds3::new(Point3::new(0.0, -1.0, 2.0), Point3::new(1.0, 5.0, 3.0));
+        let b = Bounds3::new(Point3::new(-2.0, 0.0, 1.0), Point3::new(0.5, 2.0, 7.0));
+        assert_eq!(
+            a.union(&b),
+            Bounds3::new(Point3::new(-2.0, -1.0, 1.0), Point3::new(1.0, 5.0, 7.0))
+        );
+    }
+
+    #[test]
+    fn bounding_sphere() {
+        let bounds = Bounds3::new(Point3::new(-1.0, 0.0, 2.0), Point3::new(1.0, 2.0, 4.0));
+        let (center, radius) = bounds.bounding_sphere();
+        assert_eq!(center, Point3::new(0.0, 1.0, 3.0));
+        assert!((radius - 3f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn subtended_directions() {
+        let bounds = Bounds3::new(Point3::new(-1.0, -1.0, 9.0), Point3::new(1.0, 1.0, 11.0));
+        let cone = bounds.subtended_directions(&Point3::default());
+        assert_eq!(cone.w, Vec3f::new(0.0, 0.0, 1.0));
+        // Seen through the bounding sphere of radius sqrt(3) from 10 away
+        assert!((cone.cos_theta - (1.0 - 3.0 / 100.0f64).sqrt()).abs() < 1e-12);
+
+        let inside = bounds.subtended_directions(&Point3::new(0.0, 0.0, 10.0));
+        assert_eq!(inside.cos_theta, -1.0);
+    }
+
+    #[test]
+    fn direction_cone_union() {
+        let x = DirectionCone::new(Vec3f::new(1.0, 0.0, 0.0), 1.0);
+        let y = DirectionCone::new(Vec3f::new(0.0, 1.0, 0.0), 1.0);
+        let both = x.union(&y);
+        assert!((both.w - Vec3f::new(1.0, 1.0, 0.0).normalize()).length() < 1e-12);
+        assert!((both.cos_theta - (PI / 4.0).cos()).abs() < 1e-12);
+
+        // Cones inside others add nothing
+        let wide = DirectionCone::new(Vec3f::new(1.0, 1.0, 0.0), 0.5);
+        assert_eq!(wide.union(&x), wide);
+        assert_eq!(x.union(&wide), wide);
+
+        // Opposite directions need every direction
+        let minus_x = DirectionCone::new(Vec3f::new(-1.0, 0.0, 0.0), 1.0);
+        assert_eq!(x.union(&minus_x).cos_theta, -1.0);
+    }
 }
 
 ///////////////
@@ -0,0 +1,79 @@
+use crate::core::bxdf::{BsdfSample, BxDF};
+use crate::core::frame::Frame;
+use crate::core::vector::{Color3f, Point2f, Vec3f};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A [BxDF] placed at a point on a surface.
+/// Takes world space directions and hands the [BxDF] local ones.
+pub struct Bsdf {
+    frame: Frame,
+    bxdf: Box<dyn BxDF>,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl Bsdf {
+    pub fn new(frame: Frame, bxdf: Box<dyn BxDF>) -> Self {
+        Self { frame, bxdf }
+    }
+
+    pub fn frame(&self) -> &Frame {
+        &self.frame
+    }
+
+    pub fn bxdf(&self) -> &dyn BxDF {
+        self.bxdf.as_ref()
+    }
+
+    pub fn into_bxdf(self) -> Box<dyn BxDF> {
+        self.bxdf
+    }
+
+    pub fn f(&self, wo: &Vec3f, wi: &Vec3f) -> Color3f {
+        let wo = self.frame.world_to_local(&wo.normalize());
+        let wi = self.frame.world_to_local(&wi.normalize());
+        if wo.z == 0.0 {
+            return Color3f::default();
+        }
+        self.bxdf.f(&wo, &wi)
+    }
+
+    /// Samples an incident direction, returned in world space.
+    pub fn sample_f(&self, wo: &Vec3f, uc: f64, u: &Point2f) -> Option<BsdfSample> {
+        let wo = self.frame.world_to_local(&wo.normalize());
+        if wo.z == 0.0 {
+            return None;
+        }
+
+        let mut sample = self.bxdf.sample_f(&wo, uc, u)?;
+        if sample.pdf == 0.0 || sample.f.is_black() || sample.wi.z == 0.0 {
+            return None;
+        }
+        sample.wi = self.frame.local_to_world(&sample.wi);
+        Some(sample)
+    }
+
+    pub fn pdf(&self, wo: &Vec3f, wi: &Vec3f) -> f64 {
+        let wo = self.frame.world_to_local(&wo.normalize());
+        let wi = self.frame.world_to_local(&wi.normalize());
+        if wo.z == 0.0 {
+            return 0.0;
+        }
+        self.bxdf.pdf(&wo, &wi)
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,43 @@
+use rand::RngCore;
+
+use crate::core::accelerator::Accelerator;
+use crate::core::bsdf::Bsdf;
+use crate::core::interaction::Interaction;
+use crate::core::vector::{Color3f, Vec3f};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Light that scatters beneath a surface and leaves it somewhere other than where it entered.
+/// Integrators hand the path over whenever a [Bsdf] sample refracts
+/// into a surface whose [Material](crate::core::material::Material) has one.
+pub trait Bssrdf {
+    /// Follows a path refracted into the surface at `entry` along the world space `w`
+    /// until it leaves again, finding the boundary through `accelerator`.
+    /// Returns [None] if the path was absorbed.
+    fn sample_exit<'a>(
+        &self,
+        entry: &Interaction,
+        w: &Vec3f,
+        accelerator: &dyn Accelerator<'a>,
+        rng: &mut dyn RngCore,
+    ) -> Option<BssrdfSample<'a>>;
+}
+
+/// Where and how a path leaves a [Bssrdf].
+pub struct BssrdfSample<'a> {
+    /// The point on the boundary the path leaves from, with `wo` along the outward normal
+    pub interaction: Interaction<'a>,
+
+    /// How the light leaving spreads over the directions outside, integrating to one,
+    /// for integrators to gather direct lighting with and sample the path onwards from
+    pub bsdf: Bsdf,
+
+    /// The throughput of the path from entry to leaving through the boundary
+    pub weight: Color3f,
+}
+
+///////////////////
+// END INTERFACE //
+///////////////////
@@ -0,0 +1,250 @@
+use crate::core::vector::{Color3f, Point2f, Vec3f};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A single scattering function, defined in the local shading space
+/// where the surface normal is the z axis.
+/// Both `wo` and `wi` point away from the surface and are normalized.
+pub trait BxDF {
+    /// Evaluates the distribution for the pair of directions.
+    fn f(&self, wo: &Vec3f, wi: &Vec3f) -> Color3f;
+
+    /// Samples an incident direction for `wo`.
+    /// `uc` and `u` are uniform samples in [0.0, 1.0).
+    fn sample_f(&self, wo: &Vec3f, uc: f64, u: &Point2f) -> Option<BsdfSample>;
+
+    /// The solid angle density with which [BxDF::sample_f] returns `wi`.
+    fn pdf(&self, wo: &Vec3f, wi: &Vec3f) -> f64;
+}
+
+/// The result of sampling a [BxDF].
+#[derive(Debug, Clone, Copy)]
+pub struct BsdfSample {
+    /// The value of the distribution for the sampled pair
+    pub f: Color3f,
+
+    /// The sampled incident direction
+    pub wi: Vec3f,
+
+    /// The density of `wi`, or the discrete probability of a specular lobe
+    pub pdf: f64,
+
+    /// Whether `wi` came from a delta distribution
+    pub specular: bool,
+
+    /// The relative index of refraction along `wi`, 1.0 unless `wi` was transmitted
+    pub eta: f64,
+
+    /// Whether `pdf` is only proportional to the true density,
+    /// for stochastic [BxDF]s whose density cannot be evaluated exactly.
+    /// `f / pdf` is still the correct sample weight, but [BxDF::pdf] must be
+    /// used wherever the density itself is needed, such as for MIS.
+    pub pdf_is_proportional: bool,
+}
+
+/// What is being carried along a path.
+/// Refraction compresses radiance into the smaller solid angle on the denser side,
+/// but does not affect importance flowing the other way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransportMode {
+    Radiance,
+    Importance,
+}
+
+/// Restricts sampling to the lobes on one side of the surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScatterLobes {
+    All,
+    Reflection,
+    Transmission,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl BsdfSample {
+    pub fn new(f: Color3f, wi: Vec3f, pdf: f64) -> Self {
+        Self {
+            f,
+            wi,
+            pdf,
+            specular: false,
+            eta: 1.0,
+            pdf_is_proportional: false,
+        }
+    }
+
+    pub fn new_specular(f: Color3f, wi: Vec3f, pdf: f64) -> Self {
+        Self {
+            f,
+            wi,
+            pdf,
+            specular: true,
+            eta: 1.0,
+            pdf_is_proportional: false,
+        }
+    }
+}
+
+impl ScatterLobes {
+    pub fn reflection(&self) -> bool {
+        *self != ScatterLobes::Transmission
+    }
+
+    pub fn transmission(&self) -> bool {
+        *self != ScatterLobes::Reflection
+    }
+}
+
+pub fn cos_theta(w: &Vec3f) -> f64 {
+    w.z
+}
+
+pub fn cos2_theta(w: &Vec3f) -> f64 {
+    w.z * w.z
+}
+
+pub fn abs_cos_theta(w: &Vec3f) -> f64 {
+    w.z.abs()
+}
+
+pub fn sin2_theta(w: &Vec3f) -> f64 {
+    (1.0 - cos2_theta(w)).max(0.0)
+}
+
+pub fn sin_theta(w: &Vec3f) -> f64 {
+    sin2_theta(w).sqrt()
+}
+
+pub fn tan_theta(w: &Vec3f) -> f64 {
+    sin_theta(w) / cos_theta(w)
+}
+
+pub fn cos_phi(w: &Vec3f) -> f64 {
+    let sin_theta = sin_theta(w);
+    if sin_theta == 0.0 {
+        1.0
+    } else {
+        (w.x / sin_theta).clamp(-1.0, 1.0)
+    }
+}
+
+pub fn sin_phi(w: &Vec3f) -> f64 {
+    let sin_theta = sin_theta(w);
+    if sin_theta == 0.0 {
+        0.0
+    } else {
+        (w.y / sin_theta).clamp(-1.0, 1.0)
+    }
+}
+
+pub fn same_hemisphere(w: &Vec3f, wp: &Vec3f) -> bool {
+    w.z * wp.z > 0.0
+}
+
+/// Mirrors `w` through the surface when `flip` is set.
+/// Lets one sided reflection models treat light from below as if it came from above.
+pub fn flip_z(w: &Vec3f, flip: bool) -> Vec3f {
+    if flip {
+        Vec3f::new(w.x, w.y, -w.z)
+    } else {
+        *w
+    }
+}
+
+/// Mirrors `wo` about `n`.
+pub fn reflect(wo: &Vec3f, n: &Vec3f) -> Vec3f {
+    -*wo + *n * (2.0 * wo.dot(n))
+}
+
+/// Refracts `wi` through a surface with normal `n` and relative index of refraction `eta`
+/// (inside over outside), following Snell's law.
+/// Both `wi` and the result point away from the surface, and `wi` may be on either side.
+/// Returns the transmitted direction and the relative index along it,
+/// or [None] on total internal reflection.
+pub fn refract(wi: &Vec3f, n: &Vec3f, eta: f64) -> Option<(Vec3f, f64)> {
+    let mut cos_theta_i = wi.dot(n);
+    let (eta, n) = if cos_theta_i < 0.0 {
+        cos_theta_i = -cos_theta_i;
+        (1.0 / eta, -*n)
+    } else {
+        (eta, *n)
+    };
+
+    let sin2_theta_i = (1.0 - cos_theta_i * cos_theta_i).max(0.0);
+    let sin2_theta_t = sin2_theta_i / (eta * eta);
+    if sin2_theta_t >= 1.0 {
+        return None;
+    }
+
+    let cos_theta_t = (1.0 - sin2_theta_t).sqrt();
+    let wt = -*wi / eta + n * (cos_theta_i / eta - cos_theta_t);
+    Some((wt, eta))
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spherical_terms() {
+        let w = Vec3f::new(1.0, 1.0, 2.0_f64.sqrt()).normalize();
+        assert!((cos_theta(&w) - 0.5_f64.sqrt()).abs() < 1e-9);
+        assert!((sin_theta(&w) - 0.5_f64.sqrt()).abs() < 1e-9);
+        assert!((tan_theta(&w) - 1.0).abs() < 1e-9);
+        assert!((cos_phi(&w) - 0.5_f64.sqrt()).abs() < 1e-9);
+        assert!((sin_phi(&w) - 0.5_f64.sqrt()).abs() < 1e-9);
+
+        let up = Vec3f::new(0.0, 0.0, 1.0);
+        assert_eq!(cos_phi(&up), 1.0);
+        assert_eq!(sin_phi(&up), 0.0);
+        assert!(same_hemisphere(&w, &up));
+        assert!(!same_hemisphere(&w, &-up));
+    }
+
+    #[test]
+    fn reflect_about_normal() {
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let wo = Vec3f::new(0.6, 0.0, 0.8);
+        assert_eq!(reflect(&wo, &n), Vec3f::new(-0.6, 0.0, 0.8));
+    }
+
+    #[test]
+    fn refract_snell() {
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let wi = Vec3f::new(0.6, 0.0, 0.8);
+        let eta = 1.5;
+
+        let (wt, etap) = refract(&wi, &n, eta).unwrap();
+        assert_eq!(etap, eta);
+        assert!(wt.z < 0.0);
+        assert!((wt.length() - 1.0).abs() < 1e-9);
+        // n1 sin(theta_1) = n2 sin(theta_2)
+        assert!((sin_theta(&wi) - eta * sin_theta(&wt)).abs() < 1e-9);
+
+        // Coming back out along the reversed path
+        let (back, etap) = refract(&wt, &n, eta).unwrap();
+        assert_eq!(etap, 1.0 / eta);
+        assert!((back - wi).length() < 1e-9);
+    }
+
+    #[test]
+    fn refract_total_internal_reflection() {
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let grazing_inside = Vec3f::new(0.9, 0.0, -0.1).normalize();
+        assert!(refract(&grazing_inside, &n, 1.5).is_none());
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
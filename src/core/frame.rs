@@ -0,0 +1,130 @@
+use crate::core::vector::Vec3f;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// An orthonormal basis used to move directions between world space
+/// and a local space where `n` is the z axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frame {
+    /// The first tangent, the local x axis
+    pub s: Vec3f,
+
+    /// The second tangent, the local y axis
+    pub t: Vec3f,
+
+    /// The normal, the local z axis
+    pub n: Vec3f,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl Frame {
+    pub fn new(s: Vec3f, t: Vec3f, n: Vec3f) -> Self {
+        Self { s, t, n }
+    }
+
+    /// Builds an arbitrary frame around a normalized `n`.
+    /// Uses the branchless construction from Duff et al. 2017.
+    pub fn from_normal(n: Vec3f) -> Self {
+        let sign = 1.0_f64.copysign(n.z);
+        let a = -1.0 / (sign + n.z);
+        let b = n.x * n.y * a;
+
+        let s = Vec3f::new(1.0 + sign * n.x * n.x * a, sign * b, -sign * n.x);
+        let t = Vec3f::new(b, sign + n.y * n.y * a, -n.y);
+
+        Self { s, t, n }
+    }
+
+    /// The frame whose axes are the world axes rotated by `degrees` counterclockwise
+    /// around `axis`, so that [Frame::local_to_world] applies the rotation.
+    pub fn from_rotation(axis: Vec3f, degrees: f64) -> Self {
+        let k = axis.normalize();
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        // Rodrigues' rotation formula
+        let rotate = |v: Vec3f| v * cos + k.cross(&v) * sin + k * (k.dot(&v) * (1.0 - cos));
+
+        Self {
+            s: rotate(Vec3f::new(1.0, 0.0, 0.0)),
+            t: rotate(Vec3f::new(0.0, 1.0, 0.0)),
+            n: rotate(Vec3f::new(0.0, 0.0, 1.0)),
+        }
+    }
+
+    pub fn world_to_local(&self, v: &Vec3f) -> Vec3f {
+        Vec3f::new(v.dot(&self.s), v.dot(&self.t), v.dot(&self.n))
+    }
+
+    pub fn local_to_world(&self, v: &Vec3f) -> Vec3f {
+        self.s * v.x + self.t * v.y + self.n * v.z
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: Vec3f, b: Vec3f) {
+        assert!((a - b).length() < 1e-9, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn from_normal_is_orthonormal() {
+        let normals = [
+            Vec3f::new(0.0, 0.0, 1.0),
+            Vec3f::new(0.0, 0.0, -1.0),
+            Vec3f::new(1.0, 2.0, 3.0).normalize(),
+            Vec3f::new(-3.0, 0.5, -0.2).normalize(),
+        ];
+
+        for n in normals {
+            let frame = Frame::from_normal(n);
+            assert!((frame.s.length() - 1.0).abs() < 1e-9);
+            assert!((frame.t.length() - 1.0).abs() < 1e-9);
+            assert!(frame.s.dot(&frame.t).abs() < 1e-9);
+            assert!(frame.s.dot(&frame.n).abs() < 1e-9);
+            assert_near(frame.s.cross(&frame.t), frame.n);
+        }
+    }
+
+    #[test]
+    fn from_rotation() {
+        let quarter = Frame::from_rotation(Vec3f::new(0.0, 0.0, 2.0), 90.0);
+        assert_near(
+            quarter.local_to_world(&Vec3f::new(1.0, 0.0, 0.0)),
+            Vec3f::new(0.0, 1.0, 0.0),
+        );
+        assert_near(
+            quarter.local_to_world(&Vec3f::new(0.0, 0.0, 1.0)),
+            Vec3f::new(0.0, 0.0, 1.0),
+        );
+
+        // Turning z up into y up
+        let tilt = Frame::from_rotation(Vec3f::new(1.0, 0.0, 0.0), -90.0);
+        assert_near(tilt.n, Vec3f::new(0.0, 1.0, 0.0));
+        assert_near(tilt.s.cross(&tilt.t), tilt.n);
+    }
+
+    #[test]
+    fn round_trip() {
+        let frame = Frame::from_normal(Vec3f::new(1.0, -1.0, 0.5).normalize());
+        let v = Vec3f::new(0.3, 0.4, -2.0);
+
+        assert_near(frame.local_to_world(&frame.world_to_local(&v)), v);
+        assert_near(frame.world_to_local(&frame.n), Vec3f::new(0.0, 0.0, 1.0));
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
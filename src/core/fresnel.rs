@@ -0,0 +1,282 @@
+use std::f64::consts::PI;
+
+use crate::core::spectrum::{resample_to_rgb, RGB_WAVELENGTHS};
+use crate::core::vector::Color3f;
+use crate::math::complex::Complex;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// The complex index of refraction `eta + i k` of a conductor, per color channel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComplexIor {
+    pub eta: Color3f,
+    pub k: Color3f,
+}
+
+/// A thin transparent film on top of an interface, such as a soap bubble's wall,
+/// an oil slick or the oxide layer of anodized metal.
+/// Light reflected off of its two sides interferes, tinting the reflection
+/// with colors that shift with the viewing angle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThinFilm {
+    /// The thickness of the film in nanometers
+    pub thickness: f64,
+
+    /// The index of refraction of the film, relative to the outside
+    pub eta: f64,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl ComplexIor {
+    pub const GOLD: Self = Self::new(
+        Color3f::new(0.143, 0.374, 1.442),
+        Color3f::new(3.983, 2.385, 1.603),
+    );
+
+    pub const COPPER: Self = Self::new(
+        Color3f::new(0.200, 0.924, 1.102),
+        Color3f::new(3.912, 2.452, 2.142),
+    );
+
+    pub const ALUMINUM: Self = Self::new(
+        Color3f::new(1.657, 0.880, 0.521),
+        Color3f::new(9.224, 6.270, 4.837),
+    );
+
+    pub const SILVER: Self = Self::new(
+        Color3f::new(0.155, 0.117, 0.138),
+        Color3f::new(4.828, 3.122, 2.147),
+    );
+
+    pub const fn new(eta: Color3f, k: Color3f) -> Self {
+        Self { eta, k }
+    }
+
+    /// Builds an index of refraction from measured samples,
+    /// `wavelengths` in nanometers and sorted.
+    pub fn from_spectrum(wavelengths: &[f64], eta: &[f64], k: &[f64]) -> Self {
+        Self::new(
+            resample_to_rgb(wavelengths, eta),
+            resample_to_rgb(wavelengths, k),
+        )
+    }
+}
+
+impl ThinFilm {
+    pub const fn new(thickness: f64, eta: f64) -> Self {
+        Self { thickness, eta }
+    }
+
+    /// The reflectance at one `wavelength` in nanometers, for light arriving at
+    /// `cos_theta_i` from a medium with index `eta_i` onto a substrate with index `eta_t`,
+    /// both relative to the outside (Airy summation over both polarizations).
+    fn reflectance(&self, cos_theta_i: f64, eta_i: f64, eta_t: Complex, wavelength: f64) -> f64 {
+        let one = Complex::from_real(1.0);
+        let eta_i = Complex::from_real(eta_i);
+        let eta_f = Complex::from_real(self.eta);
+
+        // Snell's law into the film and the substrate, complex past the critical angle
+        let cos_i = Complex::from_real(cos_theta_i.clamp(0.0, 1.0));
+        let sin2_i = one - cos_i * cos_i;
+        let cos_f = (one - sin2_i * (eta_i * eta_i) / (eta_f * eta_f)).sqrt();
+        let cos_t = (one - sin2_i * (eta_i * eta_i) / (eta_t * eta_t)).sqrt();
+
+        // Amplitude reflection coefficients, perpendicular and parallel
+        let r_s = |n1: Complex, c1: Complex, n2: Complex, c2: Complex| {
+            (n1 * c1 - n2 * c2) / (n1 * c1 + n2 * c2)
+        };
+        let r_p = |n1: Complex, c1: Complex, n2: Complex, c2: Complex| {
+            (n2 * c1 - n1 * c2) / (n2 * c1 + n1 * c2)
+        };
+
+        // The phase difference of one round trip through the film
+        let delta = eta_f * cos_f * (4.0 * PI * self.thickness / wavelength);
+        let phase = (Complex::new(0.0, 1.0) * delta).exp();
+
+        let airy =
+            |r12: Complex, r23: Complex| ((r12 + r23 * phase) / (one + r12 * r23 * phase)).norm();
+        let perpendicular = airy(
+            r_s(eta_i, cos_i, eta_f, cos_f),
+            r_s(eta_f, cos_f, eta_t, cos_t),
+        );
+        let parallel = airy(
+            r_p(eta_i, cos_i, eta_f, cos_f),
+            r_p(eta_f, cos_f, eta_t, cos_t),
+        );
+        ((perpendicular + parallel) / 2.0).clamp(0.0, 1.0)
+    }
+}
+
+/// Unpolarized Fresnel reflectance of a dielectric interface.
+/// `eta` is the relative index of refraction (inside over outside),
+/// and a negative `cos_theta_i` means the light arrives from inside.
+/// Returns 1.0 under total internal reflection.
+pub fn fr_dielectric(cos_theta_i: f64, eta: f64) -> f64 {
+    let mut cos_theta_i = cos_theta_i.clamp(-1.0, 1.0);
+    let eta = if cos_theta_i < 0.0 {
+        cos_theta_i = -cos_theta_i;
+        1.0 / eta
+    } else {
+        eta
+    };
+
+    let sin2_theta_i = 1.0 - cos_theta_i * cos_theta_i;
+    let sin2_theta_t = sin2_theta_i / (eta * eta);
+    if sin2_theta_t >= 1.0 {
+        return 1.0;
+    }
+    let cos_theta_t = (1.0 - sin2_theta_t).sqrt();
+
+    let r_parallel = (eta * cos_theta_i - cos_theta_t) / (eta * cos_theta_i + cos_theta_t);
+    let r_perpendicular = (cos_theta_i - eta * cos_theta_t) / (cos_theta_i + eta * cos_theta_t);
+    (r_parallel * r_parallel + r_perpendicular * r_perpendicular) / 2.0
+}
+
+/// Unpolarized Fresnel reflectance of an interface with an absorbing medium,
+/// `eta` being its complex relative index of refraction.
+/// Light is assumed to always arrive from outside the conductor.
+pub fn fr_complex(cos_theta_i: f64, eta: Complex) -> f64 {
+    let cos_theta_i = Complex::from_real(cos_theta_i.clamp(0.0, 1.0));
+    let sin2_theta_i = Complex::from_real(1.0) - cos_theta_i * cos_theta_i;
+    let sin2_theta_t = sin2_theta_i / (eta * eta);
+    let cos_theta_t = (Complex::from_real(1.0) - sin2_theta_t).sqrt();
+
+    let r_parallel = (eta * cos_theta_i - cos_theta_t) / (eta * cos_theta_i + cos_theta_t);
+    let r_perpendicular = (cos_theta_i - eta * cos_theta_t) / (cos_theta_i + eta * cos_theta_t);
+    (r_parallel.norm() + r_perpendicular.norm()) / 2.0
+}
+
+/// [fr_dielectric] with a [ThinFilm] on the outside of the interface,
+/// evaluated at [RGB_WAVELENGTHS].
+pub fn fr_thin_film_dielectric(cos_theta_i: f64, eta: f64, film: &ThinFilm) -> Color3f {
+    let (cos_theta_i, eta_i, eta_t) = if cos_theta_i < 0.0 {
+        (-cos_theta_i, eta, 1.0)
+    } else {
+        (cos_theta_i, 1.0, eta)
+    };
+    let r =
+        |wavelength| film.reflectance(cos_theta_i, eta_i, Complex::from_real(eta_t), wavelength);
+    Color3f::new(
+        r(RGB_WAVELENGTHS[0]),
+        r(RGB_WAVELENGTHS[1]),
+        r(RGB_WAVELENGTHS[2]),
+    )
+}
+
+/// [fr_conductor] with a [ThinFilm] on top of the conductor.
+pub fn fr_thin_film_conductor(cos_theta_i: f64, ior: &ComplexIor, film: &ThinFilm) -> Color3f {
+    let r = |eta: f64, k: f64, wavelength| {
+        film.reflectance(cos_theta_i, 1.0, Complex::new(eta, k), wavelength)
+    };
+    Color3f::new(
+        r(ior.eta.x, ior.k.x, RGB_WAVELENGTHS[0]),
+        r(ior.eta.y, ior.k.y, RGB_WAVELENGTHS[1]),
+        r(ior.eta.z, ior.k.z, RGB_WAVELENGTHS[2]),
+    )
+}
+
+/// [fr_complex] for each color channel.
+pub fn fr_conductor(cos_theta_i: f64, ior: &ComplexIor) -> Color3f {
+    Color3f::new(
+        fr_complex(cos_theta_i, Complex::new(ior.eta.x, ior.k.x)),
+        fr_complex(cos_theta_i, Complex::new(ior.eta.y, ior.k.y)),
+        fr_complex(cos_theta_i, Complex::new(ior.eta.z, ior.k.z)),
+    )
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dielectric_normal_incidence() {
+        // ((n - 1) / (n + 1))^2 for glass
+        let expected = (0.5_f64 / 2.5).powi(2);
+        assert!((fr_dielectric(1.0, 1.5) - expected).abs() < 1e-12);
+        assert!((fr_dielectric(-1.0, 1.5) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn dielectric_grazing_and_tir() {
+        assert!((fr_dielectric(0.0, 1.5) - 1.0).abs() < 1e-12);
+        assert_eq!(fr_dielectric(-0.1, 1.5), 1.0);
+        assert_eq!(fr_dielectric(0.7, 1.0), 0.0);
+    }
+
+    #[test]
+    fn complex_matches_dielectric_without_absorption() {
+        for cos_theta in [0.1, 0.5, 0.9, 1.0] {
+            let complex = fr_complex(cos_theta, Complex::from_real(1.5));
+            assert!((complex - fr_dielectric(cos_theta, 1.5)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn conductor_normal_incidence() {
+        // ((n - 1)^2 + k^2) / ((n + 1)^2 + k^2)
+        let ior = ComplexIor::GOLD;
+        let r = fr_conductor(1.0, &ior);
+        let expected = ((ior.eta.x - 1.0).powi(2) + ior.k.x.powi(2))
+            / ((ior.eta.x + 1.0).powi(2) + ior.k.x.powi(2));
+        assert!((r.x - expected).abs() < 1e-12);
+
+        // Gold is yellow
+        assert!(r.x > r.y && r.y > r.z);
+        assert!((fr_conductor(0.0, &ior).x - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn vanishing_film_has_no_effect() {
+        let thin = ThinFilm::new(0.0, 1.33);
+        let invisible = ThinFilm::new(500.0, 1.0);
+        for cos_theta in [-0.9, -0.5, 0.1, 0.5, 0.9, 1.0] {
+            let expected = fr_dielectric(cos_theta, 1.5);
+            for film in [thin, invisible] {
+                let r = fr_thin_film_dielectric(cos_theta, 1.5, &film);
+                assert!((r - Color3f::new(1.0, 1.0, 1.0) * expected).length() < 1e-9);
+            }
+        }
+
+        let r = fr_thin_film_conductor(0.7, &ComplexIor::GOLD, &thin);
+        assert!((r - fr_conductor(0.7, &ComplexIor::GOLD)).length() < 1e-9);
+    }
+
+    #[test]
+    fn film_interferes() {
+        // A quarter wave film between air and glass cancels the green reflection
+        let eta_film = 1.5_f64.sqrt();
+        let film = ThinFilm::new(RGB_WAVELENGTHS[1] / (4.0 * eta_film), eta_film);
+        let r = fr_thin_film_dielectric(1.0, 1.5, &film);
+        assert!(r.y < 1e-9, "{:?}", r);
+        assert!(r.x > 1e-4 && r.z > 1e-4);
+
+        // Without any dispersion the colors still shift with the viewing angle
+        let soap = ThinFilm::new(400.0, 1.33);
+        let head_on = fr_thin_film_dielectric(1.0, 1.0, &soap);
+        let oblique = fr_thin_film_dielectric(0.5, 1.0, &soap);
+        assert!((head_on - oblique).abs().max_component() > 1e-2);
+        assert!(head_on.max_component() <= 1.0 && oblique.max_component() <= 1.0);
+    }
+
+    #[test]
+    fn film_total_internal_reflection() {
+        let film = ThinFilm::new(300.0, 1.33);
+        let r = fr_thin_film_dielectric(-0.1, 1.5, &film);
+        assert!((r - Color3f::new(1.0, 1.0, 1.0)).length() < 1e-9, "{:?}", r);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
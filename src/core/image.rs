@@ -0,0 +1,222 @@
+use std::fmt;
+use std::ops::{Add, Mul};
+use std::path::Path;
+
+use crate::core::vector::{Color3f, Point2f};
+use crate::images::hdr_reader::read_hdr;
+use crate::images::png_reader::read_png;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A grid of texels, stored row by row from the top left.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image<T> {
+    width: usize,
+    height: usize,
+    texels: Vec<T>,
+}
+
+/// How lookups outside of an [Image] are brought back inside it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WrapMode {
+    /// Tiles the image
+    Repeat,
+
+    /// Extends the edge texels outward
+    Clamp,
+
+    /// Tiles the image, flipping every other tile so that edges meet seamlessly
+    Mirror,
+}
+
+/// How the values stored in an 8 or 16-bit image relate to light.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorEncoding {
+    /// Values are proportional to light, as in normal, roughness or height maps
+    Linear,
+
+    /// Values follow the sRGB curve, as in most photographs and painted color maps
+    Srgb,
+}
+
+/// Why an [Image] could not be read.
+#[derive(Debug)]
+pub enum ImageError {
+    Io(std::io::Error),
+    Png(png::DecodingError),
+
+    /// The file is damaged, or uses a feature that isn't supported
+    Format(String),
+
+    /// The file extension is not one of `png` or `hdr`
+    UnknownExtension,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<T: Copy> Image<T> {
+    /// Panics unless there are exactly `width * height` texels.
+    pub fn new(width: usize, height: usize, texels: Vec<T>) -> Self {
+        assert!(width > 0 && height > 0, "image must not be empty");
+        assert_eq!(texels.len(), width * height, "texel count must match size");
+        Self {
+            width,
+            height,
+            texels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The texel at column `x` and row `y`, wrapped back into the image if outside of it.
+    pub fn get(&self, x: i64, y: i64, wrap: WrapMode) -> T {
+        let x = wrap.apply(x, self.width);
+        let y = wrap.apply(y, self.height);
+        self.texels[y * self.width + x]
+    }
+
+    /// Applies `f` to every texel, for example to turn a color map into a scalar one.
+    pub fn map<U: Copy>(&self, f: impl Fn(T) -> U) -> Image<U> {
+        Image::new(
+            self.width,
+            self.height,
+            self.texels.iter().map(|&texel| f(texel)).collect(),
+        )
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<f64, Output = T>> Image<T> {
+    /// Bilinearly interpolates between the four texels nearest to `st`,
+    /// where `(0, 0)` is the top left corner and `(1, 1)` the bottom right one.
+    pub fn bilerp(&self, st: &Point2f, wrap: WrapMode) -> T {
+        // Texel centers lie at half-integer coordinates
+        let x = st.x * self.width as f64 - 0.5;
+        let y = st.y * self.height as f64 - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (dx, dy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+
+        self.get(x0, y0, wrap) * ((1.0 - dx) * (1.0 - dy))
+            + self.get(x0 + 1, y0, wrap) * (dx * (1.0 - dy))
+            + self.get(x0, y0 + 1, wrap) * ((1.0 - dx) * dy)
+            + self.get(x0 + 1, y0 + 1, wrap) * (dx * dy)
+    }
+}
+
+impl Image<Color3f> {
+    /// Reads a PNG or Radiance HDR file, chosen by extension.
+    /// `encoding` is ignored for HDR files, which always store linear values.
+    pub fn read(path: &Path, encoding: ColorEncoding) -> Result<Self, ImageError> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("png") => read_png(path, encoding),
+            Some("hdr") => read_hdr(path),
+            _ => Err(ImageError::UnknownExtension),
+        }
+    }
+}
+
+impl WrapMode {
+    /// Brings `i` into `[0, size)`.
+    fn apply(&self, i: i64, size: usize) -> usize {
+        let size = size as i64;
+        let i = match self {
+            WrapMode::Repeat => i.rem_euclid(size),
+            WrapMode::Clamp => i.clamp(0, size - 1),
+            WrapMode::Mirror => {
+                let i = i.rem_euclid(2 * size);
+                if i < size {
+                    i
+                } else {
+                    2 * size - 1 - i
+                }
+            }
+        };
+        i as usize
+    }
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImageError::Io(error) => write!(f, "could not read image: {}", error),
+            ImageError::Png(error) => write!(f, "could not decode PNG: {}", error),
+            ImageError::Format(message) => write!(f, "malformed image: {}", message),
+            ImageError::UnknownExtension => write!(f, "unknown image extension"),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
+
+impl From<std::io::Error> for ImageError {
+    fn from(error: std::io::Error) -> Self {
+        ImageError::Io(error)
+    }
+}
+
+impl From<png::DecodingError> for ImageError {
+    fn from(error: png::DecodingError) -> Self {
+        ImageError::Png(error)
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bilerp() {
+        let image = Image::new(2, 1, vec![0.0, 1.0]);
+        let at = |s: f64, wrap: WrapMode| image.bilerp(&Point2f::new(s, 0.5), wrap);
+
+        // Exactly at texel centers
+        assert_eq!(at(0.25, WrapMode::Repeat), 0.0);
+        assert_eq!(at(0.75, WrapMode::Repeat), 1.0);
+
+        // Between them, and across the edge
+        assert!((at(0.5, WrapMode::Repeat) - 0.5).abs() < 1e-12);
+        assert!((at(0.0, WrapMode::Repeat) - 0.5).abs() < 1e-12);
+        assert!((at(0.0, WrapMode::Clamp) - 0.0).abs() < 1e-12);
+        assert!((at(1.25, WrapMode::Repeat) - 0.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn wrap_modes() {
+        let image = Image::new(3, 1, vec![0, 1, 2]);
+        let row = |wrap: WrapMode| (-4..7).map(|x| image.get(x, 0, wrap)).collect::<Vec<_>>();
+
+        assert_eq!(row(WrapMode::Repeat), [2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0]);
+        assert_eq!(row(WrapMode::Clamp), [0, 0, 0, 0, 0, 1, 2, 2, 2, 2, 2]);
+        assert_eq!(row(WrapMode::Mirror), [2, 2, 1, 0, 0, 1, 2, 2, 1, 0, 0]);
+    }
+
+    #[test]
+    fn unknown_extension() {
+        let result = Image::read(Path::new("texture.tga"), ColorEncoding::Srgb);
+        assert!(matches!(result, Err(ImageError::UnknownExtension)));
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -1,6 +1,6 @@
-use crate::core::accelerator::Accelerator;
 use crate::core::camera::Camera;
 use crate::core::film::Film;
+use crate::core::scene::Scene;
 
 /////////////////////
 // BEGIN INTERFACE //
@@ -8,12 +8,7 @@ use crate::core::film::Film;
 
 /// Responsible for aggregating light information.
 pub trait Integrator<'a> {
-    fn render(
-        &mut self,
-        camera: &dyn Camera,
-        accelerator: &dyn Accelerator,
-        film: &'a mut dyn Film,
-    );
+    fn render(&mut self, camera: &dyn Camera, scene: &Scene, film: &'a mut dyn Film);
 }
 
 ////////////////////
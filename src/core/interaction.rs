@@ -1,14 +1,19 @@
 use std::cmp::{Ordering, PartialEq, PartialOrd};
+use std::fmt;
 
-use crate::core::vector::{Point3f, Vec3f};
+use crate::core::frame::Frame;
+use crate::core::material::Material;
+use crate::core::primitive::Primitive;
+use crate::core::ray::{Ray, RayDifferential};
+use crate::core::vector::{Color3f, Point2f, Point3f, Vec3f};
 
 /////////////////////
 // BEGIN INTERFACE //
 /////////////////////
 
 /// Represents the collision of light with some participating medium.
-#[derive(Debug, Clone, Copy)]
-pub struct Interaction {
+#[derive(Clone, Copy)]
+pub struct Interaction<'a> {
     /// The location of the hit
     pub p: Point3f,
 
@@ -21,6 +26,46 @@ pub struct Interaction {
 
     /// The wo term as represented in the rendering equation
     pub wo: Vec3f,
+
+    /// The surface coordinates of the hit
+    pub uv: Point2f,
+
+    /// How the position changes with `u` and `v`, zero if the surface has no parameterization
+    pub dpdu: Vec3f,
+    pub dpdv: Vec3f,
+
+    /// How the normal changes with `u` and `v`
+    pub dndu: Vec3f,
+    pub dndv: Vec3f,
+
+    /// How the position and surface coordinates change to the neighbouring pixels,
+    /// zero unless the ray carried [RayDifferential]s
+    pub dpdx: Vec3f,
+    pub dpdy: Vec3f,
+    pub dudx: f64,
+    pub dvdx: f64,
+    pub dudy: f64,
+    pub dvdy: f64,
+
+    /// The geometry materials scatter with, which bump maps and interpolated normals
+    /// may perturb away from the true surface above
+    pub shading: Shading,
+
+    /// The [Primitive] that was hit, [None] for media collisions
+    pub primitive: Option<&'a dyn Primitive>,
+
+    /// Where the hit lies within the triangle it was found on, [None] for other shapes
+    pub barycentrics: Option<[f64; 3]>,
+}
+
+/// The perturbed normal and derivatives used for shading.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Shading {
+    pub n: Vec3f,
+    pub dpdu: Vec3f,
+    pub dpdv: Vec3f,
+    pub dndu: Vec3f,
+    pub dndv: Vec3f,
 }
 
 //////////////////////////
@@ -28,18 +73,262 @@ pub struct Interaction {
 // BEGIN IMPLEMENTATION //
 //////////////////////////
 
-impl Interaction {
+impl<'a> Interaction<'a> {
     pub fn new_on_surface(p: Point3f, t: f64, n: Vec3f, wo: Vec3f) -> Self {
         Self {
             p,
             t,
             n: Some(n),
             wo,
+            uv: Point2f::default(),
+            dpdu: Vec3f::default(),
+            dpdv: Vec3f::default(),
+            dndu: Vec3f::default(),
+            dndv: Vec3f::default(),
+            dpdx: Vec3f::default(),
+            dpdy: Vec3f::default(),
+            dudx: 0.0,
+            dvdx: 0.0,
+            dudy: 0.0,
+            dvdy: 0.0,
+            shading: Shading {
+                n,
+                ..Shading::default()
+            },
+            primitive: None,
+            barycentrics: None,
         }
     }
 
     pub fn new_in_media(p: Point3f, t: f64, wo: Vec3f) -> Self {
-        Self { p, t, n: None, wo }
+        Self {
+            p,
+            t,
+            n: None,
+            wo,
+            uv: Point2f::default(),
+            dpdu: Vec3f::default(),
+            dpdv: Vec3f::default(),
+            dndu: Vec3f::default(),
+            dndv: Vec3f::default(),
+            dpdx: Vec3f::default(),
+            dpdy: Vec3f::default(),
+            dudx: 0.0,
+            dvdx: 0.0,
+            dudy: 0.0,
+            dvdy: 0.0,
+            shading: Shading::default(),
+            primitive: None,
+            barycentrics: None,
+        }
+    }
+
+    pub fn with_uv(self, uv: Point2f) -> Self {
+        Self { uv, ..self }
+    }
+
+    /// Sets the partial derivatives of the surface, for shading as well.
+    pub fn with_derivatives(self, dpdu: Vec3f, dpdv: Vec3f, dndu: Vec3f, dndv: Vec3f) -> Self {
+        Self {
+            dpdu,
+            dpdv,
+            dndu,
+            dndv,
+            shading: Shading {
+                dpdu,
+                dpdv,
+                dndu,
+                dndv,
+                ..self.shading
+            },
+            ..self
+        }
+    }
+
+    /// Replaces the shading geometry, turning `n` to the side of the true normal.
+    pub fn with_shading(
+        self,
+        n: Vec3f,
+        dpdu: Vec3f,
+        dpdv: Vec3f,
+        dndu: Vec3f,
+        dndv: Vec3f,
+    ) -> Self {
+        let n = match self.n {
+            Some(geometric) if n.dot(&geometric) < 0.0 => -n,
+            _ => n,
+        };
+        Self {
+            shading: Shading {
+                n,
+                dpdu,
+                dpdv,
+                dndu,
+                dndv,
+            },
+            ..self
+        }
+    }
+
+    pub fn with_barycentrics(self, barycentrics: [f64; 3]) -> Self {
+        Self {
+            barycentrics: Some(barycentrics),
+            ..self
+        }
+    }
+
+    pub fn with_primitive(self, primitive: &'a dyn Primitive) -> Self {
+        Self {
+            primitive: Some(primitive),
+            ..self
+        }
+    }
+
+    /// Finds where the offset rays of `ray` meet the tangent plane at the hit,
+    /// and from that the screen space derivatives of the position and surface coordinates.
+    /// Must follow [with_derivatives](Self::with_derivatives).
+    pub fn with_ray_differentials(self, ray: &Ray) -> Self {
+        let (Some(rd), Some(n)) = (ray.differentials, self.n) else {
+            return self;
+        };
+
+        let d = n.dot(&self.p);
+        let offset_hit = |o: Point3f, dir: Vec3f| {
+            let t = (d - n.dot(&o)) / n.dot(&dir);
+            t.is_finite().then(|| o + dir * t - self.p)
+        };
+        let (Some(dpdx), Some(dpdy)) = (offset_hit(rd.rx_o, rd.rx_d), offset_hit(rd.ry_o, rd.ry_d))
+        else {
+            return self;
+        };
+
+        // Least squares fit of dpdx = dpdu dudx + dpdv dvdx, and likewise for y
+        let ata00 = self.dpdu.dot(&self.dpdu);
+        let ata01 = self.dpdu.dot(&self.dpdv);
+        let ata11 = self.dpdv.dot(&self.dpdv);
+        let inv_det = 1.0 / (ata00 * ata11 - ata01 * ata01);
+        let inv_det = if inv_det.is_finite() { inv_det } else { 0.0 };
+        let solve = |dp: Vec3f| {
+            let (b0, b1) = (self.dpdu.dot(&dp), self.dpdv.dot(&dp));
+            let du = (ata11 * b0 - ata01 * b1) * inv_det;
+            let dv = (ata00 * b1 - ata01 * b0) * inv_det;
+            (du.clamp(-1e8, 1e8), dv.clamp(-1e8, 1e8))
+        };
+        let (dudx, dvdx) = solve(dpdx);
+        let (dudy, dvdy) = solve(dpdy);
+
+        Self {
+            dpdx,
+            dpdy,
+            dudx,
+            dvdx,
+            dudy,
+            dvdy,
+            ..self
+        }
+    }
+
+    /// The offset rays for a perfect mirror reflection along `wi`, following the curvature
+    /// of the shading normal (Igehy 1999).
+    /// [None] unless the incoming `ray` carried differentials.
+    pub fn reflected_differentials(&self, ray: &Ray, wi: &Vec3f) -> Option<RayDifferential> {
+        let rd = ray.differentials?;
+        let (n, dndx, dndy) = self.shading_normal_differentials();
+        let wo = self.wo.normalize();
+
+        let offset = |dpdx: Vec3f, d: Vec3f, dndx: Vec3f| {
+            let dwodx = -d - wo;
+            let ddndx = dwodx.dot(&n) + wo.dot(&dndx);
+            (
+                self.p + dpdx,
+                *wi - dwodx + (dndx * wo.dot(&n) + n * ddndx) * 2.0,
+            )
+        };
+        let (rx_o, rx_d) = offset(self.dpdx, rd.rx_d, dndx);
+        let (ry_o, ry_d) = offset(self.dpdy, rd.ry_d, dndy);
+        Some(RayDifferential {
+            rx_o,
+            rx_d,
+            ry_o,
+            ry_d,
+        })
+    }
+
+    /// The offset rays for a perfect refraction along `wi` through a surface
+    /// with relative index of refraction `eta` (inside over outside), as in [refract](crate::core::bxdf::refract).
+    /// [None] unless the incoming `ray` carried differentials.
+    pub fn refracted_differentials(
+        &self,
+        ray: &Ray,
+        wi: &Vec3f,
+        eta: f64,
+    ) -> Option<RayDifferential> {
+        let rd = ray.differentials?;
+        let (mut n, mut dndx, mut dndy) = self.shading_normal_differentials();
+        let wo = self.wo.normalize();
+
+        // The ratio of indices on the incident side over the transmitted side
+        let mut eta = 1.0 / eta;
+        if wo.dot(&n) < 0.0 {
+            eta = 1.0 / eta;
+            n = -n;
+            dndx = -dndx;
+            dndy = -dndy;
+        }
+
+        let cos_i = wo.dot(&n);
+        let cos_t = wi.dot(&n).abs();
+        let mu = eta * cos_i - cos_t;
+        let offset = |dpdx: Vec3f, d: Vec3f, dndx: Vec3f| {
+            let dwodx = -d - wo;
+            let ddndx = dwodx.dot(&n) + wo.dot(&dndx);
+            let dmudx = (eta - eta * eta * cos_i / cos_t) * ddndx;
+            (self.p + dpdx, *wi - dwodx * eta + (dndx * mu + n * dmudx))
+        };
+        let (rx_o, rx_d) = offset(self.dpdx, rd.rx_d, dndx);
+        let (ry_o, ry_d) = offset(self.dpdy, rd.ry_d, dndy);
+        Some(RayDifferential {
+            rx_o,
+            rx_d,
+            ry_o,
+            ry_d,
+        })
+    }
+
+    /// The shading normal and how it changes to the neighbouring pixels.
+    fn shading_normal_differentials(&self) -> (Vec3f, Vec3f, Vec3f) {
+        let shading = self.shading;
+        let dndx = shading.dndu * self.dudx + shading.dndv * self.dvdx;
+        let dndy = shading.dndu * self.dudy + shading.dndv * self.dvdy;
+        (shading.n.normalize(), dndx, dndy)
+    }
+
+    /// The radiance emitted from this point along `w`, black unless the hit glows.
+    pub fn le(&self, w: &Vec3f) -> Color3f {
+        self.primitive
+            .and_then(|primitive| primitive.area_light())
+            .map_or(Color3f::default(), |light| light.l(self, w))
+    }
+
+    /// The material of the hit [Primitive], if it has one.
+    pub fn material(&self) -> Option<&'a dyn Material> {
+        self.primitive?.material()
+    }
+
+    /// The local frame materials should scatter in, around the shading normal
+    /// and lined up with `dpdu` where the surface has one, so anisotropy follows it.
+    /// [None] for media collisions, which have no surface.
+    pub fn shading_frame(&self) -> Option<Frame> {
+        self.n?;
+        let n = self.shading.n.normalize();
+
+        // Gram-Schmidt, unless dpdu is missing or runs along the normal
+        let s = self.shading.dpdu - n * n.dot(&self.shading.dpdu);
+        if s.length_sq() <= 1e-12 * self.shading.dpdu.length_sq() {
+            return Some(Frame::from_normal(n));
+        }
+        let s = s.normalize();
+        Some(Frame::new(s, n.cross(&s), n))
     }
 
     pub fn is_eq(&self, other: &Self) -> bool {
@@ -47,13 +336,31 @@ impl Interaction {
     }
 }
 
-impl PartialOrd for Interaction {
+impl<'a> fmt::Debug for Interaction<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Interaction")
+            .field("p", &self.p)
+            .field("t", &self.t)
+            .field("n", &self.n)
+            .field("wo", &self.wo)
+            .field("uv", &self.uv)
+            .field("dpdu", &self.dpdu)
+            .field("dpdv", &self.dpdv)
+            .field("dpdx", &self.dpdx)
+            .field("dpdy", &self.dpdy)
+            .field("shading", &self.shading)
+            .field("primitive", &self.primitive.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> PartialOrd for Interaction<'a> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         self.t.partial_cmp(&other.t)
     }
 }
 
-impl PartialEq for Interaction {
+impl<'a> PartialEq for Interaction<'a> {
     fn eq(&self, other: &Self) -> bool {
         self.t == other.t
     }
@@ -67,6 +374,7 @@ impl PartialEq for Interaction {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::bxdf::{reflect, refract};
 
     #[test]
     fn ord() {
@@ -88,6 +396,97 @@ mod tests {
         assert!(b > a);
         assert!(a == a);
     }
+
+    #[test]
+    fn shading_frame() {
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let flat = Interaction::new_on_surface(Point3f::default(), 1.0, n, n);
+        assert_eq!(flat.shading_frame(), Some(Frame::from_normal(n)));
+
+        // Lined up with dpdu, made orthogonal to the normal
+        let dpdu = Vec3f::new(1.0, 1.0, 1.0);
+        let zero = Vec3f::default();
+        let parameterized = flat.with_derivatives(dpdu, zero, zero, zero);
+        let frame = parameterized.shading_frame().unwrap();
+        assert!((frame.s - Vec3f::new(1.0, 1.0, 0.0).normalize()).length() < 1e-12);
+        assert!((frame.s.cross(&frame.t) - n).length() < 1e-12);
+
+        // Shading normals stay on the side of the surface
+        let bumped = parameterized.with_shading(-n, dpdu, zero, zero, zero);
+        assert_eq!(bumped.shading.n, n);
+
+        let media = Interaction::new_in_media(Point3f::default(), 1.0, n);
+        assert!(media.shading_frame().is_none());
+    }
+
+    /// A ray from above onto the xy plane along `d`, with offset rays `spread` apart.
+    fn ray_from_above(d: Vec3f, spread: f64) -> Ray {
+        let o = Point3f::new(0.0, 0.0, 2.0);
+        Ray::new(o, d, 0.0, 100.0).with_differentials(RayDifferential {
+            rx_o: o,
+            rx_d: (d + Vec3f::new(spread, 0.0, 0.0)).normalize(),
+            ry_o: o,
+            ry_d: (d + Vec3f::new(0.0, spread, 0.0)).normalize(),
+        })
+    }
+
+    fn plane_hit(ray: &Ray) -> Interaction<'static> {
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let zero = Vec3f::default();
+        let t = -ray.o.z / ray.d.z;
+        Interaction::new_on_surface(ray.o + ray.d * t, t, n, -ray.d)
+            .with_derivatives(
+                Vec3f::new(2.0, 0.0, 0.0),
+                Vec3f::new(0.0, 1.0, 0.0),
+                zero,
+                zero,
+            )
+            .with_ray_differentials(ray)
+    }
+
+    #[test]
+    fn ray_differentials() {
+        let hit = plane_hit(&ray_from_above(Vec3f::new(0.0, 0.0, -1.0), 0.01));
+        assert!((hit.dpdx - Vec3f::new(0.02, 0.0, 0.0)).length() < 1e-12);
+        assert!((hit.dpdy - Vec3f::new(0.0, 0.02, 0.0)).length() < 1e-12);
+        assert!((hit.dudx - 0.01).abs() < 1e-12 && hit.dvdx.abs() < 1e-12);
+        assert!(hit.dudy.abs() < 1e-12 && (hit.dvdy - 0.02).abs() < 1e-12);
+
+        // Nothing to do without differentials
+        let plain = Ray::new(
+            Point3f::new(0.0, 0.0, 2.0),
+            Vec3f::new(0.0, 0.0, -1.0),
+            0.0,
+            9.0,
+        );
+        assert_eq!(plane_hit(&plain).dpdx, Vec3f::default());
+    }
+
+    #[test]
+    fn specular_differentials() {
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let ray = ray_from_above(Vec3f::new(0.3, 0.1, -1.0).normalize(), 1e-3);
+        let hit = plane_hit(&ray);
+        let rd = ray.differentials.unwrap();
+
+        // A flat mirror mirrors the offset rays too
+        let wi = reflect(&hit.wo, &n);
+        let reflected = hit.reflected_differentials(&ray, &wi).unwrap();
+        assert!((reflected.rx_o - (hit.p + hit.dpdx)).length() < 1e-12);
+        assert!((reflected.rx_d - reflect(&-rd.rx_d, &n)).length() < 1e-9);
+        assert!((reflected.ry_d - reflect(&-rd.ry_d, &n)).length() < 1e-9);
+
+        // And refraction bends them by Snell's law, to first order
+        let eta = 1.5;
+        let (wi, _) = refract(&hit.wo, &n, eta).unwrap();
+        let refracted = hit.refracted_differentials(&ray, &wi, eta).unwrap();
+        let (expected, _) = refract(&-rd.rx_d.normalize(), &n, eta).unwrap();
+        assert!((refracted.rx_d.normalize() - expected).length() < 1e-5);
+
+        assert!(hit
+            .reflected_differentials(&Ray::new(hit.p, wi, 0.0, 1.0), &wi)
+            .is_none());
+    }
 }
 
 ///////////////
@@ -0,0 +1,295 @@
+use crate::core::accelerator::Accelerator;
+use crate::core::bounds::{Bounds3f, DirectionCone};
+use crate::core::interaction::Interaction;
+use crate::core::ray::Ray;
+use crate::core::vector::{Color3f, Point2f, Point3f, Vec3f};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A source of light in the scene.
+pub trait Light {
+    /// Picks a direction from `reference` towards the light with `u`.
+    /// Returns [None] when no light arrives along the chosen direction.
+    fn sample_li(&self, reference: &Interaction, u: &Point2f) -> Option<LightSample>;
+
+    /// The density, with respect to solid angle, of [Light::sample_li] choosing `wi` from `reference`.
+    /// Always 0.0 for delta lights, which nothing else can sample.
+    fn pdf_li(&self, reference: &Interaction, wi: &Vec3f) -> f64;
+
+    /// The total power the light emits.
+    fn power(&self) -> Color3f;
+
+    /// Whether the light sits at a single point or shines from a single direction,
+    /// so that rays can never hit it by chance.
+    fn is_delta(&self) -> bool;
+
+    /// The radiance arriving along `ray` when it leaves the scene without hitting anything.
+    /// Only lights surrounding the whole scene have any.
+    fn le(&self, _ray: &Ray) -> Color3f {
+        Color3f::default()
+    }
+
+    /// Called with the extent of the scene before rendering,
+    /// for lights whose emission depends on it.
+    fn preprocess(&mut self, _scene_bounds: &Bounds3f) {}
+
+    /// Where the light sits and which way it shines, for
+    /// [LightSampler](crate::core::light_sampler::LightSampler)s to estimate its contribution.
+    /// [None] for lights surrounding the whole scene.
+    fn bounds(&self) -> Option<LightBounds> {
+        None
+    }
+}
+
+/// A [Light] covering the surface of a shape, seen by rays that hit it.
+pub trait AreaLight: Light {
+    /// The radiance leaving `interaction`, a point on the light's surface, along `w`.
+    fn l(&self, interaction: &Interaction, w: &Vec3f) -> Color3f;
+}
+
+/// The result of [Light::sample_li].
+#[derive(Debug, Clone, Copy)]
+pub struct LightSample {
+    /// The radiance arriving at the reference point
+    pub li: Color3f,
+
+    /// The normalized direction from the reference point towards the light
+    pub wi: Vec3f,
+
+    /// The density of choosing `wi`, 1.0 for delta lights
+    pub pdf: f64,
+
+    /// Runs from the reference point to the light, which is visible if the ray hits nothing
+    pub visibility: Ray,
+}
+
+/// A conservative summary of a [Light], or of several, bounding the emission
+/// that can reach any point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LightBounds {
+    /// Where the emitting points lie
+    pub bounds: Bounds3f,
+
+    /// Bounds the surface normals, or the axis of the spot, with `cos_theta`
+    pub normals: DirectionCone,
+
+    /// How far past `normals` light still leaves the surface, as the cosine of the angle
+    pub cos_theta_e: f64,
+
+    /// The emitted power, summed over the color channels' average
+    pub phi: f64,
+
+    /// Whether light also leaves against `normals`
+    pub two_sided: bool,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// How far shadow rays stay from both of their ends, so they don't hit the surfaces they join.
+const SHADOW_EPSILON: f64 = 1e-6;
+
+impl LightBounds {
+    /// A rough upper bound on the light from these bounds reaching `p`,
+    /// on a surface with normal `n` if given. Only meaningful relative to other bounds.
+    pub fn importance(&self, p: &Point3f, n: Option<&Vec3f>) -> f64 {
+        let center = self.bounds.centroid();
+        // Don't let points inside the bounds blow up
+        let distance_sq = (*p - center)
+            .length_sq()
+            .max(self.bounds.diagonal().length() / 2.0);
+        let d = *p - center;
+        let wi = if d.length_sq() > 0.0 {
+            d.normalize()
+        } else {
+            self.normals.w
+        };
+        let mut cos_theta_w = self.normals.w.dot(&wi);
+        if self.two_sided {
+            cos_theta_w = cos_theta_w.abs();
+        }
+        let sin_theta_w = sin_from_cos(cos_theta_w);
+
+        // The smallest angle between the emission and `p`, over all directions towards the bounds
+        let cos_theta_b = self.bounds.subtended_directions(p).cos_theta;
+        let sin_theta_b = sin_from_cos(cos_theta_b);
+        let cos_theta_o = self.normals.cos_theta;
+        let sin_theta_o = sin_from_cos(cos_theta_o);
+        let (sin_theta_x, cos_theta_x) =
+            subtract_clamped(sin_theta_w, cos_theta_w, sin_theta_o, cos_theta_o);
+        let cos_theta_p = subtract_clamped(sin_theta_x, cos_theta_x, sin_theta_b, cos_theta_b).1;
+        if cos_theta_p <= self.cos_theta_e {
+            return 0.0;
+        }
+
+        let mut importance = self.phi * cos_theta_p / distance_sq;
+        if let Some(n) = n {
+            let cos_theta_i = wi.dot(n).abs();
+            let sin_theta_i = sin_from_cos(cos_theta_i);
+            importance *= subtract_clamped(sin_theta_i, cos_theta_i, sin_theta_b, cos_theta_b).1;
+        }
+        importance.max(0.0)
+    }
+
+    /// Bounds covering both `self` and `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        if self.phi == 0.0 {
+            return *other;
+        }
+        if other.phi == 0.0 {
+            return *self;
+        }
+        Self {
+            bounds: self.bounds.union(&other.bounds),
+            normals: self.normals.union(&other.normals),
+            cos_theta_e: self.cos_theta_e.min(other.cos_theta_e),
+            phi: self.phi + other.phi,
+            two_sided: self.two_sided || other.two_sided,
+        }
+    }
+}
+
+fn sin_from_cos(cos_theta: f64) -> f64 {
+    (1.0 - cos_theta * cos_theta).max(0.0).sqrt()
+}
+
+/// The sine and cosine of the angle `a - b`, clamped to zero when `b` is wider than `a`.
+fn subtract_clamped(sin_a: f64, cos_a: f64, sin_b: f64, cos_b: f64) -> (f64, f64) {
+    if cos_a > cos_b {
+        return (0.0, 1.0);
+    }
+    (sin_a * cos_b - cos_a * sin_b, cos_a * cos_b + sin_a * sin_b)
+}
+
+impl LightSample {
+    /// A sample of light arriving at `from` from the point `to`.
+    pub fn from_point(li: Color3f, pdf: f64, from: &Point3f, to: &Point3f) -> Self {
+        let d = *to - *from;
+        let distance = d.length();
+        let wi = d / distance;
+        Self {
+            li,
+            wi,
+            pdf,
+            visibility: Ray::new(*from, wi, SHADOW_EPSILON, distance * (1.0 - SHADOW_EPSILON)),
+        }
+    }
+
+    /// A sample of light arriving at `from` along `wi` from infinitely far away.
+    pub fn from_direction(li: Color3f, pdf: f64, from: &Point3f, wi: &Vec3f) -> Self {
+        let wi = wi.normalize();
+        Self {
+            li,
+            wi,
+            pdf,
+            visibility: Ray::new(*from, wi, SHADOW_EPSILON, f64::INFINITY),
+        }
+    }
+
+    pub fn is_unoccluded(&self, accelerator: &dyn Accelerator) -> bool {
+        accelerator.test(&self.visibility).is_none()
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::accelerators::simple_list::SimpleList;
+    use crate::core::primitive::Primitive;
+    use crate::primitives::sphere::Sphere;
+
+    #[test]
+    fn visibility() {
+        let sphere = Sphere::new(Point3f::new(0.0, 0.0, 5.0), 1.0);
+        let primitives = vec![&sphere as &dyn Primitive];
+        let accelerator = SimpleList::new(&primitives);
+        let white = Color3f::new(1.0, 1.0, 1.0);
+        let origin = Point3f::default();
+
+        let behind = LightSample::from_point(white, 1.0, &origin, &Point3f::new(0.0, 0.0, 10.0));
+        assert_eq!(behind.wi, Vec3f::new(0.0, 0.0, 1.0));
+        assert!(!behind.is_unoccluded(&accelerator));
+
+        let in_front = LightSample::from_point(white, 1.0, &origin, &Point3f::new(0.0, 0.0, 3.0));
+        assert!(in_front.is_unoccluded(&accelerator));
+
+        // Ending on a surface doesn't count as hitting it
+        let on_surface = LightSample::from_point(white, 1.0, &origin, &Point3f::new(0.0, 0.0, 4.0));
+        assert!(on_surface.is_unoccluded(&accelerator));
+
+        let up = LightSample::from_direction(white, 1.0, &origin, &Vec3f::new(0.0, 0.0, 2.0));
+        assert_eq!(up.wi, Vec3f::new(0.0, 0.0, 1.0));
+        assert!(!up.is_unoccluded(&accelerator));
+        let side = LightSample::from_direction(white, 1.0, &origin, &Vec3f::new(1.0, 0.0, 0.0));
+        assert!(side.is_unoccluded(&accelerator));
+    }
+
+    fn bounds_at(p: Point3f, normal: Vec3f, cos_theta_o: f64) -> LightBounds {
+        LightBounds {
+            bounds: Bounds3f::from_point(p),
+            normals: DirectionCone::new(normal, cos_theta_o),
+            cos_theta_e: 0.0,
+            phi: 1.0,
+            two_sided: false,
+        }
+    }
+
+    #[test]
+    fn importance() {
+        let down = Vec3f::new(0.0, 0.0, -1.0);
+        let light = bounds_at(Point3f::new(0.0, 0.0, 2.0), down, 1.0);
+        let origin = Point3f::default();
+
+        // Inverse square falloff straight under the light
+        assert!((light.importance(&origin, None) - 0.25).abs() < 1e-12);
+        let far = light.importance(&Point3f::new(0.0, 0.0, -2.0), None);
+        assert!((far - 1.0 / 16.0).abs() < 1e-12);
+
+        // Tilted away from the light, and out of its reach above it
+        let up = Vec3f::new(0.0, 0.0, 1.0);
+        let tilted = Vec3f::new(1.0, 0.0, 1.0).normalize();
+        assert!((light.importance(&origin, Some(&up)) - 0.25).abs() < 1e-12);
+        let tilted_importance = light.importance(&origin, Some(&tilted));
+        assert!((tilted_importance - 0.25 * tilted.z).abs() < 1e-12);
+        assert_eq!(light.importance(&Point3f::new(0.0, 0.0, 3.0), None), 0.0);
+        let two_sided = LightBounds {
+            two_sided: true,
+            ..light
+        };
+        assert!(two_sided.importance(&Point3f::new(0.0, 0.0, 3.0), None) > 0.0);
+
+        // Sideways, the light's surface is seen edge on
+        assert_eq!(light.importance(&Point3f::new(5.0, 0.0, 2.0), None), 0.0);
+    }
+
+    #[test]
+    fn union() {
+        let a = bounds_at(Point3f::new(0.0, 0.0, 0.0), Vec3f::new(1.0, 0.0, 0.0), 1.0);
+        let b = bounds_at(Point3f::new(2.0, 1.0, 0.0), Vec3f::new(0.0, 1.0, 0.0), 1.0);
+        let both = a.union(&b);
+        assert_eq!(both.phi, 2.0);
+        assert_eq!(
+            both.bounds,
+            Bounds3f::new(Point3f::default(), Point3f::new(2.0, 1.0, 0.0))
+        );
+        assert!((both.normals.cos_theta - (PI / 4.0).cos()).abs() < 1e-12);
+
+        let dark = LightBounds { phi: 0.0, ..a };
+        assert_eq!(dark.union(&b), b);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,45 @@
+use crate::core::interaction::Interaction;
+use crate::core::light::Light;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Picks one of the scene's [Light]s to sample at a shading point,
+/// ideally in proportion to how much it contributes there.
+pub trait LightSampler {
+    /// Picks a light to sample from `reference` with `u` in [0.0, 1.0).
+    /// Returns [None] when no light can reach `reference`.
+    fn sample(&self, reference: &Interaction, u: f64) -> Option<SampledLight<'_>>;
+
+    /// The probability of [LightSampler::sample] picking `light` from `reference`.
+    fn pmf(&self, reference: &Interaction, light: &dyn Light) -> f64;
+}
+
+/// The result of [LightSampler::sample].
+#[derive(Clone, Copy)]
+pub struct SampledLight<'a> {
+    pub light: &'a dyn Light,
+
+    /// The probability of having picked `light`
+    pub pmf: f64,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// Whether `a` and `b` are the same light, which needs only their addresses to match.
+pub fn same_light(a: &dyn Light, b: &dyn Light) -> bool {
+    std::ptr::addr_eq(a as *const dyn Light, b as *const dyn Light)
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+///////////////
+// END TESTS //
+///////////////
@@ -1,3 +1,5 @@
+use crate::core::bsdf::Bsdf;
+use crate::core::bssrdf::Bssrdf;
 use crate::core::interaction::Interaction;
 
 /////////////////////
@@ -7,10 +9,17 @@ use crate::core::interaction::Interaction;
 /// Defines the physical properties of some [Primitive],
 /// determining how light interacts with the object.
 pub trait Material {
-    fn interact(&self, interaction: &Interaction);
+    /// Builds the [Bsdf] describing scattering at `interaction`.
+    /// Returns [None] when the interaction has no surface to scatter from.
+    fn get_bsdf(&self, interaction: &Interaction) -> Option<Bsdf>;
+
+    /// Builds the [Bssrdf] for light refracted into the surface at `interaction`.
+    /// Only materials that scatter light beneath their surface have one.
+    fn get_bssrdf(&self, _interaction: &Interaction) -> Option<Box<dyn Bssrdf>> {
+        None
+    }
 }
 
 ///////////////////
 // END INTERFACE //
 ///////////////////
-
@@ -0,0 +1,69 @@
+use crate::core::bxdf::abs_cos_theta;
+use crate::core::vector::{Point2f, Vec3f};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A statistical distribution of microfacet normals, used by rough [BxDF](crate::core::bxdf::BxDF)s.
+/// Defined in the local shading space where the macro surface normal is the z axis,
+/// with separate roughness along the x and y axes for anisotropy.
+pub trait MicrofacetDistribution {
+    /// The density of microfacets with normal `wm`, per unit projected area.
+    fn d(&self, wm: &Vec3f) -> f64;
+
+    /// Smith's auxiliary function, the ratio of masked to visible microfacet area.
+    fn lambda(&self, w: &Vec3f) -> f64;
+
+    /// Samples a microfacet normal visible from `w`, always in the +z hemisphere.
+    /// Directions below the surface are sampled as if seen from `-w`.
+    fn sample_wm(&self, w: &Vec3f, u: &Point2f) -> Vec3f;
+
+    /// Below this roughness the distribution should be treated as a perfect mirror.
+    fn effectively_smooth(&self) -> bool;
+
+    /// Smith's masking function, the fraction of microfacets visible from `w`.
+    fn g1(&self, w: &Vec3f) -> f64 {
+        1.0 / (1.0 + self.lambda(w))
+    }
+
+    /// Smith's height-correlated masking-shadowing function G2,
+    /// the fraction of microfacets visible from both `wo` and `wi`.
+    fn g(&self, wo: &Vec3f, wi: &Vec3f) -> f64 {
+        1.0 / (1.0 + self.lambda(wo) + self.lambda(wi))
+    }
+
+    /// The distribution of normals visible from `w`, or from `-w` below the surface.
+    fn visible_d(&self, w: &Vec3f, wm: &Vec3f) -> f64 {
+        let cos_theta = abs_cos_theta(w);
+        if cos_theta == 0.0 {
+            return 0.0;
+        }
+        let cos_theta_m = w.dot(wm) * w.z.signum();
+        self.g1(w) / cos_theta * self.d(wm) * cos_theta_m.max(0.0)
+    }
+
+    /// The density with which [MicrofacetDistribution::sample_wm] returns `wm`.
+    fn pdf(&self, w: &Vec3f, wm: &Vec3f) -> f64 {
+        self.visible_d(w, wm)
+    }
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// Maps a perceptually linear roughness in [0.0, 1.0] to an alpha parameter.
+pub fn roughness_to_alpha(roughness: f64) -> f64 {
+    roughness.max(0.0).sqrt()
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,313 @@
+use std::ops::{Add, Mul};
+
+use crate::core::image::{Image, WrapMode};
+use crate::core::vector::{Point2f, Vec2f};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// An [Image] along with successively halved copies of it, so that lookups covering
+/// many texels can be answered from a few texels of a coarser level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MipMap<T> {
+    /// From the full resolution image down to a single texel
+    levels: Vec<Image<T>>,
+
+    wrap: WrapMode,
+}
+
+/// How a lookup covering an area of the texture is averaged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterMode {
+    /// Bilinear interpolation at full resolution, ignoring the area; aliases when minified
+    Bilinear,
+
+    /// Blends bilinear lookups from the two levels closest to the widest extent of the area;
+    /// cheap, but blurs when the area is long and thin
+    Trilinear,
+
+    /// Elliptically weighted average over the area, sharp even at grazing angles.
+    /// Areas longer than `max_anisotropy` times their width are widened to bound the cost.
+    Ewa { max_anisotropy: f64 },
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// How quickly the Gaussian EWA weights fall off towards the edge of the ellipse.
+const EWA_FALLOFF: f64 = 2.0;
+
+impl<T: Copy + Default + Add<Output = T> + Mul<f64, Output = T>> MipMap<T> {
+    /// Builds the pyramid by repeatedly averaging 2x2 blocks of texels.
+    pub fn new(image: Image<T>, wrap: WrapMode) -> Self {
+        let mut levels = vec![image];
+        loop {
+            let last = levels.last().unwrap();
+            if last.width() == 1 && last.height() == 1 {
+                break;
+            }
+
+            let width = last.width().div_ceil(2);
+            let height = last.height().div_ceil(2);
+            let mut texels = Vec::with_capacity(width * height);
+            for y in 0..height as i64 {
+                for x in 0..width as i64 {
+                    // Odd sizes wrap to fill the missing texels
+                    let sum = last.get(2 * x, 2 * y, wrap)
+                        + last.get(2 * x + 1, 2 * y, wrap)
+                        + last.get(2 * x, 2 * y + 1, wrap)
+                        + last.get(2 * x + 1, 2 * y + 1, wrap);
+                    texels.push(sum * 0.25);
+                }
+            }
+            levels.push(Image::new(width, height, texels));
+        }
+
+        Self { levels, wrap }
+    }
+
+    pub fn levels(&self) -> usize {
+        self.levels.len()
+    }
+
+    pub fn level(&self, level: usize) -> &Image<T> {
+        &self.levels[level]
+    }
+
+    /// Looks up the texture over the parallelogram spanned by `dst_dx` and `dst_dy` around `st`,
+    /// the changes in texture coordinates between neighbouring pixels.
+    pub fn filter(&self, st: &Point2f, dst_dx: &Vec2f, dst_dy: &Vec2f, mode: FilterMode) -> T {
+        match mode {
+            FilterMode::Bilinear => self.bilerp(0, st),
+            FilterMode::Trilinear => {
+                let width = 2.0
+                    * dst_dx
+                        .x
+                        .abs()
+                        .max(dst_dx.y.abs())
+                        .max(dst_dy.x.abs())
+                        .max(dst_dy.y.abs());
+                self.trilinear(st, width)
+            }
+            FilterMode::Ewa { max_anisotropy } => self.ewa(st, *dst_dx, *dst_dy, max_anisotropy),
+        }
+    }
+
+    fn bilerp(&self, level: usize, st: &Point2f) -> T {
+        self.levels[level].bilerp(st, self.wrap)
+    }
+
+    /// Blends the levels whose texels are about `width` across.
+    fn trilinear(&self, st: &Point2f, width: f64) -> T {
+        let coarsest = self.levels.len() - 1;
+        let level = coarsest as f64 + width.max(1e-8).log2();
+        if level <= 0.0 {
+            return self.bilerp(0, st);
+        }
+        if level >= coarsest as f64 {
+            return self.levels[coarsest].get(0, 0, self.wrap);
+        }
+
+        let below = level.floor() as usize;
+        let t = level - below as f64;
+        self.bilerp(below, st) * (1.0 - t) + self.bilerp(below + 1, st) * t
+    }
+
+    fn ewa(&self, st: &Point2f, dst_dx: Vec2f, dst_dy: Vec2f, max_anisotropy: f64) -> T {
+        let length = |v: &Vec2f| (v.x * v.x + v.y * v.y).sqrt();
+        let (major, mut minor) = if length(&dst_dx) >= length(&dst_dy) {
+            (dst_dx, dst_dy)
+        } else {
+            (dst_dy, dst_dx)
+        };
+        let major_length = length(&major);
+        let mut minor_length = length(&minor);
+
+        // Widen overly eccentric ellipses, blurring them a little to bound the texels visited
+        if minor_length * max_anisotropy < major_length && minor_length > 0.0 {
+            let scale = major_length / (minor_length * max_anisotropy);
+            minor = minor * scale;
+            minor_length *= scale;
+        }
+        if minor_length == 0.0 {
+            return self.bilerp(0, st);
+        }
+
+        // Pick the levels where the minor axis spans a few texels
+        let coarsest = self.levels.len() - 1;
+        let level = (coarsest as f64 + minor_length.log2()).max(0.0);
+        let below = level.floor() as usize;
+        let t = level - below as f64;
+        self.ewa_level(below, st, major, minor) * (1.0 - t)
+            + self.ewa_level(below + 1, st, major, minor) * t
+    }
+
+    fn ewa_level(&self, level: usize, st: &Point2f, major: Vec2f, minor: Vec2f) -> T {
+        let Some(image) = self.levels.get(level) else {
+            return self.levels[self.levels.len() - 1].get(0, 0, self.wrap);
+        };
+        let (width, height) = (image.width() as f64, image.height() as f64);
+
+        // Into texel space, where texel centers lie at integer coordinates
+        let s = st.x * width - 0.5;
+        let t = st.y * height - 0.5;
+        let major = Vec2f::new(major.x * width, major.y * height);
+        let minor = Vec2f::new(minor.x * width, minor.y * height);
+
+        // The implicit ellipse a s^2 + b s t + c t^2 < 1, grown by a texel to cover the
+        // reconstruction filter
+        let mut a = major.y * major.y + minor.y * minor.y + 1.0;
+        let mut b = -2.0 * (major.x * major.y + minor.x * minor.y);
+        let mut c = major.x * major.x + minor.x * minor.x + 1.0;
+        let inv_f = 1.0 / (a * c - b * b * 0.25);
+        a *= inv_f;
+        b *= inv_f;
+        c *= inv_f;
+
+        // Its bounding box
+        let det = -b * b + 4.0 * a * c;
+        let inv_det = 1.0 / det;
+        let s_radius = 2.0 * inv_det * (det * c).sqrt();
+        let t_radius = 2.0 * inv_det * (a * det).sqrt();
+        let (s0, s1) = ((s - s_radius).ceil() as i64, (s + s_radius).floor() as i64);
+        let (t0, t1) = ((t - t_radius).ceil() as i64, (t + t_radius).floor() as i64);
+
+        let edge = (-EWA_FALLOFF).exp();
+        let mut sum = T::default();
+        let mut total_weight = 0.0;
+        for it in t0..=t1 {
+            let dt = it as f64 - t;
+            for is in s0..=s1 {
+                let ds = is as f64 - s;
+                let r2 = a * ds * ds + b * ds * dt + c * dt * dt;
+                if r2 < 1.0 {
+                    let weight = (-EWA_FALLOFF * r2).exp() - edge;
+                    sum = sum + image.get(is, it, self.wrap) * weight;
+                    total_weight += weight;
+                }
+            }
+        }
+
+        if total_weight > 0.0 {
+            sum * (1.0 / total_weight)
+        } else {
+            image.bilerp(st, self.wrap)
+        }
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE: usize = 64;
+
+    /// Vertical stripes 8 texels wide, alternating between 1.0 and 0.0.
+    fn stripes() -> MipMap<f64> {
+        let texels = (0..SIZE * SIZE)
+            .map(|i| {
+                if ((i % SIZE) / 8).is_multiple_of(2) {
+                    1.0
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        MipMap::new(Image::new(SIZE, SIZE, texels), WrapMode::Repeat)
+    }
+
+    #[test]
+    fn pyramid() {
+        let mipmap = MipMap::new(Image::new(5, 3, vec![1.0; 15]), WrapMode::Clamp);
+        let sizes = (0..mipmap.levels())
+            .map(|level| (mipmap.level(level).width(), mipmap.level(level).height()))
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, [(5, 3), (3, 2), (2, 1), (1, 1)]);
+        assert_eq!(mipmap.level(3).get(0, 0, WrapMode::Clamp), 1.0);
+
+        // The coarsest level is the average
+        let stripes = stripes();
+        assert_eq!(
+            stripes
+                .level(stripes.levels() - 1)
+                .get(0, 0, WrapMode::Clamp),
+            0.5
+        );
+    }
+
+    #[test]
+    fn small_footprints_are_sharp() {
+        let mipmap = stripes();
+        let st = Point2f::new(4.0 / SIZE as f64, 0.5);
+        let tiny = Vec2f::new(1e-4, 0.0);
+        for mode in [
+            FilterMode::Bilinear,
+            FilterMode::Trilinear,
+            FilterMode::Ewa {
+                max_anisotropy: 8.0,
+            },
+        ] {
+            let v = mipmap.filter(&st, &tiny, &Vec2f::new(0.0, 1e-4), mode);
+            assert!((v - 1.0).abs() < 1e-6, "{:?} gave {}", mode, v);
+        }
+    }
+
+    #[test]
+    fn large_footprints_average() {
+        let mipmap = stripes();
+        let st = Point2f::new(4.0 / SIZE as f64, 0.5);
+        let wide = (Vec2f::new(0.5, 0.0), Vec2f::new(0.0, 0.5));
+
+        // Bilinear aliases
+        assert_eq!(
+            mipmap.filter(&st, &wide.0, &wide.1, FilterMode::Bilinear),
+            1.0
+        );
+        for mode in [
+            FilterMode::Trilinear,
+            FilterMode::Ewa {
+                max_anisotropy: 8.0,
+            },
+        ] {
+            let v = mipmap.filter(&st, &wide.0, &wide.1, mode);
+            assert!((v - 0.5).abs() < 0.05, "{:?} gave {}", mode, v);
+        }
+    }
+
+    #[test]
+    fn ewa_is_sharp_along_thin_footprints() {
+        let mipmap = stripes();
+        let st = Point2f::new(4.0 / SIZE as f64, 0.5);
+        // Long along the stripes, narrow across them
+        let (along, across) = (Vec2f::new(0.0, 0.25), Vec2f::new(1.0 / 256.0, 0.0));
+
+        let trilinear = mipmap.filter(&st, &along, &across, FilterMode::Trilinear);
+        assert!(
+            (trilinear - 0.5).abs() < 0.1,
+            "trilinear gave {}",
+            trilinear
+        );
+
+        let ewa = mipmap.filter(
+            &st,
+            &along,
+            &across,
+            FilterMode::Ewa {
+                max_anisotropy: 64.0,
+            },
+        );
+        assert!(ewa > 0.9, "EWA gave {}", ewa);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -1,13 +1,27 @@
 pub mod accelerator;
 pub mod bounds;
+pub mod bsdf;
+pub mod bssrdf;
+pub mod bxdf;
 pub mod camera;
 pub mod film;
+pub mod frame;
+pub mod fresnel;
+pub mod image;
 pub mod integrator;
 pub mod interaction;
+pub mod light;
+pub mod light_sampler;
 pub mod material;
+pub mod microfacet;
+pub mod mipmap;
 pub mod primitive;
 pub mod ray;
 pub mod rustrace;
 pub mod sample;
 pub mod sampler;
+pub mod scene;
+pub mod shape;
+pub mod spectrum;
+pub mod texture;
 pub mod vector;
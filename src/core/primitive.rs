@@ -1,4 +1,6 @@
 use crate::core::interaction::Interaction;
+use crate::core::light::AreaLight;
+use crate::core::material::Material;
 use crate::core::ray::Ray;
 
 /////////////////////
@@ -9,7 +11,17 @@ use crate::core::ray::Ray;
 pub trait Primitive {
     /// Tests a ray against this primitive.
     /// Assumes that `ray` is normalized.
-    fn test(&self, ray: &Ray) -> Option<Interaction>;
+    fn test(&self, ray: &Ray) -> Option<Interaction<'_>>;
+
+    /// The [Material] hits on this primitive scatter with, [None] for bare geometry.
+    fn material(&self) -> Option<&dyn Material> {
+        None
+    }
+
+    /// The [AreaLight] covering this primitive, [None] unless it glows.
+    fn area_light(&self) -> Option<&dyn AreaLight> {
+        None
+    }
 }
 
 //////////////////////////
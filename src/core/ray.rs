@@ -5,7 +5,7 @@ use crate::core::vector::{Point3f, Vec3f};
 /////////////////////
 
 /// An instance of the simple geometric [Ray].
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct Ray {
     /// The origin
     pub o: Point3f,
@@ -18,6 +18,19 @@ pub struct Ray {
 
     /// The maximum t that is valid on the ray
     max_t: f64,
+
+    /// Rays through the neighbouring pixels, tracking the footprint of the pixel
+    /// so that textures can be filtered over it
+    pub differentials: Option<RayDifferential>,
+}
+
+/// The origins and directions of two rays offset by a pixel in x and y from a main [Ray].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayDifferential {
+    pub rx_o: Point3f,
+    pub rx_d: Vec3f,
+    pub ry_o: Point3f,
+    pub ry_d: Vec3f,
 }
 
 //////////////////////////
@@ -27,7 +40,39 @@ pub struct Ray {
 
 impl Ray {
     pub fn new(o: Point3f, d: Vec3f, min_t: f64, max_t: f64) -> Self {
-        Ray { o, d, min_t, max_t }
+        Ray {
+            o,
+            d,
+            min_t,
+            max_t,
+            differentials: None,
+        }
+    }
+
+    pub fn with_differentials(self, differentials: RayDifferential) -> Self {
+        Self {
+            differentials: Some(differentials),
+            ..self
+        }
+    }
+
+    /// Shrinks the footprint to a fraction `s` of a pixel,
+    /// for when each pixel is covered by several samples.
+    pub fn scale_differentials(&mut self, s: f64) {
+        if let Some(rd) = &mut self.differentials {
+            rd.rx_o = self.o + (rd.rx_o - self.o) * s;
+            rd.ry_o = self.o + (rd.ry_o - self.o) * s;
+            rd.rx_d = self.d + (rd.rx_d - self.d) * s;
+            rd.ry_d = self.d + (rd.ry_d - self.d) * s;
+        }
+    }
+
+    pub fn min_t(&self) -> f64 {
+        self.min_t
+    }
+
+    pub fn max_t(&self) -> f64 {
+        self.max_t
     }
 
     pub fn at(&self, t: f64) -> Option<Point3f> {
@@ -43,6 +88,33 @@ impl Ray {
 // BEGIN TESTS        //
 ////////////////////////
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_differentials() {
+        let mut ray = Ray::new(
+            Point3f::new(0.0, 0.0, 0.0),
+            Vec3f::new(0.0, 0.0, 1.0),
+            0.0,
+            100.0,
+        )
+        .with_differentials(RayDifferential {
+            rx_o: Point3f::new(1.0, 0.0, 0.0),
+            rx_d: Vec3f::new(0.2, 0.0, 1.0),
+            ry_o: Point3f::new(0.0, 0.0, 0.0),
+            ry_d: Vec3f::new(0.0, 0.4, 1.0),
+        });
+        ray.scale_differentials(0.5);
+
+        let rd = ray.differentials.unwrap();
+        assert_eq!(rd.rx_o, Point3f::new(0.5, 0.0, 0.0));
+        assert_eq!(rd.rx_d, Vec3f::new(0.1, 0.0, 1.0));
+        assert_eq!(rd.ry_d, Vec3f::new(0.0, 0.2, 1.0));
+    }
+}
+
 ///////////////
 // END TESTS //
 ///////////////
@@ -2,7 +2,10 @@ use crate::core::accelerator::Accelerator;
 use crate::core::camera::Camera;
 use crate::core::film::Film;
 use crate::core::integrator::Integrator;
+use crate::core::light::Light;
 use crate::core::primitive::Primitive;
+use crate::core::scene::Scene;
+use crate::light_samplers::bvh_light_sampler::BvhLightSampler;
 
 /////////////////////
 // BEGIN INTERFACE //
@@ -15,6 +18,8 @@ pub struct RusTrace<'a> {
     camera: &'a dyn Camera,
     accelerator: &'a mut dyn Accelerator<'a>,
     film: &'a mut dyn Film,
+    lights: &'a [&'a dyn Light],
+    light_sampler: BvhLightSampler<'a>,
 }
 
 //////////////////////////
@@ -34,16 +39,24 @@ impl<'a> RusTrace<'a> {
             accelerator,
             camera,
             film,
+            lights: &[],
+            light_sampler: BvhLightSampler::new(&[]),
         }
     }
 
-    pub fn load_primitives(&mut self, primitives: &'a Vec<&'a dyn Primitive>) {
+    pub fn load_primitives(&mut self, primitives: &'a [&'a dyn Primitive]) {
         self.accelerator.build(primitives)
     }
 
+    /// Lights whose emission depends on the scene should be preprocessed first.
+    pub fn load_lights(&mut self, lights: &'a [&'a dyn Light]) {
+        self.lights = lights;
+        self.light_sampler = BvhLightSampler::new(lights);
+    }
+
     pub fn render(&'a mut self) {
-        self.integrator
-            .render(self.camera, self.accelerator, self.film);
+        let scene = Scene::new(self.accelerator, self.lights, &self.light_sampler);
+        self.integrator.render(self.camera, &scene, self.film);
     }
 }
 
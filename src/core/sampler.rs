@@ -1,4 +1,5 @@
 use crate::core::sample::Sample;
+use crate::core::vector::Point2f;
 
 /////////////////////
 // BEGIN INTERFACE //
@@ -6,6 +7,20 @@ use crate::core::sample::Sample;
 
 pub trait Sampler {
     fn next_sample(&mut self) -> Option<Sample>;
+
+    /// How many samples each pixel gets, for integrators to narrow the footprint
+    /// of camera rays to the part of the pixel a single sample stands for.
+    fn samples_per_pixel(&self) -> usize;
+
+    /// A value in [0.0, 1.0) for the sample last returned by [Sampler::next_sample],
+    /// for the random decisions integrators make along its rays.
+    /// Each sample has values of its own, whatever order samples are rendered in.
+    fn get_1d(&mut self) -> f64;
+
+    /// Two values in [0.0, 1.0), as for [Sampler::get_1d].
+    fn get_2d(&mut self) -> Point2f {
+        Point2f::new(self.get_1d(), self.get_1d())
+    }
 }
 
 //////////////////////////
@@ -0,0 +1,44 @@
+use crate::core::accelerator::Accelerator;
+use crate::core::light::Light;
+use crate::core::light_sampler::LightSampler;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Everything an [Integrator](crate::core::integrator::Integrator) can see:
+/// the geometry, behind an [Accelerator], and the [Light]s shining on it,
+/// with a [LightSampler] to pick among them.
+pub struct Scene<'a> {
+    pub accelerator: &'a dyn Accelerator<'a>,
+    pub lights: &'a [&'a dyn Light],
+    pub light_sampler: &'a dyn LightSampler,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<'a> Scene<'a> {
+    pub fn new(
+        accelerator: &'a dyn Accelerator<'a>,
+        lights: &'a [&'a dyn Light],
+        light_sampler: &'a dyn LightSampler,
+    ) -> Self {
+        Self {
+            accelerator,
+            lights,
+            light_sampler,
+        }
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+///////////////
+// END TESTS //
+///////////////
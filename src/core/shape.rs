@@ -0,0 +1,73 @@
+use crate::core::bounds::{Bounds3f, DirectionCone};
+use crate::core::interaction::Interaction;
+use crate::core::primitive::Primitive;
+use crate::core::vector::{Point2f, Point3f, Vec3f};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A [Primitive] that points can be picked on, so that it can emit light.
+pub trait Shape: Primitive {
+    fn area(&self) -> f64;
+
+    fn bounds(&self) -> Bounds3f;
+
+    /// The cone holding every normal of the surface.
+    fn normal_bounds(&self) -> DirectionCone;
+
+    /// Picks a point uniformly over the surface, with a density with respect to area.
+    fn sample_area(&self, u: &Point2f) -> Option<ShapeSample<'_>>;
+
+    /// Picks a point on the surface as seen from `reference`,
+    /// with a density with respect to solid angle at `reference`.
+    /// The interaction's `wo` points back towards `reference`.
+    fn sample(&self, reference: &Point3f, u: &Point2f) -> Option<ShapeSample<'_>>;
+
+    /// The density of [Shape::sample] picking the point seen along `wi` from `reference`,
+    /// 0.0 if the shape isn't there.
+    fn pdf(&self, reference: &Point3f, wi: &Vec3f) -> f64;
+}
+
+/// A point picked on a [Shape].
+#[derive(Debug, Clone, Copy)]
+pub struct ShapeSample<'a> {
+    pub interaction: Interaction<'a>,
+    pub pdf: f64,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<'a> ShapeSample<'a> {
+    /// Converts an area density to solid angle as seen from `reference`.
+    /// Returns [None] when the point is seen exactly edge on.
+    pub fn area_to_solid_angle(self, reference: &Point3f) -> Option<Self> {
+        let interaction = self.interaction;
+        let d = *reference - interaction.p;
+        let distance_sq = d.length_sq();
+        let n = interaction.n?;
+        let cos_theta = n.dot(&d).abs() / (n.length() * distance_sq.sqrt());
+        if distance_sq == 0.0 || cos_theta == 0.0 {
+            return None;
+        }
+        Some(Self {
+            interaction: Interaction {
+                wo: d.normalize(),
+                ..interaction
+            },
+            pdf: self.pdf * distance_sq / cos_theta,
+        })
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+///////////////
+// END TESTS //
+///////////////
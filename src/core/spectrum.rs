@@ -0,0 +1,86 @@
+use crate::core::vector::Color3f;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// The wavelengths, in nanometers, that the red, green and blue channels stand in for
+/// when a spectral quantity has to be reduced to a [Color3f].
+pub const RGB_WAVELENGTHS: [f64; 3] = [630.0, 532.0, 465.0];
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// Linearly interpolates a sampled spectrum at [RGB_WAVELENGTHS].
+/// `wavelengths` must be sorted; values outside the samples are clamped to the ends.
+pub fn resample_to_rgb(wavelengths: &[f64], values: &[f64]) -> Color3f {
+    let at = |lambda: f64| -> f64 {
+        let last = wavelengths.len() - 1;
+        if lambda <= wavelengths[0] {
+            return values[0];
+        }
+        if lambda >= wavelengths[last] {
+            return values[last];
+        }
+
+        let i = wavelengths.partition_point(|&w| w <= lambda) - 1;
+        let t = (lambda - wavelengths[i]) / (wavelengths[i + 1] - wavelengths[i]);
+        values[i] * (1.0 - t) + values[i + 1] * t
+    };
+
+    Color3f::new(
+        at(RGB_WAVELENGTHS[0]),
+        at(RGB_WAVELENGTHS[1]),
+        at(RGB_WAVELENGTHS[2]),
+    )
+}
+
+/// Converts an sRGB encoded value in [0.0, 1.0], as stored in most 8-bit images, to linear light.
+pub fn srgb_to_linear(v: f64) -> f64 {
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resample() {
+        let wavelengths = [400.0, 500.0, 600.0, 700.0];
+        let values = [1.0, 2.0, 3.0, 4.0];
+        let rgb = resample_to_rgb(&wavelengths, &values);
+        assert!((rgb.x - 3.3).abs() < 1e-12);
+        assert!((rgb.y - 2.32).abs() < 1e-12);
+        assert!((rgb.z - 1.65).abs() < 1e-12);
+    }
+
+    #[test]
+    fn resample_clamps() {
+        let rgb = resample_to_rgb(&[500.0, 550.0], &[1.0, 2.0]);
+        assert!((rgb - Color3f::new(2.0, 1.64, 1.0)).length() < 1e-12);
+    }
+
+    #[test]
+    fn srgb() {
+        assert_eq!(srgb_to_linear(0.0), 0.0);
+        assert!((srgb_to_linear(1.0) - 1.0).abs() < 1e-12);
+        assert!((srgb_to_linear(0.5) - 0.214).abs() < 1e-3);
+        // Continuous where the curve switches to a power
+        assert!((srgb_to_linear(0.04045) - srgb_to_linear(0.04045 + 1e-9)).abs() < 1e-8);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,60 @@
+use crate::core::interaction::Interaction;
+use crate::core::vector::{Point2f, Point3f, Vec2f, Vec3f};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A function over the surface of a [Primitive](crate::core::primitive::Primitive),
+/// used to vary [Material](crate::core::material::Material) parameters.
+pub trait Texture<T> {
+    /// Evaluates the texture at the location of `interaction`.
+    fn evaluate(&self, interaction: &Interaction) -> T;
+}
+
+/// Places a 2D [Texture] onto a surface, giving texture coordinates `(s, t)` for a hit.
+pub trait TextureMapping2D {
+    fn map(&self, interaction: &Interaction) -> Point2f;
+
+    /// Like [map](Self::map), along with the area of the pixel in texture space,
+    /// so that lookups can be filtered over it.
+    /// Found by mapping the hit moved to the neighbouring pixels, which is exact for
+    /// mappings that are linear in position and surface coordinates.
+    fn map_with_differentials(&self, interaction: &Interaction) -> TexCoord2D {
+        let st = self.map(interaction);
+        let offset = |dp: Vec3f, du: f64, dv: f64| {
+            let moved = Interaction {
+                p: interaction.p + dp,
+                uv: interaction.uv + Point2f::new(du, dv),
+                ..*interaction
+            };
+            self.map(&moved) - st
+        };
+        TexCoord2D {
+            st,
+            dst_dx: offset(interaction.dpdx, interaction.dudx, interaction.dvdx),
+            dst_dy: offset(interaction.dpdy, interaction.dudy, interaction.dvdy),
+        }
+    }
+}
+
+/// Places a solid [Texture] in space, giving the point a hit is looked up at.
+pub trait TextureMapping3D {
+    fn map(&self, interaction: &Interaction) -> Point3f;
+}
+
+/// Texture coordinates for a hit, and how they change to the neighbouring pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TexCoord2D {
+    pub st: Point2f,
+
+    /// The change in `st` one pixel to the right
+    pub dst_dx: Vec2f,
+
+    /// The change in `st` one pixel down
+    pub dst_dy: Vec2f,
+}
+
+///////////////////
+// END INTERFACE //
+///////////////////
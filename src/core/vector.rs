@@ -26,13 +26,26 @@ pub type Point3i = Vec3i;
 pub type Color3f = Vec3f;
 pub type Color3i = Vec3i;
 
+/// A geometric two-dimensional vector,
+/// most often used for sample values and surface coordinates
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+pub type Point2<T> = Vec2<T>;
+
+pub type Vec2f = Vec2<f64>;
+pub type Point2f = Vec2f;
+
 //////////////////////////
 // END INTERFACE        //
 // BEGIN IMPLEMENTATION //
 //////////////////////////
 
 impl<T> Vec3<T> {
-    pub fn new(x: T, y: T, z: T) -> Self {
+    pub const fn new(x: T, y: T, z: T) -> Self {
         Self { x, y, z }
     }
 
@@ -182,6 +195,65 @@ impl<T: Mul<Output = T> + Sub<Output = T> + Copy> Vec3<T> {
     }
 }
 
+impl Vec3f {
+    pub fn abs(&self) -> Self {
+        Self::new(self.x.abs(), self.y.abs(), self.z.abs())
+    }
+
+    pub fn max_component(&self) -> f64 {
+        self.x.max(self.y).max(self.z)
+    }
+
+    pub fn min_component(&self) -> f64 {
+        self.x.min(self.y).min(self.z)
+    }
+
+    /// The mean of the components, a cheap stand-in for luminance
+    pub fn average(&self) -> f64 {
+        (self.x + self.y + self.z) / 3.0
+    }
+
+    pub fn is_black(&self) -> bool {
+        self.x == 0.0 && self.y == 0.0 && self.z == 0.0
+    }
+
+    /// Applies `f` to every component
+    pub fn map(&self, f: impl Fn(f64) -> f64) -> Self {
+        Self::new(f(self.x), f(self.y), f(self.z))
+    }
+}
+
+impl<T> Vec2<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Add<Output = T> + Copy> Add for Vec2<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T> + Copy> Sub for Vec2<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+/// Scalar multiplication
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vec2<T> {
+    type Output = Self;
+
+    fn mul(self, scalar: T) -> Self {
+        Self::new(self.x * scalar, self.y * scalar)
+    }
+}
+
 pub fn random_in_unit_disk() -> Vec3f {
     // TODO: Don't use shitty non-deterministic approach
     let mut rng = rand::thread_rng();
@@ -263,6 +335,26 @@ mod tests {
         let vec_f = Vec3::new(1.0, 2.0, 3.0);
         assert_eq!(vec_f.length(), vec_f.length_sq().sqrt());
     }
+
+    #[test]
+    fn components() {
+        let vec = Vec3f::new(-1.0, 2.0, 0.5);
+        assert_eq!(vec.abs(), Vec3f::new(1.0, 2.0, 0.5));
+        assert_eq!(vec.max_component(), 2.0);
+        assert_eq!(vec.min_component(), -1.0);
+        assert_eq!(vec.average(), 0.5);
+        assert!(!vec.is_black());
+        assert!(Vec3f::default().is_black());
+    }
+
+    #[test]
+    fn vec2() {
+        let vec1 = Vec2::new(1, 2);
+        let vec2 = Vec2::new(3, 2);
+        assert_eq!(vec1 + vec2, Vec2::new(4, 4));
+        assert_eq!(vec1 - vec2, Vec2::new(-2, 0));
+        assert_eq!(vec1 * 2, Vec2::new(2, 4));
+    }
 }
 
 ///////////////
@@ -61,7 +61,7 @@ impl Film for PngFilm {
 
         let path = Path::new(r"/Users/tylerhm/Pictures/result.png");
         let file = File::create(path).unwrap();
-        let ref mut w = BufWriter::new(file);
+        let w = &mut BufWriter::new(file);
 
         let mut encoder = png::Encoder::new(w, self.width, self.height);
         encoder.set_color(png::ColorType::Rgb);
@@ -0,0 +1,218 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+use crate::core::image::{Image, ImageError};
+use crate::core::vector::Color3f;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Reads a Radiance RGBE (`.hdr`) file, flat or run length encoded,
+/// stored in the usual top to bottom, left to right order.
+pub fn read_hdr(path: &Path) -> Result<Image<Color3f>, ImageError> {
+    read(&mut BufReader::new(File::open(path)?))
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+fn format_error(message: &str) -> ImageError {
+    ImageError::Format(message.to_string())
+}
+
+fn read(reader: &mut impl BufRead) -> Result<Image<Color3f>, ImageError> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    if !line.starts_with("#?") {
+        return Err(format_error("missing Radiance signature"));
+    }
+
+    // Header variables, up to an empty line
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(format_error("unterminated header"));
+        }
+        let line = line.trim();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(format) = line.strip_prefix("FORMAT=") {
+            if format != "32-bit_rle_rgbe" {
+                return Err(format_error("only RGBE pixels are supported"));
+            }
+        }
+    }
+
+    line.clear();
+    reader.read_line(&mut line)?;
+    let (height, width) = match line.split_whitespace().collect::<Vec<_>>()[..] {
+        ["-Y", height, "+X", width] => (height.parse::<usize>(), width.parse::<usize>()),
+        _ => return Err(format_error("only top to bottom images are supported")),
+    };
+    let (Ok(height), Ok(width)) = (height, width) else {
+        return Err(format_error("invalid resolution"));
+    };
+    if width == 0 || height == 0 {
+        return Err(format_error("invalid resolution"));
+    }
+
+    let mut texels = Vec::with_capacity(width * height);
+    let mut scanline = vec![[0u8; 4]; width];
+    for _ in 0..height {
+        read_scanline(reader, &mut scanline)?;
+        texels.extend(scanline.iter().map(rgbe_to_color));
+    }
+
+    Ok(Image::new(width, height, texels))
+}
+
+fn read_scanline(reader: &mut impl Read, scanline: &mut [[u8; 4]]) -> Result<(), ImageError> {
+    let width = scanline.len();
+    let mut first = [0u8; 4];
+    reader.read_exact(&mut first)?;
+
+    let is_run_length_encoded = (8..0x8000).contains(&width)
+        && first[0] == 2
+        && first[1] == 2
+        && ((first[2] as usize) << 8 | first[3] as usize) == width;
+    if !is_run_length_encoded {
+        if first[..3] == [1, 1, 1] {
+            return Err(format_error(
+                "old style run length encoding is not supported",
+            ));
+        }
+        scanline[0] = first;
+        for pixel in scanline[1..].iter_mut() {
+            reader.read_exact(pixel)?;
+        }
+        return Ok(());
+    }
+
+    // Each channel is encoded separately, as runs and literal spans
+    for channel in 0..4 {
+        let mut x = 0;
+        while x < width {
+            let mut count = [0u8; 1];
+            reader.read_exact(&mut count)?;
+            let (is_run, count) = if count[0] > 128 {
+                (true, count[0] as usize - 128)
+            } else {
+                (false, count[0] as usize)
+            };
+            if count == 0 || x + count > width {
+                return Err(format_error("corrupt run length encoding"));
+            }
+
+            if is_run {
+                let mut value = [0u8; 1];
+                reader.read_exact(&mut value)?;
+                for pixel in scanline[x..x + count].iter_mut() {
+                    pixel[channel] = value[0];
+                }
+            } else {
+                let mut values = vec![0u8; count];
+                reader.read_exact(&mut values)?;
+                for (pixel, value) in scanline[x..x + count].iter_mut().zip(values) {
+                    pixel[channel] = value;
+                }
+            }
+            x += count;
+        }
+    }
+
+    Ok(())
+}
+
+/// Mantissas share the exponent in the fourth byte.
+fn rgbe_to_color(rgbe: &[u8; 4]) -> Color3f {
+    if rgbe[3] == 0 {
+        return Color3f::default();
+    }
+    let scale = 2f64.powi(rgbe[3] as i32 - (128 + 8));
+    Color3f::new(rgbe[0] as f64, rgbe[1] as f64, rgbe[2] as f64) * scale
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::image::WrapMode;
+
+    fn header(width: usize, height: usize) -> Vec<u8> {
+        format!(
+            "#?RADIANCE\nFORMAT=32-bit_rle_rgbe\nEXPOSURE=1.0\n\n-Y {} +X {}\n",
+            height, width
+        )
+        .into_bytes()
+    }
+
+    #[test]
+    fn flat() {
+        let mut file = header(3, 1);
+        // 0.5 in red, 4.0 in gray, then black
+        file.extend([128, 0, 0, 128, 128, 128, 128, 131, 0, 0, 0, 0]);
+
+        let image = read(&mut file.as_slice()).unwrap();
+        assert_eq!((image.width(), image.height()), (3, 1));
+        assert_eq!(
+            image.get(0, 0, WrapMode::Clamp),
+            Color3f::new(0.5, 0.0, 0.0)
+        );
+        assert_eq!(
+            image.get(1, 0, WrapMode::Clamp),
+            Color3f::new(4.0, 4.0, 4.0)
+        );
+        assert_eq!(image.get(2, 0, WrapMode::Clamp), Color3f::default());
+
+        // Truncated
+        assert!(read(&mut &file[..file.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn run_length_encoded() {
+        let width = 10;
+        let mut file = header(width, 2);
+        for row in 0..2u8 {
+            file.extend([2, 2, 0, width as u8]);
+            // Red counts up in a literal span, green and blue are runs
+            file.push(width as u8);
+            file.extend((0..width as u8).map(|x| 128 + x + row));
+            file.extend([128 + width as u8, 64]);
+            file.extend([128 + 4, 0, 128 + 6, 128]);
+            file.extend([128 + width as u8, 128]);
+        }
+
+        let image = read(&mut file.as_slice()).unwrap();
+        assert_eq!((image.width(), image.height()), (width, 2));
+        assert_eq!(
+            image.get(0, 0, WrapMode::Clamp),
+            Color3f::new(0.5, 0.25, 0.0)
+        );
+        assert_eq!(
+            image.get(9, 1, WrapMode::Clamp),
+            Color3f::new(138.0 / 256.0, 0.25, 0.5)
+        );
+    }
+
+    #[test]
+    fn rejects_bad_headers() {
+        assert!(read(&mut b"P6\n".as_slice()).is_err());
+        assert!(
+            read(&mut b"#?RADIANCE\nFORMAT=32-bit_rle_xyze\n\n-Y 1 +X 1\n".as_slice()).is_err()
+        );
+        assert!(read(&mut b"#?RADIANCE\n\n+Y 1 +X 1\n".as_slice()).is_err());
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
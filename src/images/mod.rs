@@ -0,0 +1,2 @@
+pub mod hdr_reader;
+pub mod png_reader;
@@ -0,0 +1,142 @@
+use std::fs::File;
+use std::path::Path;
+
+use crate::core::image::{ColorEncoding, Image, ImageError};
+use crate::core::spectrum::srgb_to_linear;
+use crate::core::vector::Color3f;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Reads an 8 or 16-bit PNG of any color type into linear colors.
+/// Grayscale images are spread over all three channels, and alpha is dropped.
+pub fn read_png(path: &Path, encoding: ColorEncoding) -> Result<Image<Color3f>, ImageError> {
+    read(File::open(path)?, encoding)
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+fn read(file: File, encoding: ColorEncoding) -> Result<Image<Color3f>, ImageError> {
+    let mut decoder = png::Decoder::new(file);
+    // Palettes and bit depths below 8 become plain 8-bit channels
+    decoder.set_transformations(png::Transformations::EXPAND);
+    let mut reader = decoder.read_info()?;
+    let mut data = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut data)?;
+
+    let channels = match info.color_type {
+        png::ColorType::Grayscale => 1,
+        png::ColorType::GrayscaleAlpha => 2,
+        png::ColorType::Rgb => 3,
+        png::ColorType::Rgba => 4,
+        png::ColorType::Indexed => {
+            return Err(ImageError::Format("unexpanded palette".to_string()));
+        }
+    };
+    let bytes = match info.bit_depth {
+        png::BitDepth::Eight => 1,
+        png::BitDepth::Sixteen => 2,
+        _ => return Err(ImageError::Format("unexpanded bit depth".to_string())),
+    };
+
+    let decode = |v: f64| match encoding {
+        ColorEncoding::Linear => v,
+        ColorEncoding::Srgb => srgb_to_linear(v),
+    };
+    let (width, height) = (info.width as usize, info.height as usize);
+    let mut texels = Vec::with_capacity(width * height);
+    for row in data.chunks(info.line_size).take(height) {
+        for pixel in row.chunks(channels * bytes).take(width) {
+            let channel = |i: usize| {
+                let v = match bytes {
+                    1 => pixel[i] as f64 / 255.0,
+                    _ => u16::from_be_bytes([pixel[2 * i], pixel[2 * i + 1]]) as f64 / 65535.0,
+                };
+                decode(v)
+            };
+            texels.push(if channels < 3 {
+                let v = channel(0);
+                Color3f::new(v, v, v)
+            } else {
+                Color3f::new(channel(0), channel(1), channel(2))
+            });
+        }
+    }
+
+    Ok(Image::new(width, height, texels))
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::image::WrapMode;
+    use std::io::BufWriter;
+    use std::path::PathBuf;
+
+    fn write(name: &str, color: png::ColorType, depth: png::BitDepth, data: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("rustrace-{}.png", name));
+        let file = File::create(&path).unwrap();
+        let mut encoder = png::Encoder::new(BufWriter::new(file), 2, 1);
+        encoder.set_color(color);
+        encoder.set_depth(depth);
+        encoder
+            .write_header()
+            .unwrap()
+            .write_image_data(data)
+            .unwrap();
+        path
+    }
+
+    #[test]
+    fn rgb() {
+        let path = write(
+            "rgb",
+            png::ColorType::Rgba,
+            png::BitDepth::Eight,
+            &[255, 0, 128, 255, 0, 255, 0, 0],
+        );
+
+        let linear = read_png(&path, ColorEncoding::Linear).unwrap();
+        assert_eq!((linear.width(), linear.height()), (2, 1));
+        assert_eq!(
+            linear.get(0, 0, WrapMode::Clamp),
+            Color3f::new(1.0, 0.0, 128.0 / 255.0)
+        );
+
+        let srgb = read_png(&path, ColorEncoding::Srgb).unwrap();
+        let texel = srgb.get(0, 0, WrapMode::Clamp);
+        assert!((texel.z - 0.216).abs() < 1e-3);
+        assert_eq!(srgb.get(1, 0, WrapMode::Clamp), Color3f::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn gray_16_bit() {
+        let path = write(
+            "gray16",
+            png::ColorType::Grayscale,
+            png::BitDepth::Sixteen,
+            &[0x80, 0x00, 0xff, 0xff],
+        );
+
+        let image = read_png(&path, ColorEncoding::Linear).unwrap();
+        let texel = image.get(0, 0, WrapMode::Clamp);
+        assert!((texel - Color3f::new(0.5, 0.5, 0.5)).abs().max_component() < 1e-4);
+        assert_eq!(
+            image.get(1, 0, WrapMode::Clamp),
+            Color3f::new(1.0, 1.0, 1.0)
+        );
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,175 @@
+use crate::core::camera::Camera;
+use crate::core::film::Film;
+use crate::core::frame::Frame;
+use crate::core::integrator::Integrator;
+use crate::core::ray::Ray;
+use crate::core::sampler::Sampler;
+use crate::core::scene::Scene;
+use crate::core::vector::Color3f;
+use crate::math::sampling::sample_cosine_hemisphere;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// An [Integrator] showing how open the surface seen through each pixel is.
+/// Rays leave every camera hit cosine distributed around the normal, and the fraction
+/// that escape without hitting anything within `max_distance` is written out as gray.
+/// Materials and lights are ignored, which makes it a quick check of the geometry
+/// and the [Accelerator](crate::core::accelerator::Accelerator) alone.
+pub struct AmbientOcclusionIntegrator<'a> {
+    sampler: &'a mut dyn Sampler,
+
+    /// The number of occlusion rays cast from each hit
+    samples: usize,
+
+    /// How far away geometry still occludes
+    max_distance: f64,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// Keeps rays leaving a surface from hitting it again.
+const RAY_EPSILON: f64 = 1e-6;
+
+impl<'a> AmbientOcclusionIntegrator<'a> {
+    pub fn new(sampler: &'a mut dyn Sampler) -> Self {
+        Self {
+            sampler,
+            samples: 16,
+            max_distance: f64::INFINITY,
+        }
+    }
+
+    pub fn with_samples(self, samples: usize) -> Self {
+        Self { samples, ..self }
+    }
+
+    pub fn with_max_distance(self, max_distance: f64) -> Self {
+        Self {
+            max_distance,
+            ..self
+        }
+    }
+
+    /// Black where the ray leaves the scene, as there is nothing to occlude.
+    pub fn calculate_ray_color(&mut self, scene: &Scene, ray: &Ray) -> Color3f {
+        let Some(interaction) = scene.accelerator.test(ray) else {
+            return Color3f::default();
+        };
+        if self.samples == 0 {
+            return Color3f::default();
+        }
+
+        // Occlusion is gathered on the side the ray came from
+        let mut n = interaction.shading.n.normalize();
+        if n.dot(&interaction.wo) < 0.0 {
+            n = -n;
+        }
+        let frame = Frame::from_normal(n);
+
+        let mut unoccluded = 0;
+        for _ in 0..self.samples {
+            let u = self.sampler.get_2d();
+            let wi = frame.local_to_world(&sample_cosine_hemisphere(&u));
+            let ray = Ray::new(interaction.p, wi, RAY_EPSILON, self.max_distance);
+            if scene.accelerator.test(&ray).is_none() {
+                unoccluded += 1;
+            }
+        }
+        let fraction = unoccluded as f64 / self.samples as f64;
+        Color3f::new(fraction, fraction, fraction)
+    }
+}
+
+impl<'a> Integrator<'a> for AmbientOcclusionIntegrator<'a> {
+    fn render(&mut self, camera: &dyn Camera, scene: &Scene, film: &'a mut dyn Film) {
+        // Each sample stands for only part of its pixel
+        let scale = 1.0 / (self.sampler.samples_per_pixel() as f64).sqrt();
+        while let Some(mut sample) = self.sampler.next_sample() {
+            let mut camera_ray = camera.get_ray(&sample);
+            camera_ray.scale_differentials(scale);
+            sample.color = self.calculate_ray_color(scene, &camera_ray);
+            film.add_sample(&sample);
+        }
+        film.develop();
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accelerators::simple_list::SimpleList;
+    use crate::core::light::Light;
+    use crate::core::primitive::Primitive;
+    use crate::core::vector::{Point3f, Vec3f};
+    use crate::light_samplers::uniform_light_sampler::UniformLightSampler;
+    use crate::materials::lambertian_material::LambertianMaterial;
+    use crate::primitives::geometric_primitive::GeometricPrimitive;
+    use crate::primitives::triangle::Triangle;
+    use crate::samplers::perfect_square_sampler::PerfectSquareSampler;
+    use crate::textures::constant_texture::ConstantTexture;
+
+    #[test]
+    fn corner_between_floor_and_wall() {
+        let albedo = ConstantTexture::new(Color3f::new(0.5, 0.5, 0.5));
+        let material = LambertianMaterial::new(&albedo);
+        let floor_shape = Triangle::new(
+            Point3f::new(-1000.0, 0.0, -1000.0),
+            Point3f::new(1000.0, 0.0, -1000.0),
+            Point3f::new(0.0, 0.0, 1000.0),
+        );
+        let wall_shape = Triangle::new(
+            Point3f::new(0.0, -1000.0, -1000.0),
+            Point3f::new(0.0, 1000.0, -1000.0),
+            Point3f::new(0.0, 0.0, 1000.0),
+        );
+        let floor = GeometricPrimitive::new(&floor_shape, &material);
+        let wall = GeometricPrimitive::new(&wall_shape, &material);
+        let primitives = vec![&floor as &dyn Primitive, &wall as &dyn Primitive];
+        let accelerator = SimpleList::new(&primitives);
+        let lights: Vec<&dyn Light> = vec![];
+        let light_sampler = UniformLightSampler::new(&lights);
+        let scene = Scene::new(&accelerator, &lights, &light_sampler);
+
+        let mut sampler = PerfectSquareSampler::new(1, 1, 1);
+        let mut integrator = AmbientOcclusionIntegrator::new(&mut sampler).with_samples(4000);
+
+        // Right next to the wall, which hides half of what the floor sees
+        let corner = Ray::new(
+            Point3f::new(0.001, 1.0, 0.0),
+            Vec3f::new(0.0, -1.0, 0.0),
+            0.0,
+            f64::INFINITY,
+        );
+        let color = integrator.calculate_ray_color(&scene, &corner);
+        assert!((color.x - 0.5).abs() < 0.03, "{:?}", color);
+
+        // Beyond a shorter reach the wall no longer counts
+        let mut integrator = AmbientOcclusionIntegrator::new(&mut sampler)
+            .with_samples(64)
+            .with_max_distance(0.0005);
+        let color = integrator.calculate_ray_color(&scene, &corner);
+        assert_eq!(color, Color3f::new(1.0, 1.0, 1.0));
+
+        let miss = Ray::new(
+            Point3f::new(5.0, 1.0, 0.0),
+            Vec3f::new(0.0, 1.0, 0.0),
+            0.0,
+            f64::INFINITY,
+        );
+        assert!(integrator.calculate_ray_color(&scene, &miss).is_black());
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
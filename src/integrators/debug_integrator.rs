@@ -0,0 +1,311 @@
+use std::ptr;
+
+use crate::core::camera::Camera;
+use crate::core::film::Film;
+use crate::core::integrator::Integrator;
+use crate::core::interaction::Interaction;
+use crate::core::material::Material;
+use crate::core::primitive::Primitive;
+use crate::core::ray::Ray;
+use crate::core::sampler::Sampler;
+use crate::core::scene::Scene;
+use crate::core::vector::{Color3f, Vec3f};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// An [Integrator] writing data about what each camera ray finds straight to the film,
+/// to see what went wrong with an import. Lights and materials play no part.
+/// Values are mapped into the [0.0, 1.0] the film expects.
+pub struct DebugIntegrator<'a> {
+    sampler: &'a mut dyn Sampler,
+    mode: DebugMode,
+
+    /// The primitives [DebugMode::PrimitiveIndex] reports positions in
+    primitives: &'a [&'a dyn Primitive],
+
+    /// The materials [DebugMode::MaterialId] reports positions in
+    materials: &'a [&'a dyn Material],
+
+    /// The hit distance [DebugMode::Depth] shows as white
+    max_distance: f64,
+
+    /// The number of visited nodes [DebugMode::NodesVisited] shows as white
+    max_nodes: usize,
+}
+
+/// What a [DebugIntegrator] shows for each pixel.
+/// Rays that leave the scene are black, and single values are repeated across
+/// the three channels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugMode {
+    /// The normal the surface is shaded with, its `x`, `y` and `z` mapped from [-1.0, 1.0]
+    ShadingNormal,
+
+    /// The true normal of the surface, mapped like [DebugMode::ShadingNormal]
+    GeometricNormal,
+
+    /// The distance `t` along the ray to the hit, white from the maximum distance on
+    Depth,
+
+    /// The surface coordinates of the hit, clamped, with blue at zero
+    Uv,
+
+    /// The position of the hit [Primitive] among the ones it was given, as a color
+    /// made up for each position. Black for primitives not among them.
+    PrimitiveIndex,
+
+    /// The position of the hit's [Material] among the ones it was given,
+    /// colored like [DebugMode::PrimitiveIndex]
+    MaterialId,
+
+    /// The barycentric coordinates of hits on triangles, black on other shapes
+    Barycentrics,
+
+    /// How many nodes of the [Accelerator](crate::core::accelerator::Accelerator)
+    /// finding the hit were visited, white from the maximum count on
+    NodesVisited,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// Keeps the colors made up for ids away from the black of misses.
+const MIN_ID_CHANNEL: f64 = 0.2;
+
+impl<'a> DebugIntegrator<'a> {
+    pub fn new(sampler: &'a mut dyn Sampler, mode: DebugMode) -> Self {
+        Self {
+            sampler,
+            mode,
+            primitives: &[],
+            materials: &[],
+            max_distance: 100.0,
+            max_nodes: 64,
+        }
+    }
+
+    /// Usually the primitives the scene's accelerator was built with. Hits on primitives
+    /// wrapping others, such as alpha masked ones, report the one inside and are not found.
+    pub fn with_primitives(self, primitives: &'a [&'a dyn Primitive]) -> Self {
+        Self { primitives, ..self }
+    }
+
+    pub fn with_materials(self, materials: &'a [&'a dyn Material]) -> Self {
+        Self { materials, ..self }
+    }
+
+    pub fn with_max_distance(self, max_distance: f64) -> Self {
+        Self {
+            max_distance,
+            ..self
+        }
+    }
+
+    pub fn with_max_nodes(self, max_nodes: usize) -> Self {
+        Self { max_nodes, ..self }
+    }
+
+    pub fn calculate_ray_color(&self, scene: &Scene, ray: &Ray) -> Color3f {
+        let hit = || scene.accelerator.test(ray);
+        let black = Color3f::default();
+        match self.mode {
+            DebugMode::ShadingNormal => {
+                hit().map_or(black, |hit| encode_normal(&hit.shading.n.normalize()))
+            }
+            DebugMode::GeometricNormal => hit()
+                .and_then(|hit| hit.n)
+                .map_or(black, |n| encode_normal(&n.normalize())),
+            DebugMode::Depth => hit().map_or(black, |hit| {
+                gray((hit.t / self.max_distance).clamp(0.0, 1.0))
+            }),
+            DebugMode::Uv => hit().map_or(black, |hit| {
+                Color3f::new(hit.uv.x.clamp(0.0, 1.0), hit.uv.y.clamp(0.0, 1.0), 0.0)
+            }),
+            DebugMode::PrimitiveIndex => hit()
+                .and_then(|hit| self.primitive_index(&hit))
+                .map_or(black, id_color),
+            DebugMode::MaterialId => hit()
+                .and_then(|hit| self.material_id(&hit))
+                .map_or(black, id_color),
+            DebugMode::Barycentrics => hit()
+                .and_then(|hit| hit.barycentrics)
+                .map_or(black, |[b0, b1, b2]| Color3f::new(b0, b1, b2)),
+            DebugMode::NodesVisited => {
+                let nodes = scene.accelerator.nodes_visited(ray) as f64;
+                gray((nodes / self.max_nodes as f64).min(1.0))
+            }
+        }
+    }
+
+    fn primitive_index(&self, interaction: &Interaction) -> Option<usize> {
+        let primitive = interaction.primitive?;
+        self.primitives
+            .iter()
+            .position(|other| ptr::addr_eq(*other, primitive))
+    }
+
+    fn material_id(&self, interaction: &Interaction) -> Option<usize> {
+        let material = interaction.material()?;
+        self.materials
+            .iter()
+            .position(|other| ptr::addr_eq(*other, material))
+    }
+}
+
+/// Maps a normal's components from [-1.0, 1.0] to [0.0, 1.0].
+fn encode_normal(n: &Vec3f) -> Color3f {
+    Color3f::new(n.x * 0.5 + 0.5, n.y * 0.5 + 0.5, n.z * 0.5 + 0.5)
+}
+
+/// A color for `id`, scrambled so neighbouring ids look nothing alike.
+fn id_color(id: usize) -> Color3f {
+    // SplitMix64's finalizer
+    let mut h = (id as u64).wrapping_add(0x9e37_79b9_7f4a_7c15);
+    h = (h ^ (h >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    h = (h ^ (h >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    h ^= h >> 31;
+    let channel = |shift: u32| {
+        let unit = ((h >> shift) & 0x1f_ffff) as f64 / 0x1f_ffff as f64;
+        MIN_ID_CHANNEL + (1.0 - MIN_ID_CHANNEL) * unit
+    };
+    Color3f::new(channel(0), channel(21), channel(42))
+}
+
+fn gray(value: f64) -> Color3f {
+    Color3f::new(value, value, value)
+}
+
+impl<'a> Integrator<'a> for DebugIntegrator<'a> {
+    fn render(&mut self, camera: &dyn Camera, scene: &Scene, film: &'a mut dyn Film) {
+        // Each sample stands for only part of its pixel
+        let scale = 1.0 / (self.sampler.samples_per_pixel() as f64).sqrt();
+        while let Some(mut sample) = self.sampler.next_sample() {
+            let mut camera_ray = camera.get_ray(&sample);
+            camera_ray.scale_differentials(scale);
+            sample.color = self.calculate_ray_color(scene, &camera_ray);
+            film.add_sample(&sample);
+        }
+        film.develop();
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accelerators::simple_list::SimpleList;
+    use crate::core::light::Light;
+    use crate::core::vector::{Point3f, Vec3f};
+    use crate::light_samplers::uniform_light_sampler::UniformLightSampler;
+    use crate::materials::lambertian_material::LambertianMaterial;
+    use crate::primitives::geometric_primitive::GeometricPrimitive;
+    use crate::primitives::sphere::Sphere;
+    use crate::primitives::triangle::Triangle;
+    use crate::samplers::perfect_square_sampler::PerfectSquareSampler;
+    use crate::textures::constant_texture::ConstantTexture;
+
+    #[test]
+    fn shows_hit_data() {
+        let albedo = ConstantTexture::new(gray(0.5));
+        let first = LambertianMaterial::new(&albedo);
+        let second = LambertianMaterial::new(&albedo);
+        let unlisted = LambertianMaterial::new(&albedo);
+        let ball = Sphere::new(Point3f::new(0.0, 0.0, -10.0), 1.0);
+        let floor = Triangle::new(
+            Point3f::new(0.0, 0.0, 0.0),
+            Point3f::new(2.0, 0.0, 0.0),
+            Point3f::new(0.0, 2.0, 0.0),
+        );
+        let pebble = Sphere::new(Point3f::new(10.0, 0.0, 0.0), 1.0);
+        let sphere = GeometricPrimitive::new(&ball, &second);
+        let triangle = GeometricPrimitive::new(&floor, &first);
+        let stray = GeometricPrimitive::new(&pebble, &unlisted);
+        let primitives = vec![
+            &sphere as &dyn Primitive,
+            &triangle as &dyn Primitive,
+            &stray as &dyn Primitive,
+        ];
+        let accelerator = SimpleList::new(&primitives);
+        let lights: Vec<&dyn Light> = vec![];
+        let light_sampler = UniformLightSampler::new(&lights);
+        let scene = Scene::new(&accelerator, &lights, &light_sampler);
+        let materials = vec![&first as &dyn Material, &second as &dyn Material];
+
+        let down = Vec3f::new(0.0, 0.0, -1.0);
+        let on_triangle = Ray::new(Point3f::new(0.5, 0.5, 3.0), down, 0.0, 100.0);
+        let on_sphere = Ray::new(Point3f::new(0.0, 0.0, -5.0), down, 0.0, 100.0);
+        let on_stray = Ray::new(Point3f::new(10.0, 0.0, 5.0), down, 0.0, 100.0);
+        let miss = Ray::new(Point3f::new(5.0, 5.0, 3.0), down, 0.0, 100.0);
+
+        let mut sampler = PerfectSquareSampler::new(1, 1, 1);
+        let mut show = |mode: DebugMode, ray: &Ray| {
+            DebugIntegrator::new(&mut sampler, mode)
+                .with_primitives(&primitives)
+                .with_materials(&materials)
+                .with_max_distance(10.0)
+                .with_max_nodes(4)
+                .calculate_ray_color(&scene, ray)
+        };
+
+        let up = Color3f::new(0.5, 0.5, 1.0);
+        assert_eq!(show(DebugMode::ShadingNormal, &on_triangle), up);
+        assert_eq!(show(DebugMode::GeometricNormal, &on_sphere), up);
+        assert_eq!(show(DebugMode::Depth, &on_triangle), gray(0.3));
+        assert_eq!(show(DebugMode::Depth, &on_sphere), gray(0.4));
+        assert_eq!(show(DebugMode::Depth, &miss), Color3f::default());
+        let uv = show(DebugMode::Uv, &on_triangle);
+        assert!((uv - Color3f::new(0.5, 0.25, 0.0)).length() < 1e-12);
+
+        let b = show(DebugMode::Barycentrics, &on_triangle);
+        assert!((b - Color3f::new(0.5, 0.25, 0.25)).length() < 1e-12);
+        assert!(show(DebugMode::Barycentrics, &on_sphere).is_black());
+
+        assert_eq!(show(DebugMode::PrimitiveIndex, &on_sphere), id_color(0));
+        assert_eq!(show(DebugMode::PrimitiveIndex, &on_triangle), id_color(1));
+        assert!(show(DebugMode::PrimitiveIndex, &miss).is_black());
+        assert_eq!(show(DebugMode::MaterialId, &on_sphere), id_color(1));
+        assert_eq!(show(DebugMode::MaterialId, &on_triangle), id_color(0));
+        assert!(show(DebugMode::MaterialId, &on_stray).is_black());
+
+        // The list tests every primitive, hit or not
+        assert_eq!(show(DebugMode::NodesVisited, &miss), gray(0.75));
+
+        // Everything lands in what the film can show
+        for mode in [
+            DebugMode::ShadingNormal,
+            DebugMode::GeometricNormal,
+            DebugMode::Depth,
+            DebugMode::Uv,
+            DebugMode::PrimitiveIndex,
+            DebugMode::MaterialId,
+            DebugMode::Barycentrics,
+            DebugMode::NodesVisited,
+        ] {
+            for ray in [&on_triangle, &on_sphere, &on_stray, &miss] {
+                let color = show(mode, ray);
+                assert!(color.min_component() >= 0.0 && color.max_component() <= 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn ids_look_apart() {
+        for id in 0..100 {
+            let color = id_color(id);
+            assert!(color.min_component() >= MIN_ID_CHANNEL && color.max_component() <= 1.0);
+            assert!((color - id_color(id + 1)).length() > 1e-3);
+        }
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
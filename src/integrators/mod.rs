@@ -1 +1,5 @@
-pub mod sampler_integrator;
\ No newline at end of file
+pub mod ambient_occlusion_integrator;
+pub mod debug_integrator;
+pub mod path_integrator;
+pub mod sampler_integrator;
+pub mod whitted_integrator;
\ No newline at end of file
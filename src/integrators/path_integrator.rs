@@ -0,0 +1,463 @@
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+use crate::core::bsdf::Bsdf;
+use crate::core::bxdf::BsdfSample;
+use crate::core::camera::Camera;
+use crate::core::film::Film;
+use crate::core::integrator::Integrator;
+use crate::core::interaction::Interaction;
+use crate::core::light::Light;
+use crate::core::ray::Ray;
+use crate::core::sampler::Sampler;
+use crate::core::scene::Scene;
+use crate::core::vector::{Color3f, Vec3f};
+use crate::math::sampling::power_heuristic;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// An [Integrator] following paths from the camera as they bounce through [Bsdf]s,
+/// gathering light at each vertex from both a light sampled directly with a shadow ray
+/// and the light the next bounce happens to hit, weighted against each other by
+/// multiple importance sampling. Paths are ended early at random once they carry little.
+pub struct PathIntegrator<'a> {
+    sampler: &'a mut dyn Sampler,
+
+    /// The number of bounces after which paths are cut off
+    max_depth: usize,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// Keeps rays leaving a surface from hitting it again.
+const RAY_EPSILON: f64 = 1e-6;
+
+/// Russian roulette only starts after this many bounces, so short paths stay noise free.
+const MIN_ROULETTE_DEPTH: usize = 1;
+
+impl<'a> PathIntegrator<'a> {
+    pub fn new(sampler: &'a mut dyn Sampler) -> Self {
+        Self {
+            sampler,
+            max_depth: 5,
+        }
+    }
+
+    /// With a `max_depth` of 0 only emitted light is seen, with 1 only direct lighting.
+    pub fn with_max_depth(self, max_depth: usize) -> Self {
+        Self { max_depth, ..self }
+    }
+
+    pub fn calculate_ray_color(&mut self, scene: &Scene, ray: &Ray) -> Color3f {
+        let mut color = Color3f::default();
+        let mut beta = Color3f::new(1.0, 1.0, 1.0);
+        let mut ray = *ray;
+        let mut depth = 0;
+
+        // Whether emission found by the last bounce is counted fully, as nothing else sampled it
+        let mut specular_bounce = true;
+        // The squared relative index of refraction along the path, which roulette leaves out
+        // so paths compressed into denser media aren't cut off for it
+        let mut eta_scale = 1.0;
+        // Where the last bounce left from, and the density of the direction it took
+        let mut previous: Option<Interaction> = None;
+        let mut bsdf_pdf = 0.0;
+
+        loop {
+            let Some(interaction) = scene.accelerator.test(&ray) else {
+                for light in scene.lights {
+                    let le = light.le(&ray);
+                    if le.is_black() {
+                        continue;
+                    }
+                    let weight = match &previous {
+                        Some(previous) if !specular_bounce => {
+                            self.bsdf_weight(scene, previous, *light, &ray.d, bsdf_pdf)
+                        }
+                        _ => 1.0,
+                    };
+                    color += beta * le * weight;
+                }
+                break;
+            };
+
+            let le = interaction.le(&interaction.wo);
+            if !le.is_black() {
+                let weight = match (&previous, interaction.primitive) {
+                    (Some(previous), Some(primitive)) if !specular_bounce => {
+                        primitive.area_light().map_or(1.0, |light| {
+                            self.bsdf_weight(scene, previous, light, &ray.d, bsdf_pdf)
+                        })
+                    }
+                    _ => 1.0,
+                };
+                color += beta * le * weight;
+            }
+
+            // Surfaces without a material absorb everything
+            let Some(material) = interaction.material() else {
+                break;
+            };
+            let Some(bsdf) = material.get_bsdf(&interaction) else {
+                break;
+            };
+            if depth == self.max_depth {
+                break;
+            }
+            depth += 1;
+
+            color += beta * self.sample_direct(scene, &interaction, &bsdf);
+
+            let Some((sample, pdf)) = self.sample_bounce(&interaction, &bsdf, &mut beta) else {
+                break;
+            };
+            bsdf_pdf = pdf;
+            specular_bounce = sample.specular;
+
+            let wo = interaction.wo;
+            let n = interaction.shading.n;
+
+            let geometric_n = interaction.n.unwrap_or(n);
+            let transmitted = sample.wi.dot(&geometric_n) * wo.dot(&geometric_n) < 0.0;
+            if transmitted {
+                eta_scale *= sample.eta * sample.eta;
+            }
+
+            let next = Ray::new(interaction.p, sample.wi, RAY_EPSILON, f64::INFINITY);
+            let differentials = match (specular_bounce, transmitted) {
+                (false, _) => None,
+                (true, false) => interaction.reflected_differentials(&ray, &sample.wi),
+                (true, true) => {
+                    // The surface's own index, inside over outside, from the one along the path
+                    let eta = if wo.dot(&n) >= 0.0 {
+                        sample.eta
+                    } else {
+                        1.0 / sample.eta
+                    };
+                    interaction.refracted_differentials(&ray, &sample.wi, eta)
+                }
+            };
+            ray = match differentials {
+                Some(differentials) => next.with_differentials(differentials),
+                None => next,
+            };
+            previous = Some(interaction);
+
+            // Light refracted into a translucent surface resurfaces somewhere else
+            if transmitted {
+                if let Some(bssrdf) = material.get_bssrdf(&interaction) {
+                    // The walk takes as many values as it needs from a stream of its own,
+                    // seeded by the sampler
+                    let mut rng = SmallRng::seed_from_u64(self.sampler.get_1d().to_bits());
+                    let Some(exit) =
+                        bssrdf.sample_exit(&interaction, &sample.wi, scene.accelerator, &mut rng)
+                    else {
+                        break;
+                    };
+                    beta *= exit.weight;
+
+                    // Where the walk resurfaces is a vertex of the path of its own
+                    if depth == self.max_depth {
+                        break;
+                    }
+                    depth += 1;
+
+                    let exit_interaction = exit.interaction;
+                    color += beta * self.sample_direct(scene, &exit_interaction, &exit.bsdf);
+
+                    let Some((leaving, pdf)) =
+                        self.sample_bounce(&exit_interaction, &exit.bsdf, &mut beta)
+                    else {
+                        break;
+                    };
+                    bsdf_pdf = pdf;
+                    specular_bounce = leaving.specular;
+                    previous = Some(exit_interaction);
+                    ray = Ray::new(exit_interaction.p, leaving.wi, RAY_EPSILON, f64::INFINITY);
+                }
+            }
+
+            if beta.is_black() {
+                break;
+            }
+            let roulette_beta = beta * eta_scale;
+            if depth > MIN_ROULETTE_DEPTH && roulette_beta.max_component() < 1.0 {
+                let q = (1.0 - roulette_beta.max_component()).max(0.0);
+                if self.sampler.get_1d() < q {
+                    break;
+                }
+                beta /= 1.0 - q;
+            }
+        }
+        color
+    }
+
+    /// The light reaching `interaction` from one light picked by the scene's
+    /// [LightSampler](crate::core::light_sampler::LightSampler), reflected towards `wo`
+    /// and weighted against finding the same light by sampling `bsdf`.
+    fn sample_direct(&mut self, scene: &Scene, interaction: &Interaction, bsdf: &Bsdf) -> Color3f {
+        let Some(sampled) = scene
+            .light_sampler
+            .sample(interaction, self.sampler.get_1d())
+        else {
+            return Color3f::default();
+        };
+        let u = self.sampler.get_2d();
+        let Some(sample) = sampled.light.sample_li(interaction, &u) else {
+            return Color3f::default();
+        };
+        if sample.pdf == 0.0 || sample.li.is_black() {
+            return Color3f::default();
+        }
+
+        let wo = interaction.wo;
+        let f = bsdf.f(&wo, &sample.wi) * sample.wi.dot(&interaction.shading.n).abs();
+        if f.is_black() || !sample.is_unoccluded(scene.accelerator) {
+            return Color3f::default();
+        }
+
+        let light_pdf = sampled.pmf * sample.pdf;
+        if sampled.light.is_delta() {
+            return f * sample.li / light_pdf;
+        }
+        let weight = power_heuristic(1, light_pdf, 1, bsdf.pdf(&wo, &sample.wi));
+        f * sample.li * (weight / light_pdf)
+    }
+
+    /// Samples the direction a path leaves `interaction` along through `bsdf`,
+    /// scaling the path's throughput `beta` by it. Returns the sample along with
+    /// the density [PathIntegrator::bsdf_weight] weighs emission found along it with.
+    fn sample_bounce(
+        &mut self,
+        interaction: &Interaction,
+        bsdf: &Bsdf,
+        beta: &mut Color3f,
+    ) -> Option<(BsdfSample, f64)> {
+        let wo = interaction.wo;
+        let uc = self.sampler.get_1d();
+        let u = self.sampler.get_2d();
+        let sample = bsdf.sample_f(&wo, uc, &u)?;
+
+        let cos_theta = sample.wi.dot(&interaction.shading.n).abs();
+        *beta = *beta * sample.f * (cos_theta / sample.pdf);
+        let pdf = if sample.pdf_is_proportional {
+            bsdf.pdf(&wo, &sample.wi)
+        } else {
+            sample.pdf
+        };
+        Some((sample, pdf))
+    }
+
+    /// The weight of emission from `light` found by sampling a [Bsdf] at `previous`
+    /// along `wi` with density `bsdf_pdf`, against [PathIntegrator::sample_direct]
+    /// having picked it.
+    fn bsdf_weight(
+        &self,
+        scene: &Scene,
+        previous: &Interaction,
+        light: &dyn Light,
+        wi: &Vec3f,
+        bsdf_pdf: f64,
+    ) -> f64 {
+        let light_pdf = scene.light_sampler.pmf(previous, light) * light.pdf_li(previous, wi);
+        power_heuristic(1, bsdf_pdf, 1, light_pdf)
+    }
+}
+
+impl<'a> Integrator<'a> for PathIntegrator<'a> {
+    fn render(&mut self, camera: &dyn Camera, scene: &Scene, film: &'a mut dyn Film) {
+        // Each sample stands for only part of its pixel
+        let scale = 1.0 / (self.sampler.samples_per_pixel() as f64).sqrt();
+        while let Some(mut sample) = self.sampler.next_sample() {
+            let mut camera_ray = camera.get_ray(&sample);
+            camera_ray.scale_differentials(scale);
+            sample.color = self.calculate_ray_color(scene, &camera_ray);
+            film.add_sample(&sample);
+        }
+        film.develop();
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::accelerators::simple_list::SimpleList;
+    use crate::core::image::Image;
+    use crate::core::primitive::Primitive;
+    use crate::core::vector::Point3f;
+    use crate::light_samplers::bvh_light_sampler::BvhLightSampler;
+    use crate::lights::diffuse_area_light::DiffuseAreaLight;
+    use crate::lights::environment_light::EnvironmentLight;
+    use crate::lights::point_light::PointLight;
+    use crate::materials::dielectric_material::DielectricMaterial;
+    use crate::materials::lambertian_material::LambertianMaterial;
+    use crate::materials::subsurface_material::SubsurfaceMaterial;
+    use crate::primitives::geometric_primitive::GeometricPrimitive;
+    use crate::primitives::sphere::Sphere;
+    use crate::samplers::perfect_square_sampler::PerfectSquareSampler;
+    use crate::textures::constant_texture::ConstantTexture;
+
+    /// Averages `n` paths along `ray`.
+    fn average(integrator: &mut PathIntegrator, scene: &Scene, ray: &Ray, n: usize) -> Color3f {
+        (0..n).fold(Color3f::default(), |color, _| {
+            color + integrator.calculate_ray_color(scene, ray)
+        }) / n as f64
+    }
+
+    fn gray(value: f64) -> Color3f {
+        Color3f::new(value, value, value)
+    }
+
+    #[test]
+    fn inside_glowing_sphere() {
+        // Every bounce inside a sphere glowing with L and reflecting a of the light
+        // sees L again, adding up to L (1 + a + ... + a^depth)
+        let albedo = ConstantTexture::new(gray(0.5));
+        let material = LambertianMaterial::new(&albedo);
+        let radiance = ConstantTexture::new(gray(1.0));
+        let sphere = Sphere::new(Point3f::default(), 1.0);
+        let light = DiffuseAreaLight::new(&sphere, &radiance).with_two_sided(true);
+        let wall = GeometricPrimitive::new(&sphere, &material).with_area_light(&light);
+        let primitives = vec![&wall as &dyn Primitive];
+        let accelerator = SimpleList::new(&primitives);
+        let lights = vec![&light as &dyn Light];
+        let light_sampler = BvhLightSampler::new(&lights);
+        let scene = Scene::new(&accelerator, &lights, &light_sampler);
+
+        let mut sampler = PerfectSquareSampler::new(1, 1, 1);
+        let ray = Ray::new(
+            Point3f::new(0.0, 0.2, 0.0),
+            Vec3f::new(1.0, 0.0, 0.0),
+            0.0,
+            f64::INFINITY,
+        );
+        for (depth, expected) in [(0, 1.0), (1, 1.5), (3, 1.875)] {
+            let mut integrator = PathIntegrator::new(&mut sampler).with_max_depth(depth);
+            let color = average(&mut integrator, &scene, &ray, 4000);
+            assert!(
+                (color.x - expected).abs() < 0.02 * expected,
+                "{} {:?}",
+                depth,
+                color
+            );
+        }
+    }
+
+    #[test]
+    fn white_furnace() {
+        // A diffuse sphere under a uniform sky only ever sees the sky,
+        // so it reflects its albedo of it whatever the lighting strategy
+        let albedo = ConstantTexture::new(gray(0.8));
+        let material = LambertianMaterial::new(&albedo);
+        let sphere = Sphere::new(Point3f::default(), 1.0);
+        let diffuse = GeometricPrimitive::new(&sphere, &material);
+        let primitives = vec![&diffuse as &dyn Primitive];
+        let accelerator = SimpleList::new(&primitives);
+        let sky = EnvironmentLight::new(Image::new(8, 4, vec![gray(1.0); 32]));
+        let lights = vec![&sky as &dyn Light];
+        let light_sampler = BvhLightSampler::new(&lights);
+        let scene = Scene::new(&accelerator, &lights, &light_sampler);
+
+        let mut sampler = PerfectSquareSampler::new(1, 1, 1);
+        let mut integrator = PathIntegrator::new(&mut sampler);
+        let ray = Ray::new(
+            Point3f::new(0.3, 0.0, 5.0),
+            Vec3f::new(0.0, 0.0, -1.0),
+            0.0,
+            f64::INFINITY,
+        );
+        let color = average(&mut integrator, &scene, &ray, 4000);
+        assert!((color.x - 0.8).abs() < 0.02, "{:?}", color);
+
+        // The sky itself
+        let up = Ray::new(Point3f::default(), Vec3f::new(0.0, 0.0, 1.0), 2.0, 100.0);
+        assert_eq!(integrator.calculate_ray_color(&scene, &up), gray(1.0));
+    }
+
+    #[test]
+    fn translucent_under_point_light() {
+        // Only a light sampled directly where the walk resurfaces can reach the eye,
+        // as the smooth boundary lets nothing else find a point light
+        let albedo = ConstantTexture::new(gray(0.8));
+        let mean_free_path = ConstantTexture::new(gray(0.1));
+        let wax = SubsurfaceMaterial::new_from_albedo(1.33, &albedo, &mean_free_path);
+        let sphere = Sphere::new(Point3f::default(), 1.0);
+        let ball = GeometricPrimitive::new(&sphere, &wax);
+        let primitives = vec![&ball as &dyn Primitive];
+        let accelerator = SimpleList::new(&primitives);
+        let light = PointLight::new(Point3f::new(0.0, 0.0, 3.0), gray(4.0));
+        let lights = vec![&light as &dyn Light];
+        let light_sampler = BvhLightSampler::new(&lights);
+        let scene = Scene::new(&accelerator, &lights, &light_sampler);
+
+        let mut sampler = PerfectSquareSampler::new(1, 1, 1);
+        let mut integrator = PathIntegrator::new(&mut sampler);
+        let ray = Ray::new(
+            Point3f::new(0.0, 0.0, 5.0),
+            Vec3f::new(0.0, 0.0, -1.0),
+            0.0,
+            f64::INFINITY,
+        );
+        // A white diffuse surface there would show 1 / pi * 4 / 2^2
+        let color = average(&mut integrator, &scene, &ray, 2000);
+        assert!(color.x > 0.05 && color.x < 1.0 / PI, "{:?}", color);
+
+        // Resurfacing is a bounce of its own, beyond the one into the ball
+        let mut sampler = PerfectSquareSampler::new(1, 1, 1);
+        let mut integrator = PathIntegrator::new(&mut sampler).with_max_depth(1);
+        assert!(average(&mut integrator, &scene, &ray, 100).is_black());
+    }
+
+    #[test]
+    fn seen_through_glass() {
+        // A glowing ball behind a glass one, found only by following refractions,
+        // dimmed by the light reflected away at each of the glass' two surfaces
+        let clear = DielectricMaterial::new(1.5);
+        let ball = Sphere::new(Point3f::new(0.0, 0.0, 2.0), 1.0);
+        let glass = GeometricPrimitive::new(&ball, &clear);
+        let black = ConstantTexture::new(gray(0.0));
+        let dark = LambertianMaterial::new(&black);
+        let radiance = ConstantTexture::new(gray(1.0));
+        let lamp = Sphere::new(Point3f::new(0.0, 0.0, 6.0), 1.0);
+        let light = DiffuseAreaLight::new(&lamp, &radiance);
+        let bulb = GeometricPrimitive::new(&lamp, &dark).with_area_light(&light);
+        let primitives = vec![&glass as &dyn Primitive, &bulb as &dyn Primitive];
+        let accelerator = SimpleList::new(&primitives);
+        let lights = vec![&light as &dyn Light];
+        let light_sampler = BvhLightSampler::new(&lights);
+        let scene = Scene::new(&accelerator, &lights, &light_sampler);
+
+        let mut sampler = PerfectSquareSampler::new(1, 1, 1);
+        let mut integrator = PathIntegrator::new(&mut sampler);
+        let ray = Ray::new(
+            Point3f::default(),
+            Vec3f::new(0.0, 0.0, 1.0),
+            0.0,
+            f64::INFINITY,
+        );
+        let reflectance: f64 = (0.5 / 2.5) * (0.5 / 2.5);
+        let expected = (1.0 - reflectance).powi(2);
+        let color = average(&mut integrator, &scene, &ray, 4000);
+        assert!((color.x - expected).abs() < 0.02, "{:?}", color);
+
+        // Cut off before the second refraction
+        let mut integrator = PathIntegrator::new(&mut sampler).with_max_depth(1);
+        assert!(integrator.calculate_ray_color(&scene, &ray).is_black());
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
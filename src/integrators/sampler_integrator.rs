@@ -1,17 +1,20 @@
-use crate::core::accelerator::Accelerator;
 use crate::core::camera::Camera;
 use crate::core::film::Film;
 use crate::core::integrator::Integrator;
+use crate::core::interaction::Interaction;
 use crate::core::ray::Ray;
 use crate::core::sampler::Sampler;
+use crate::core::scene::Scene;
 use crate::core::vector::Color3f;
 
 /////////////////////
 // BEGIN INTERFACE //
 /////////////////////
 
-/// An [Integrator] implementation that samples screen-space coordinates
-/// with the help of a [Sampler].
+/// An [Integrator] showing direct lighting only. At each camera ray's first hit,
+/// one light is picked by the scene's [LightSampler](crate::core::light_sampler::LightSampler)
+/// and its light reflected towards the camera, shadows included. Rays that miss see
+/// the lights' emission into the background.
 pub struct SamplerIntegrator<'a> {
     sampler: &'a mut dyn Sampler,
 }
@@ -26,27 +29,59 @@ impl<'a> SamplerIntegrator<'a> {
         Self { sampler }
     }
 
-    pub fn calculate_ray_color(&self, accelerator: &dyn Accelerator, ray: &Ray) -> Color3f {
-        let mut attenuation = Color3f::new(1.0, 1.0, 1.0);
-
-        if let Some(_) = accelerator.test(ray) {
-            attenuation *= Color3f::new(0.0, 0.0, 0.0);
+    pub fn calculate_ray_color(&mut self, scene: &Scene, ray: &Ray) -> Color3f {
+        match scene.accelerator.test(ray) {
+            Some(interaction) => {
+                interaction.le(&interaction.wo) + self.direct_lighting(scene, &interaction)
+            }
+            None => scene
+                .lights
+                .iter()
+                .fold(Color3f::default(), |color, light| color + light.le(ray)),
         }
+    }
 
-        attenuation
+    /// Gathers the light reflected towards `wo` straight from one light,
+    /// picked by the scene's [LightSampler](crate::core::light_sampler::LightSampler).
+    /// Surfaces without a material stay black.
+    fn direct_lighting(&mut self, scene: &Scene, interaction: &Interaction) -> Color3f {
+        let Some(bsdf) = interaction
+            .material()
+            .and_then(|material| material.get_bsdf(interaction))
+        else {
+            return Color3f::default();
+        };
+
+        let Some(sampled) = scene
+            .light_sampler
+            .sample(interaction, self.sampler.get_1d())
+        else {
+            return Color3f::default();
+        };
+        let u = self.sampler.get_2d();
+        let Some(sample) = sampled.light.sample_li(interaction, &u) else {
+            return Color3f::default();
+        };
+        if sample.pdf == 0.0 || sample.li.is_black() {
+            return Color3f::default();
+        }
+        let f = bsdf.f(&interaction.wo, &sample.wi);
+        if f.is_black() || !sample.is_unoccluded(scene.accelerator) {
+            return Color3f::default();
+        }
+        let cos_theta = sample.wi.dot(&interaction.shading.n).abs();
+        f * sample.li * (cos_theta / (sample.pdf * sampled.pmf))
     }
 }
 
 impl<'a> Integrator<'a> for SamplerIntegrator<'a> {
-    fn render(
-        &mut self,
-        camera: &dyn Camera,
-        accelerator: &dyn Accelerator,
-        film: &'a mut dyn Film,
-    ) {
+    fn render(&mut self, camera: &dyn Camera, scene: &Scene, film: &'a mut dyn Film) {
+        // Each sample stands for only part of its pixel
+        let scale = 1.0 / (self.sampler.samples_per_pixel() as f64).sqrt();
         while let Some(mut sample) = self.sampler.next_sample() {
-            let camera_ray = camera.get_ray(&sample);
-            sample.color = self.calculate_ray_color(accelerator, &camera_ray);
+            let mut camera_ray = camera.get_ray(&sample);
+            camera_ray.scale_differentials(scale);
+            sample.color = self.calculate_ray_color(scene, &camera_ray);
             film.add_sample(&sample);
         }
         film.develop();
@@ -58,6 +93,68 @@ impl<'a> Integrator<'a> for SamplerIntegrator<'a> {
 // BEGIN TESTS        //
 ////////////////////////
 
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::accelerators::simple_list::SimpleList;
+    use crate::core::light::Light;
+    use crate::core::primitive::Primitive;
+    use crate::core::vector::{Point3f, Vec3f};
+    use crate::light_samplers::bvh_light_sampler::BvhLightSampler;
+    use crate::lights::point_light::PointLight;
+    use crate::materials::lambertian_material::LambertianMaterial;
+    use crate::primitives::geometric_primitive::GeometricPrimitive;
+    use crate::primitives::sphere::Sphere;
+    use crate::samplers::perfect_square_sampler::PerfectSquareSampler;
+    use crate::textures::constant_texture::ConstantTexture;
+
+    #[test]
+    fn lit_by_point_lights() {
+        let albedo = ConstantTexture::new(Color3f::new(0.5, 0.5, 0.5));
+        let material = LambertianMaterial::new(&albedo);
+        let sphere = Sphere::new(Point3f::new(0.0, 0.0, 0.0), 1.0);
+        let diffuse = GeometricPrimitive::new(&sphere, &material);
+        let primitives = vec![&diffuse as &dyn Primitive];
+        let accelerator = SimpleList::new(&primitives);
+
+        // One light straight above the top of the sphere, one hidden behind it
+        let above = PointLight::new(Point3f::new(0.0, 3.0, 0.0), Color3f::new(4.0, 4.0, 4.0));
+        let below = PointLight::new(Point3f::new(0.0, -3.0, 0.0), Color3f::new(4.0, 4.0, 4.0));
+        let lights = vec![&above as &dyn Light, &below as &dyn Light];
+        let light_sampler = BvhLightSampler::new(&lights);
+        let scene = Scene::new(&accelerator, &lights, &light_sampler);
+
+        let mut sampler = PerfectSquareSampler::new(1, 1, 1);
+        let mut integrator = SamplerIntegrator::new(&mut sampler);
+
+        let down = Ray::new(
+            Point3f::new(0.0, 5.0, 0.0),
+            Vec3f::new(0.0, -1.0, 0.0),
+            0.0,
+            100.0,
+        );
+        // One light is picked per hit, mostly the nearer one
+        let n = 2000;
+        let color = (0..n).fold(Color3f::default(), |color, _| {
+            color + integrator.calculate_ray_color(&scene, &down)
+        }) / n as f64;
+        // albedo / pi * intensity / distance^2
+        let expected = 0.5 / PI * 4.0 / 4.0;
+        assert!((color.x - expected).abs() < 0.03 * expected, "{:?}", color);
+
+        let miss = Ray::new(
+            Point3f::new(0.0, 5.0, 0.0),
+            Vec3f::new(0.0, 1.0, 0.0),
+            0.0,
+            100.0,
+        );
+        // Nothing surrounds the scene
+        assert!(integrator.calculate_ray_color(&scene, &miss).is_black());
+    }
+}
+
 ///////////////
 // END TESTS //
 ///////////////
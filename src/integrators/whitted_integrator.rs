@@ -0,0 +1,295 @@
+use crate::core::bsdf::Bsdf;
+use crate::core::camera::Camera;
+use crate::core::film::Film;
+use crate::core::integrator::Integrator;
+use crate::core::interaction::Interaction;
+use crate::core::ray::Ray;
+use crate::core::sampler::Sampler;
+use crate::core::scene::Scene;
+use crate::core::vector::{Color3f, Point2f};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// An [Integrator] in the style of Whitted: every light with a position is sampled
+/// once with a hard shadow ray, and perfectly specular surfaces are followed recursively
+/// along both their reflection and refraction. Nothing is random, so the same scene
+/// always gives the same noise free, if biased, image.
+pub struct WhittedIntegrator<'a> {
+    sampler: &'a mut dyn Sampler,
+
+    /// The number of specular bounces after which rays are no longer followed
+    max_depth: usize,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// Keeps rays leaving a surface from hitting it again.
+const RAY_EPSILON: f64 = 1e-6;
+
+/// Where each light is sampled, the middle of its domain.
+const LIGHT_SAMPLE: Point2f = Point2f { x: 0.5, y: 0.5 };
+
+/// The largest sample below one, picking the last of a [Bsdf]'s specular lobes.
+const ONE_MINUS_EPSILON: f64 = 1.0 - f64::EPSILON / 2.0;
+
+impl<'a> WhittedIntegrator<'a> {
+    pub fn new(sampler: &'a mut dyn Sampler) -> Self {
+        Self {
+            sampler,
+            max_depth: 5,
+        }
+    }
+
+    /// With a `max_depth` of 0 specular surfaces only show their direct lighting.
+    pub fn with_max_depth(self, max_depth: usize) -> Self {
+        Self { max_depth, ..self }
+    }
+
+    pub fn calculate_ray_color(&self, scene: &Scene, ray: &Ray) -> Color3f {
+        self.trace(scene, ray, 0)
+    }
+
+    fn trace(&self, scene: &Scene, ray: &Ray, depth: usize) -> Color3f {
+        let Some(interaction) = scene.accelerator.test(ray) else {
+            return scene
+                .lights
+                .iter()
+                .fold(Color3f::default(), |color, light| color + light.le(ray));
+        };
+
+        let mut color = interaction.le(&interaction.wo);
+        let Some(bsdf) = interaction
+            .material()
+            .and_then(|material| material.get_bsdf(&interaction))
+        else {
+            return color;
+        };
+
+        color += Self::direct_lighting(scene, &interaction, &bsdf);
+        if depth < self.max_depth {
+            color += self.specular(scene, ray, &interaction, &bsdf, depth);
+        }
+        color
+    }
+
+    /// The light reflected towards `wo` from every light that has a position or a single
+    /// direction, each sampled once at its middle. Lights surrounding the scene, which
+    /// one sample would represent poorly, are only seen by rays leaving it.
+    fn direct_lighting(scene: &Scene, interaction: &Interaction, bsdf: &Bsdf) -> Color3f {
+        let mut color = Color3f::default();
+        for light in scene.lights {
+            if !light.is_delta() && light.bounds().is_none() {
+                continue;
+            }
+            let Some(sample) = light.sample_li(interaction, &LIGHT_SAMPLE) else {
+                continue;
+            };
+            if sample.pdf == 0.0 || sample.li.is_black() {
+                continue;
+            }
+            let f = bsdf.f(&interaction.wo, &sample.wi);
+            if f.is_black() || !sample.is_unoccluded(scene.accelerator) {
+                continue;
+            }
+            let cos_theta = sample.wi.dot(&interaction.shading.n).abs();
+            color += f * sample.li * (cos_theta / sample.pdf);
+        }
+        color
+    }
+
+    /// The light arriving along the specular lobes of `bsdf`, traced recursively.
+    /// A [Bsdf] picks between its lobes with its first sample, so the start of the range
+    /// finds the first lobe and, unless that one was certain, the end finds the other.
+    /// Each is weighted by what it carries rather than divided by its probability.
+    fn specular(
+        &self,
+        scene: &Scene,
+        ray: &Ray,
+        interaction: &Interaction,
+        bsdf: &Bsdf,
+        depth: usize,
+    ) -> Color3f {
+        let wo = interaction.wo;
+        let n = interaction.shading.n;
+        let mut color = Color3f::default();
+        let mut covered = 0.0;
+        for uc in [0.0, ONE_MINUS_EPSILON] {
+            if covered >= 1.0 {
+                break;
+            }
+            let Some(sample) = bsdf.sample_f(&wo, uc, &Point2f::default()) else {
+                continue;
+            };
+            if !sample.specular {
+                break;
+            }
+            covered += sample.pdf;
+
+            let geometric_n = interaction.n.unwrap_or(n);
+            let transmitted = sample.wi.dot(&geometric_n) * wo.dot(&geometric_n) < 0.0;
+            let differentials = if transmitted {
+                // The surface's own index, inside over outside, from the one along the ray
+                let eta = if wo.dot(&n) >= 0.0 {
+                    sample.eta
+                } else {
+                    1.0 / sample.eta
+                };
+                interaction.refracted_differentials(ray, &sample.wi, eta)
+            } else {
+                interaction.reflected_differentials(ray, &sample.wi)
+            };
+            let next = Ray::new(interaction.p, sample.wi, RAY_EPSILON, f64::INFINITY);
+            let next = match differentials {
+                Some(differentials) => next.with_differentials(differentials),
+                None => next,
+            };
+
+            let li = self.trace(scene, &next, depth + 1);
+            color += sample.f * li * sample.wi.dot(&n).abs();
+        }
+        color
+    }
+}
+
+impl<'a> Integrator<'a> for WhittedIntegrator<'a> {
+    fn render(&mut self, camera: &dyn Camera, scene: &Scene, film: &'a mut dyn Film) {
+        // Each sample stands for only part of its pixel
+        let scale = 1.0 / (self.sampler.samples_per_pixel() as f64).sqrt();
+        while let Some(mut sample) = self.sampler.next_sample() {
+            let mut camera_ray = camera.get_ray(&sample);
+            camera_ray.scale_differentials(scale);
+            sample.color = self.calculate_ray_color(scene, &camera_ray);
+            film.add_sample(&sample);
+        }
+        film.develop();
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::accelerators::simple_list::SimpleList;
+    use crate::core::light::Light;
+    use crate::core::primitive::Primitive;
+    use crate::core::vector::{Point3f, Vec3f};
+    use crate::light_samplers::uniform_light_sampler::UniformLightSampler;
+    use crate::lights::diffuse_area_light::DiffuseAreaLight;
+    use crate::lights::point_light::PointLight;
+    use crate::materials::dielectric_material::DielectricMaterial;
+    use crate::materials::lambertian_material::LambertianMaterial;
+    use crate::primitives::geometric_primitive::GeometricPrimitive;
+    use crate::primitives::sphere::Sphere;
+    use crate::samplers::perfect_square_sampler::PerfectSquareSampler;
+    use crate::textures::constant_texture::ConstantTexture;
+
+    fn gray(value: f64) -> Color3f {
+        Color3f::new(value, value, value)
+    }
+
+    #[test]
+    fn hard_shadows() {
+        let albedo = ConstantTexture::new(gray(0.5));
+        let material = LambertianMaterial::new(&albedo);
+        let sphere = Sphere::new(Point3f::default(), 1.0);
+        let diffuse = GeometricPrimitive::new(&sphere, &material);
+        let radiance = ConstantTexture::new(gray(100.0));
+        let bulb = Sphere::new(Point3f::new(0.0, 11.0, 0.0), 0.1);
+        let area = DiffuseAreaLight::new(&bulb, &radiance);
+        let lamp = GeometricPrimitive::new(&bulb, &material).with_area_light(&area);
+        let primitives = vec![&diffuse as &dyn Primitive, &lamp as &dyn Primitive];
+        let accelerator = SimpleList::new(&primitives);
+
+        // A point light straight above the top of the sphere, one hidden behind it,
+        // and a small glowing ball further up
+        let above = PointLight::new(Point3f::new(0.0, 3.0, 0.0), gray(4.0));
+        let below = PointLight::new(Point3f::new(0.0, -3.0, 0.0), gray(4.0));
+        let lights = vec![
+            &above as &dyn Light,
+            &below as &dyn Light,
+            &area as &dyn Light,
+        ];
+        let light_sampler = UniformLightSampler::new(&lights);
+        let scene = Scene::new(&accelerator, &lights, &light_sampler);
+
+        let mut sampler = PerfectSquareSampler::new(1, 1, 1);
+        let integrator = WhittedIntegrator::new(&mut sampler);
+        let down = Ray::new(
+            Point3f::new(0.0, 5.0, 0.0),
+            Vec3f::new(0.0, -1.0, 0.0),
+            0.0,
+            100.0,
+        );
+        // albedo / pi * (intensity / distance^2 + radiance * pi * sin^2(theta_max))
+        let point = 4.0 / 4.0;
+        let ball = 100.0 * PI * (0.1 * 0.1) / (10.0 * 10.0);
+        let expected = 0.5 / PI * (point + ball);
+        let color = integrator.calculate_ray_color(&scene, &down);
+        assert!((color.x - expected).abs() < 1e-3 * expected, "{:?}", color);
+        // Nothing random goes into it
+        assert_eq!(integrator.calculate_ray_color(&scene, &down), color);
+
+        let up = Ray::new(
+            Point3f::new(0.0, -5.0, 0.0),
+            Vec3f::new(0.0, 1.0, 0.0),
+            0.0,
+            100.0,
+        );
+        let expected = 0.5 / PI * 4.0 / 4.0;
+        let color = integrator.calculate_ray_color(&scene, &up);
+        assert!((color.x - expected).abs() < 1e-9, "{:?}", color);
+    }
+
+    #[test]
+    fn through_glass() {
+        // A glowing ball behind a glass one, dimmed by the light reflected away at each
+        // of the glass' two surfaces, plus a little more bouncing around inside
+        let clear = DielectricMaterial::new(1.5);
+        let ball = Sphere::new(Point3f::new(0.0, 0.0, 2.0), 1.0);
+        let glass = GeometricPrimitive::new(&ball, &clear);
+        let black = ConstantTexture::new(gray(0.0));
+        let dark = LambertianMaterial::new(&black);
+        let radiance = ConstantTexture::new(gray(1.0));
+        let lamp = Sphere::new(Point3f::new(0.0, 0.0, 6.0), 1.0);
+        let light = DiffuseAreaLight::new(&lamp, &radiance);
+        let bulb = GeometricPrimitive::new(&lamp, &dark).with_area_light(&light);
+        let primitives = vec![&glass as &dyn Primitive, &bulb as &dyn Primitive];
+        let accelerator = SimpleList::new(&primitives);
+        let lights = vec![&light as &dyn Light];
+        let light_sampler = UniformLightSampler::new(&lights);
+        let scene = Scene::new(&accelerator, &lights, &light_sampler);
+
+        let mut sampler = PerfectSquareSampler::new(1, 1, 1);
+        let ray = Ray::new(
+            Point3f::default(),
+            Vec3f::new(0.0, 0.0, 1.0),
+            0.0,
+            f64::INFINITY,
+        );
+        let reflectance: f64 = (0.5 / 2.5) * (0.5 / 2.5);
+        let transmittance = 1.0 - reflectance;
+        let expected = transmittance.powi(2) * (1.0 + reflectance.powi(2));
+        let integrator = WhittedIntegrator::new(&mut sampler);
+        let color = integrator.calculate_ray_color(&scene, &ray);
+        assert!((color.x - expected).abs() < 1e-6, "{:?}", color);
+
+        // Cut off before the second refraction
+        let integrator = WhittedIntegrator::new(&mut sampler).with_max_depth(1);
+        assert!(integrator.calculate_ray_color(&scene, &ray).is_black());
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
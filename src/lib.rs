@@ -0,0 +1,16 @@
+pub mod accelerators;
+pub mod bssrdfs;
+pub mod bxdfs;
+pub mod cameras;
+pub mod core;
+pub mod films;
+pub mod images;
+pub mod integrators;
+pub mod light_samplers;
+pub mod lights;
+pub mod materials;
+pub mod math;
+pub mod microfacets;
+pub mod primitives;
+pub mod samplers;
+pub mod textures;
@@ -0,0 +1,494 @@
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use crate::core::bounds::Bounds3f;
+use crate::core::interaction::Interaction;
+use crate::core::light::{Light, LightBounds};
+use crate::core::light_sampler::{LightSampler, SampledLight};
+use crate::core::vector::{Point3f, Vec3f};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A [LightSampler] walking a bounding volume hierarchy over the lights,
+/// choosing between branches by the light they could send to the shading point,
+/// as estimated from their [LightBounds]. Scenes with thousands of small lights
+/// then mostly pick the few that matter nearby.
+/// Lights surrounding the scene can't be bounded and are picked uniformly beside the tree.
+pub struct BvhLightSampler<'a> {
+    lights: &'a [&'a dyn Light],
+
+    /// Indices into `lights`
+    infinite: Vec<usize>,
+
+    /// Depth first, with each interior node's first child right after it
+    nodes: Vec<LightBvhNode>,
+
+    /// The branches taken from the root to each bounded light's leaf,
+    /// keyed by the light's address, with the first branch in the lowest bit
+    /// and set bits going to the second child
+    trails: HashMap<usize, u64>,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+struct LightBvhNode {
+    bounds: LightBounds,
+    kind: NodeKind,
+}
+
+enum NodeKind {
+    /// Holds the index of the light in `lights`
+    Leaf(usize),
+
+    /// Holds the index of the second child in `nodes`
+    Interior(usize),
+}
+
+/// How many buckets along each axis candidate splits are evaluated between.
+const SPLIT_BUCKETS: usize = 12;
+
+/// The largest [f64] below 1.0, keeping rescaled sample values in [0.0, 1.0).
+const ONE_MINUS_EPSILON: f64 = 1.0 - f64::EPSILON / 2.0;
+
+fn address(light: &dyn Light) -> usize {
+    light as *const dyn Light as *const () as usize
+}
+
+fn component(v: &Vec3f, axis: usize) -> f64 {
+    match axis {
+        0 => v.x,
+        1 => v.y,
+        _ => v.z,
+    }
+}
+
+impl<'a> BvhLightSampler<'a> {
+    pub fn new(lights: &'a [&'a dyn Light]) -> Self {
+        let mut infinite = Vec::new();
+        let mut bounded = Vec::new();
+        for (index, light) in lights.iter().enumerate() {
+            match light.bounds() {
+                None => infinite.push(index),
+                Some(bounds) if bounds.phi > 0.0 => bounded.push((index, bounds)),
+                // Black lights never need sampling
+                Some(_) => {}
+            }
+        }
+
+        let mut sampler = Self {
+            lights,
+            infinite,
+            nodes: Vec::new(),
+            trails: HashMap::new(),
+        };
+        if !bounded.is_empty() {
+            sampler.build(&mut bounded, 0, 0);
+        }
+        sampler
+    }
+
+    /// Appends the subtree over `lights` and returns its bounds.
+    fn build(
+        &mut self,
+        lights: &mut [(usize, LightBounds)],
+        trail: u64,
+        depth: u32,
+    ) -> LightBounds {
+        if let [(index, bounds)] = lights {
+            self.trails.insert(address(self.lights[*index]), trail);
+            self.nodes.push(LightBvhNode {
+                bounds: *bounds,
+                kind: NodeKind::Leaf(*index),
+            });
+            return *bounds;
+        }
+
+        // Balanced splits from here on once the trail has no bits to spare,
+        // so even lopsided sets of lights never run out of them
+        let levels_needed = lights.len().next_power_of_two().trailing_zeros();
+        let mid = if levels_needed < u64::BITS - depth {
+            Self::split(lights)
+        } else {
+            Self::median_split(lights)
+        };
+        let node_index = self.nodes.len();
+        self.nodes.push(LightBvhNode {
+            bounds: lights[0].1,
+            kind: NodeKind::Interior(0),
+        });
+        let (first, second) = lights.split_at_mut(mid);
+        let first_bounds = self.build(first, trail, depth + 1);
+        let second_index = self.nodes.len();
+        let second_bounds = self.build(second, trail | (1 << depth), depth + 1);
+
+        let bounds = first_bounds.union(&second_bounds);
+        self.nodes[node_index] = LightBvhNode {
+            bounds,
+            kind: NodeKind::Interior(second_index),
+        };
+        bounds
+    }
+
+    /// Reorders `lights` into two groups and returns where the second begins,
+    /// splitting where the summed cost of the groups' bounds is least.
+    fn split(lights: &mut [(usize, LightBounds)]) -> usize {
+        let bounds = lights[1..]
+            .iter()
+            .fold(lights[0].1.bounds, |b, (_, l)| b.union(&l.bounds));
+        let first_centroid = Bounds3f::from_point(lights[0].1.bounds.centroid());
+        let centroids = lights[1..].iter().fold(first_centroid, |b, (_, l)| {
+            b.union(&Bounds3f::from_point(l.bounds.centroid()))
+        });
+
+        let bucket_of = |light: &LightBounds, axis: usize| {
+            let min = component(&centroids.min(), axis);
+            let extent = component(&centroids.diagonal(), axis);
+            let offset = (component(&light.bounds.centroid(), axis) - min) / extent;
+            ((offset * SPLIT_BUCKETS as f64) as usize).min(SPLIT_BUCKETS - 1)
+        };
+
+        let mut best: Option<(f64, usize, usize)> = None;
+        for axis in 0..3 {
+            if component(&centroids.diagonal(), axis) == 0.0 {
+                continue;
+            }
+            let mut buckets: [Option<LightBounds>; SPLIT_BUCKETS] = [None; SPLIT_BUCKETS];
+            for (_, light) in lights.iter() {
+                let bucket = &mut buckets[bucket_of(light, axis)];
+                *bucket = Some(bucket.map_or(*light, |b| b.union(light)));
+            }
+
+            for split in 1..SPLIT_BUCKETS {
+                let union = |range: &[Option<LightBounds>]| {
+                    range.iter().flatten().copied().reduce(|a, b| a.union(&b))
+                };
+                let (Some(below), Some(above)) =
+                    (union(&buckets[..split]), union(&buckets[split..]))
+                else {
+                    continue;
+                };
+                let cost = Self::cost(&below, &bounds, axis) + Self::cost(&above, &bounds, axis);
+                if best.is_none_or(|(best_cost, _, _)| cost < best_cost) {
+                    best = Some((cost, axis, split));
+                }
+            }
+        }
+
+        let Some((_, axis, split)) = best else {
+            // Every light sits in the same place
+            return lights.len() / 2;
+        };
+        lights.sort_by_key(|(_, light)| bucket_of(light, axis) >= split);
+        let mid = lights.partition_point(|(_, light)| bucket_of(light, axis) < split);
+        if mid == 0 || mid == lights.len() {
+            lights.len() / 2
+        } else {
+            mid
+        }
+    }
+
+    /// Reorders `lights` along the axis their centroids spread furthest on
+    /// and returns the middle, the split that keeps the hierarchy shallowest.
+    fn median_split(lights: &mut [(usize, LightBounds)]) -> usize {
+        let first_centroid = Bounds3f::from_point(lights[0].1.bounds.centroid());
+        let centroids = lights[1..].iter().fold(first_centroid, |b, (_, l)| {
+            b.union(&Bounds3f::from_point(l.bounds.centroid()))
+        });
+        let diagonal = centroids.diagonal();
+        let axis = (0..3)
+            .max_by(|&a, &b| component(&diagonal, a).total_cmp(&component(&diagonal, b)))
+            .unwrap_or(0);
+
+        let mid = lights.len() / 2;
+        lights.select_nth_unstable_by(mid, |(_, a), (_, b)| {
+            let a = component(&a.bounds.centroid(), axis);
+            let b = component(&b.bounds.centroid(), axis);
+            a.total_cmp(&b)
+        });
+        mid
+    }
+
+    /// Grows with the power, extent and spread of directions of `light`, a candidate child
+    /// of a node spanning `bounds` split along `axis`. Splitting across short axes costs more.
+    fn cost(light: &LightBounds, bounds: &Bounds3f, axis: usize) -> f64 {
+        let theta_o = light.normals.cos_theta.clamp(-1.0, 1.0).acos();
+        let theta_e = light.cos_theta_e.clamp(-1.0, 1.0).acos();
+        let theta_w = (theta_o + theta_e).min(PI);
+        let (sin_theta_o, cos_theta_o) = theta_o.sin_cos();
+        // The solid angle of the cone, plus the emission past it weighted by cosine
+        let m_omega = 2.0 * PI * (1.0 - cos_theta_o)
+            + PI / 2.0
+                * (2.0 * theta_w * sin_theta_o
+                    - (theta_o - 2.0 * theta_w).cos()
+                    - 2.0 * theta_o * sin_theta_o
+                    + cos_theta_o);
+
+        let diagonal = bounds.diagonal();
+        let regularity = diagonal.max_component() / component(&diagonal, axis);
+        regularity * light.phi * m_omega * light.bounds.surface_area()
+    }
+
+    /// The probability of having picked the bounded lights at all.
+    fn bounded_probability(&self) -> f64 {
+        if self.nodes.is_empty() {
+            return 0.0;
+        }
+        1.0 / (self.infinite.len() + 1) as f64
+    }
+
+    /// The importances of the children of the interior node at `index`,
+    /// which has its second child at `second`.
+    fn child_importances(
+        &self,
+        index: usize,
+        second: usize,
+        p: &Point3f,
+        n: Option<&Vec3f>,
+    ) -> [f64; 2] {
+        [
+            self.nodes[index + 1].bounds.importance(p, n),
+            self.nodes[second].bounds.importance(p, n),
+        ]
+    }
+}
+
+impl<'a> LightSampler for BvhLightSampler<'a> {
+    fn sample(&self, reference: &Interaction, u: f64) -> Option<SampledLight<'_>> {
+        let p_bounded = self.bounded_probability();
+        let p_infinite = 1.0 - p_bounded;
+        if u < p_infinite && !self.infinite.is_empty() {
+            let count = self.infinite.len();
+            let index = ((u / p_infinite * count as f64) as usize).min(count - 1);
+            return Some(SampledLight {
+                light: self.lights[self.infinite[index]],
+                pmf: p_infinite / count as f64,
+            });
+        }
+        if self.nodes.is_empty() {
+            return None;
+        }
+
+        let (p, n) = (&reference.p, reference.n.as_ref());
+        let mut u = ((u - p_infinite) / p_bounded).min(ONE_MINUS_EPSILON);
+        let mut pmf = p_bounded;
+        let mut index = 0;
+        loop {
+            match self.nodes[index].kind {
+                NodeKind::Interior(second) => {
+                    let [first_importance, second_importance] =
+                        self.child_importances(index, second, p, n);
+                    if first_importance == 0.0 && second_importance == 0.0 {
+                        return None;
+                    }
+                    let p_first = first_importance / (first_importance + second_importance);
+                    if u < p_first {
+                        index += 1;
+                        u /= p_first;
+                        pmf *= p_first;
+                    } else {
+                        index = second;
+                        u = (u - p_first) / (1.0 - p_first);
+                        pmf *= 1.0 - p_first;
+                    }
+                    u = u.min(ONE_MINUS_EPSILON);
+                }
+                NodeKind::Leaf(light) => {
+                    // Lone lights still have to be able to reach the reference
+                    if index > 0 || self.nodes[index].bounds.importance(p, n) > 0.0 {
+                        return Some(SampledLight {
+                            light: self.lights[light],
+                            pmf,
+                        });
+                    }
+                    return None;
+                }
+            }
+        }
+    }
+
+    fn pmf(&self, reference: &Interaction, light: &dyn Light) -> f64 {
+        let Some(&trail) = self.trails.get(&address(light)) else {
+            let is_infinite = self
+                .infinite
+                .iter()
+                .any(|&index| address(self.lights[index]) == address(light));
+            return if is_infinite {
+                (1.0 - self.bounded_probability()) / self.infinite.len() as f64
+            } else {
+                0.0
+            };
+        };
+
+        let (p, n) = (&reference.p, reference.n.as_ref());
+        let mut trail = trail;
+        let mut pmf = self.bounded_probability();
+        let mut index = 0;
+        while let NodeKind::Interior(second) = self.nodes[index].kind {
+            let importances = self.child_importances(index, second, p, n);
+            let total = importances[0] + importances[1];
+            if total == 0.0 {
+                return 0.0;
+            }
+            let branch = (trail & 1) as usize;
+            pmf *= importances[branch] / total;
+            index = if branch == 0 { index + 1 } else { second };
+            trail >>= 1;
+        }
+        if index == 0 && self.nodes[index].bounds.importance(p, n) == 0.0 {
+            return 0.0;
+        }
+        pmf
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::light_sampler::same_light;
+    use crate::core::vector::Color3f;
+    use crate::lights::diffuse_area_light::DiffuseAreaLight;
+    use crate::lights::distant_light::DistantLight;
+    use crate::lights::point_light::PointLight;
+    use crate::primitives::triangle::Triangle;
+    use crate::textures::constant_texture::ConstantTexture;
+
+    fn reference(p: Point3f) -> Interaction<'static> {
+        let up = Vec3f::new(0.0, 0.0, 1.0);
+        Interaction::new_on_surface(p, 1.0, up, up)
+    }
+
+    /// Checks that samples and pmfs agree, and that the pmfs sum to one over `lights`.
+    fn check_consistency(
+        sampler: &BvhLightSampler,
+        lights: &[&dyn Light],
+        reference: &Interaction,
+    ) {
+        let total: f64 = lights
+            .iter()
+            .map(|light| sampler.pmf(reference, *light))
+            .sum();
+        assert!((total - 1.0).abs() < 1e-9, "{}", total);
+
+        let n = 1000;
+        for i in 0..n {
+            let u = (i as f64 + 0.5) / n as f64;
+            let sampled = sampler.sample(reference, u).unwrap();
+            let pmf = sampler.pmf(reference, sampled.light);
+            assert!(
+                (sampled.pmf - pmf).abs() < 1e-9 * pmf,
+                "{} {}",
+                sampled.pmf,
+                pmf
+            );
+        }
+    }
+
+    #[test]
+    fn favors_nearby_lights() {
+        // A grid of equal point lights high above, and one close to the ground
+        let white = Color3f::new(1.0, 1.0, 1.0);
+        let mut point_lights: Vec<PointLight> = (0..64)
+            .map(|i| {
+                let (x, y) = ((i % 8) as f64 * 10.0, (i / 8) as f64 * 10.0);
+                PointLight::new(Point3f::new(x, y, 50.0), white)
+            })
+            .collect();
+        point_lights.push(PointLight::new(Point3f::new(30.0, 30.0, 1.0), white));
+        let lights: Vec<&dyn Light> = point_lights.iter().map(|l| l as &dyn Light).collect();
+        let sampler = BvhLightSampler::new(&lights);
+
+        let reference = reference(Point3f::new(30.0, 30.0, 0.0));
+        check_consistency(&sampler, &lights, &reference);
+        let nearby = sampler.pmf(&reference, lights[64]);
+        assert!(nearby > 0.5, "{}", nearby);
+        assert!(sampler.pmf(&reference, lights[0]) < 0.01);
+    }
+
+    #[test]
+    fn lopsided_lights() {
+        // Spread so unevenly that cost based splits only peel a few lights off at a time
+        let white = Color3f::new(1.0, 1.0, 1.0);
+        let point_lights: Vec<PointLight> = (0..300)
+            .map(|i| PointLight::new(Point3f::new(2.0_f64.powi(i), 0.0, 0.0), white))
+            .collect();
+        let lights: Vec<&dyn Light> = point_lights.iter().map(|l| l as &dyn Light).collect();
+        let sampler = BvhLightSampler::new(&lights);
+
+        let reference = reference(Point3f::new(0.0, 0.0, -1.0));
+        check_consistency(&sampler, &lights, &reference);
+        assert!(sampler.pmf(&reference, lights[0]) > sampler.pmf(&reference, lights[299]));
+    }
+
+    #[test]
+    fn respects_orientation() {
+        // Two triangles facing opposite ways, and the sky
+        let radiance = ConstantTexture::new(Color3f::new(1.0, 1.0, 1.0));
+        let up_facing = Triangle::new(
+            Point3f::new(0.0, 0.0, 1.0),
+            Point3f::new(1.0, 0.0, 1.0),
+            Point3f::new(0.0, 1.0, 1.0),
+        );
+        let down_facing = Triangle::new(
+            Point3f::new(0.0, 0.0, 2.0),
+            Point3f::new(0.0, 1.0, 2.0),
+            Point3f::new(1.0, 0.0, 2.0),
+        );
+        let up_light = DiffuseAreaLight::new(&up_facing, &radiance);
+        let down_light = DiffuseAreaLight::new(&down_facing, &radiance);
+        let sun = DistantLight::new(Vec3f::new(0.0, 0.0, -1.0), Color3f::new(1.0, 1.0, 1.0));
+        let lights = vec![
+            &up_light as &dyn Light,
+            &sun as &dyn Light,
+            &down_light as &dyn Light,
+        ];
+        let sampler = BvhLightSampler::new(&lights);
+
+        // From below, only the light facing down shines on the reference
+        let below = reference(Point3f::new(0.2, 0.2, 0.0));
+        check_consistency(&sampler, &lights, &below);
+        assert_eq!(sampler.pmf(&below, &sun), 0.5);
+        assert_eq!(sampler.pmf(&below, &down_light), 0.5);
+        assert_eq!(sampler.pmf(&below, &up_light), 0.0);
+        let sampled = sampler.sample(&below, 0.7).unwrap();
+        assert!(same_light(sampled.light, &down_light));
+
+        // Between them, both are seen
+        let between = reference(Point3f::new(0.2, 0.2, 1.5));
+        check_consistency(&sampler, &lights, &between);
+        assert!(sampler.pmf(&between, &up_light) > 0.0);
+    }
+
+    #[test]
+    fn lone_light_out_of_reach() {
+        let radiance = ConstantTexture::new(Color3f::new(1.0, 1.0, 1.0));
+        let triangle = Triangle::new(
+            Point3f::new(0.0, 0.0, 1.0),
+            Point3f::new(1.0, 0.0, 1.0),
+            Point3f::new(0.0, 1.0, 1.0),
+        );
+        let light = DiffuseAreaLight::new(&triangle, &radiance);
+        let lights = vec![&light as &dyn Light];
+        let sampler = BvhLightSampler::new(&lights);
+
+        let above = reference(Point3f::new(0.2, 0.2, 2.0));
+        assert_eq!(sampler.sample(&above, 0.5).unwrap().pmf, 1.0);
+        let below = reference(Point3f::new(0.2, 0.2, 0.0));
+        assert!(sampler.sample(&below, 0.5).is_none());
+        assert_eq!(sampler.pmf(&below, &light), 0.0);
+        assert!(BvhLightSampler::new(&[]).sample(&below, 0.5).is_none());
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
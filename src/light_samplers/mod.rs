@@ -0,0 +1,3 @@
+pub mod bvh_light_sampler;
+pub mod power_light_sampler;
+pub mod uniform_light_sampler;
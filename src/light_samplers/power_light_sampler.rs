@@ -0,0 +1,107 @@
+use crate::core::interaction::Interaction;
+use crate::core::light::Light;
+use crate::core::light_sampler::{same_light, LightSampler, SampledLight};
+use crate::math::distribution::Distribution1D;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A [LightSampler] picking lights in proportion to their total power,
+/// wherever the shading point is.
+pub struct PowerLightSampler<'a> {
+    lights: &'a [&'a dyn Light],
+    distribution: Option<Distribution1D>,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<'a> PowerLightSampler<'a> {
+    /// Lights whose power depends on the scene should be preprocessed first.
+    /// Picks uniformly if none of the lights emit anything.
+    pub fn new(lights: &'a [&'a dyn Light]) -> Self {
+        if lights.is_empty() {
+            return Self {
+                lights,
+                distribution: None,
+            };
+        }
+        let mut powers: Vec<f64> = lights.iter().map(|light| light.power().average()).collect();
+        if powers.iter().all(|&power| power == 0.0) {
+            powers.fill(1.0);
+        }
+        Self {
+            lights,
+            distribution: Some(Distribution1D::new(&powers)),
+        }
+    }
+
+    /// The probability of picking the light at `index`.
+    fn pmf_at(distribution: &Distribution1D, index: usize) -> f64 {
+        let count = distribution.count() as f64;
+        distribution.pdf((index as f64 + 0.5) / count) / count
+    }
+}
+
+impl<'a> LightSampler for PowerLightSampler<'a> {
+    fn sample(&self, _reference: &Interaction, u: f64) -> Option<SampledLight<'_>> {
+        let distribution = self.distribution.as_ref()?;
+        let (_, _, index) = distribution.sample_continuous(u);
+        Some(SampledLight {
+            light: self.lights[index],
+            pmf: Self::pmf_at(distribution, index),
+        })
+    }
+
+    fn pmf(&self, _reference: &Interaction, light: &dyn Light) -> f64 {
+        let Some(distribution) = &self.distribution else {
+            return 0.0;
+        };
+        self.lights
+            .iter()
+            .position(|other| same_light(*other, light))
+            .map_or(0.0, |index| Self::pmf_at(distribution, index))
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::{Color3f, Point3f, Vec3f};
+    use crate::lights::point_light::PointLight;
+
+    #[test]
+    fn picks_by_power() {
+        let dim = PointLight::new(Point3f::new(0.0, 0.0, 1.0), Color3f::new(1.0, 1.0, 1.0));
+        let bright = PointLight::new(Point3f::new(0.0, 0.0, 9.0), Color3f::new(3.0, 3.0, 3.0));
+        let lights = vec![&dim as &dyn Light, &bright as &dyn Light];
+        let sampler = PowerLightSampler::new(&lights);
+        let up = Vec3f::new(0.0, 0.0, 1.0);
+        let reference = Interaction::new_on_surface(Point3f::default(), 1.0, up, up);
+
+        let first = sampler.sample(&reference, 0.2).unwrap();
+        assert!(same_light(first.light, &dim));
+        assert!((first.pmf - 0.25).abs() < 1e-12);
+        let second = sampler.sample(&reference, 0.3).unwrap();
+        assert!(same_light(second.light, &bright));
+        assert!((sampler.pmf(&reference, &bright) - 0.75).abs() < 1e-12);
+
+        // Black lights fall back to uniform picks
+        let black = PointLight::new(Point3f::default(), Color3f::default());
+        let lights = vec![&black as &dyn Light, &black as &dyn Light];
+        let sampler = PowerLightSampler::new(&lights);
+        assert_eq!(sampler.sample(&reference, 0.9).unwrap().pmf, 0.5);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
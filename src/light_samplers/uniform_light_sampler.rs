@@ -0,0 +1,88 @@
+use crate::core::interaction::Interaction;
+use crate::core::light::Light;
+use crate::core::light_sampler::{same_light, LightSampler, SampledLight};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A [LightSampler] picking every light with the same probability,
+/// wherever the shading point is.
+pub struct UniformLightSampler<'a> {
+    lights: &'a [&'a dyn Light],
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<'a> UniformLightSampler<'a> {
+    pub fn new(lights: &'a [&'a dyn Light]) -> Self {
+        Self { lights }
+    }
+}
+
+impl<'a> LightSampler for UniformLightSampler<'a> {
+    fn sample(&self, _reference: &Interaction, u: f64) -> Option<SampledLight<'_>> {
+        if self.lights.is_empty() {
+            return None;
+        }
+        let count = self.lights.len();
+        let index = ((u * count as f64) as usize).min(count - 1);
+        Some(SampledLight {
+            light: self.lights[index],
+            pmf: 1.0 / count as f64,
+        })
+    }
+
+    fn pmf(&self, _reference: &Interaction, light: &dyn Light) -> f64 {
+        if self.lights.iter().any(|other| same_light(*other, light)) {
+            1.0 / self.lights.len() as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::{Color3f, Point3f, Vec3f};
+    use crate::lights::point_light::PointLight;
+
+    #[test]
+    fn picks_evenly() {
+        let white = Color3f::new(1.0, 1.0, 1.0);
+        let near = PointLight::new(Point3f::new(0.0, 0.0, 1.0), white);
+        let far = PointLight::new(Point3f::new(0.0, 0.0, 100.0), white);
+        let lights = vec![&near as &dyn Light, &far as &dyn Light];
+        let sampler = UniformLightSampler::new(&lights);
+        let up = Vec3f::new(0.0, 0.0, 1.0);
+        let reference = Interaction::new_on_surface(Point3f::default(), 1.0, up, up);
+
+        let first = sampler.sample(&reference, 0.3).unwrap();
+        assert!(same_light(first.light, &near));
+        assert_eq!(first.pmf, 0.5);
+        assert!(same_light(
+            sampler.sample(&reference, 0.7).unwrap().light,
+            &far
+        ));
+        assert_eq!(sampler.pmf(&reference, &far), 0.5);
+
+        let stranger = PointLight::new(Point3f::default(), white);
+        assert_eq!(sampler.pmf(&reference, &stranger), 0.0);
+        assert!(UniformLightSampler::new(&[])
+            .sample(&reference, 0.5)
+            .is_none());
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,214 @@
+use std::f64::consts::PI;
+
+use crate::core::interaction::Interaction;
+use crate::core::light::{AreaLight, Light, LightBounds, LightSample};
+use crate::core::shape::Shape;
+use crate::core::texture::Texture;
+use crate::core::vector::{Color3f, Point2f, Vec3f};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Makes a [Shape] glow evenly in all directions from the side its normal faces,
+/// or both sides if asked to.
+pub struct DiffuseAreaLight<'a> {
+    shape: &'a dyn Shape,
+    radiance: &'a dyn Texture<Color3f>,
+    two_sided: bool,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// How many points per side of a grid [Light::power] averages textured radiance over.
+const POWER_GRID: usize = 16;
+
+impl<'a> DiffuseAreaLight<'a> {
+    /// `radiance` is looked up at each point of the surface,
+    /// a [ConstantTexture](crate::textures::constant_texture::ConstantTexture) for plain lights.
+    pub fn new(shape: &'a dyn Shape, radiance: &'a dyn Texture<Color3f>) -> Self {
+        Self {
+            shape,
+            radiance,
+            two_sided: false,
+        }
+    }
+
+    pub fn with_two_sided(self, two_sided: bool) -> Self {
+        Self { two_sided, ..self }
+    }
+}
+
+impl<'a> Light for DiffuseAreaLight<'a> {
+    fn sample_li(&self, reference: &Interaction, u: &Point2f) -> Option<LightSample> {
+        let sample = self.shape.sample(&reference.p, u)?;
+        if sample.pdf == 0.0 || (sample.interaction.p - reference.p).length_sq() == 0.0 {
+            return None;
+        }
+        let light = LightSample::from_point(
+            Color3f::default(),
+            sample.pdf,
+            &reference.p,
+            &sample.interaction.p,
+        );
+        let li = self.l(&sample.interaction, &-light.wi);
+        if li.is_black() {
+            return None;
+        }
+        Some(LightSample { li, ..light })
+    }
+
+    fn pdf_li(&self, reference: &Interaction, wi: &Vec3f) -> f64 {
+        self.shape.pdf(&reference.p, wi)
+    }
+
+    /// Averages the radiance over a grid of points on the surface.
+    fn power(&self) -> Color3f {
+        let mut radiance = Color3f::default();
+        for i in 0..POWER_GRID {
+            for j in 0..POWER_GRID {
+                let u = Point2f::new(
+                    (i as f64 + 0.5) / POWER_GRID as f64,
+                    (j as f64 + 0.5) / POWER_GRID as f64,
+                );
+                if let Some(sample) = self.shape.sample_area(&u) {
+                    radiance += self.radiance.evaluate(&sample.interaction);
+                }
+            }
+        }
+        let radiance = radiance / (POWER_GRID * POWER_GRID) as f64;
+        let sides = if self.two_sided { 2.0 } else { 1.0 };
+        radiance * (PI * sides * self.shape.area())
+    }
+
+    fn is_delta(&self) -> bool {
+        false
+    }
+
+    fn bounds(&self) -> Option<LightBounds> {
+        Some(LightBounds {
+            bounds: self.shape.bounds(),
+            normals: self.shape.normal_bounds(),
+            cos_theta_e: 0.0,
+            // Each point glows into a hemisphere, which the bounds' cone term accounts for
+            phi: self.power().average() / PI,
+            two_sided: self.two_sided,
+        })
+    }
+}
+
+impl<'a> AreaLight for DiffuseAreaLight<'a> {
+    fn l(&self, interaction: &Interaction, w: &Vec3f) -> Color3f {
+        let Some(n) = interaction.n else {
+            return Color3f::default();
+        };
+        if !self.two_sided && n.dot(w) < 0.0 {
+            return Color3f::default();
+        }
+        self.radiance.evaluate(interaction)
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::primitive::Primitive;
+    use crate::core::ray::Ray;
+    use crate::core::vector::Point3f;
+    use crate::materials::lambertian_material::LambertianMaterial;
+    use crate::primitives::geometric_primitive::GeometricPrimitive;
+    use crate::primitives::sphere::Sphere;
+    use crate::primitives::triangle::Triangle;
+    use crate::textures::constant_texture::ConstantTexture;
+
+    /// Estimates the irradiance at `reference` from `light` over a stratified grid of samples.
+    fn irradiance(light: &dyn Light, reference: &Interaction) -> Color3f {
+        let n = 64;
+        let mut sum = Color3f::default();
+        for i in 0..n {
+            for j in 0..n {
+                let u = Point2f::new((i as f64 + 0.5) / n as f64, (j as f64 + 0.5) / n as f64);
+                if let Some(sample) = light.sample_li(reference, &u) {
+                    let cos_theta = sample.wi.dot(&reference.n.unwrap()).max(0.0);
+                    sum += sample.li * (cos_theta / sample.pdf);
+                }
+            }
+        }
+        sum / (n * n) as f64
+    }
+
+    #[test]
+    fn sphere_irradiance() {
+        let radiance = ConstantTexture::new(Color3f::new(2.0, 2.0, 2.0));
+        let sphere = Sphere::new(Point3f::new(0.0, 0.0, 4.0), 1.0);
+        let light = DiffuseAreaLight::new(&sphere, &radiance);
+
+        // A sphere fully above the horizon gives pi L sin^2(theta_max)
+        let up = Vec3f::new(0.0, 0.0, 1.0);
+        let reference = Interaction::new_on_surface(Point3f::default(), 1.0, up, up);
+        let expected = PI * 2.0 / 16.0;
+        assert!((irradiance(&light, &reference).x - expected).abs() < 1e-3);
+
+        assert!((light.power().x - 2.0 * PI * 4.0 * PI).abs() < 1e-9);
+    }
+
+    #[test]
+    fn triangle_irradiance() {
+        // An octant of the unit sphere's directions, seen from the origin
+        let radiance = ConstantTexture::new(Color3f::new(1.0, 1.0, 1.0));
+        let triangle = Triangle::new(
+            Point3f::new(1.0, 0.0, 0.0),
+            Point3f::new(0.0, 1.0, 0.0),
+            Point3f::new(0.0, 0.0, 1.0),
+        );
+        let light = DiffuseAreaLight::new(&triangle, &radiance);
+        let up = Vec3f::new(0.0, 0.0, 1.0);
+        let reference = Interaction::new_on_surface(Point3f::default(), 1.0, up, up);
+
+        // The triangle faces away from the origin, which only sees it glow when two sided
+        assert!(irradiance(&light, &reference).is_black());
+        let light = light.with_two_sided(true);
+
+        // L times the projected solid angle of the octant, a quarter of the hemisphere's pi
+        assert!((irradiance(&light, &reference).x - PI / 4.0).abs() < 1e-3);
+        let wi = Vec3f::new(1.0, 1.0, 1.0);
+        assert!((light.pdf_li(&reference, &wi) - 2.0 / PI).abs() < 1e-9);
+        assert_eq!(light.pdf_li(&reference, &-wi), 0.0);
+    }
+
+    #[test]
+    fn visible_to_camera_rays() {
+        let radiance = ConstantTexture::new(Color3f::new(3.0, 2.0, 1.0));
+        let albedo = ConstantTexture::new(Color3f::default());
+        let material = LambertianMaterial::new(&albedo);
+        let sphere = Sphere::new(Point3f::default(), 1.0);
+        let light = DiffuseAreaLight::new(&sphere, &radiance);
+        let primitive = GeometricPrimitive::new(&sphere, &material).with_area_light(&light);
+
+        let o = Point3f::new(0.0, 0.0, 5.0);
+        let hit = primitive
+            .test(&Ray::new(o, Vec3f::new(0.0, 0.0, -1.0), 0.0, 100.0))
+            .unwrap();
+        assert_eq!(hit.le(&hit.wo), Color3f::new(3.0, 2.0, 1.0));
+        // The inside of the sphere is dark
+        assert!(hit.le(&-hit.wo).is_black());
+        // As is the bare shape
+        assert!(sphere
+            .test(&Ray::new(o, Vec3f::new(0.0, 0.0, -1.0), 0.0, 100.0))
+            .unwrap()
+            .le(&hit.wo)
+            .is_black());
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,98 @@
+use std::f64::consts::PI;
+
+use crate::core::bounds::Bounds3f;
+use crate::core::interaction::Interaction;
+use crate::core::light::{Light, LightSample};
+use crate::core::vector::{Color3f, Point2f, Vec3f};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A [Light] infinitely far away, shining along a single direction onto the whole scene,
+/// like the sun.
+pub struct DistantLight {
+    direction: Vec3f,
+    radiance: Color3f,
+    scene_radius: f64,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl DistantLight {
+    /// `direction` is the way the light travels.
+    pub fn new(direction: Vec3f, radiance: Color3f) -> Self {
+        Self {
+            direction: direction.normalize(),
+            radiance,
+            scene_radius: 0.0,
+        }
+    }
+}
+
+impl Light for DistantLight {
+    fn sample_li(&self, reference: &Interaction, _u: &Point2f) -> Option<LightSample> {
+        Some(LightSample::from_direction(
+            self.radiance,
+            1.0,
+            &reference.p,
+            &-self.direction,
+        ))
+    }
+
+    fn pdf_li(&self, _reference: &Interaction, _wi: &Vec3f) -> f64 {
+        0.0
+    }
+
+    /// Only what falls onto the scene counts, which is unknown until [Light::preprocess].
+    fn power(&self) -> Color3f {
+        self.radiance * (PI * self.scene_radius * self.scene_radius)
+    }
+
+    fn is_delta(&self) -> bool {
+        true
+    }
+
+    fn preprocess(&mut self, scene_bounds: &Bounds3f) {
+        self.scene_radius = scene_bounds.bounding_sphere().1;
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::Point3f;
+
+    #[test]
+    fn shines_along_direction() {
+        let radiance = Color3f::new(1.0, 2.0, 3.0);
+        let mut light = DistantLight::new(Vec3f::new(0.0, -2.0, 0.0), radiance);
+        let n = Vec3f::new(0.0, 1.0, 0.0);
+
+        // Distance doesn't matter
+        for y in [0.0, -100.0] {
+            let reference = Interaction::new_on_surface(Point3f::new(0.0, y, 0.0), 1.0, n, n);
+            let sample = light.sample_li(&reference, &Point2f::default()).unwrap();
+            assert_eq!(sample.li, radiance);
+            assert_eq!(sample.wi, n);
+            assert_eq!(sample.visibility.max_t(), f64::INFINITY);
+        }
+
+        assert!(light.power().is_black());
+        let bounds = Bounds3f::new(Point3f::new(-1.0, -1.0, -1.0), Point3f::new(1.0, 1.0, 1.0));
+        light.preprocess(&bounds);
+        assert!((light.power() - radiance * (3.0 * PI)).length() < 1e-12);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
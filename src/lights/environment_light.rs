@@ -0,0 +1,245 @@
+use std::f64::consts::PI;
+
+use crate::core::bounds::Bounds3f;
+use crate::core::frame::Frame;
+use crate::core::image::{Image, WrapMode};
+use crate::core::interaction::Interaction;
+use crate::core::light::{Light, LightSample};
+use crate::core::ray::Ray;
+use crate::core::vector::{Color3f, Point2f, Vec3f};
+use crate::math::distribution::Distribution2D;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A [Light] infinitely far away in every direction, with radiance read from an
+/// equirectangular image such as a Radiance HDR photograph of the sky.
+/// Directions are sampled in proportion to the brightness of the image,
+/// so small bright features like the sun are found quickly.
+pub struct EnvironmentLight {
+    image: Image<Color3f>,
+    distribution: Distribution2D,
+
+    /// Takes directions from the map's space, with z up, to the world
+    rotation: Frame,
+
+    scene_radius: f64,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl EnvironmentLight {
+    /// The top row of `image` lies around +z, and the left edge along +x,
+    /// with longitudes increasing towards +y to the right.
+    pub fn new(image: Image<Color3f>) -> Self {
+        let (width, height) = (image.width(), image.height());
+
+        // Rows near the poles cover less solid angle
+        let mut func = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let sin_theta = (PI * (y as f64 + 0.5) / height as f64).sin();
+            for x in 0..width {
+                func.push(image.get(x as i64, y as i64, WrapMode::Clamp).average() * sin_theta);
+            }
+        }
+
+        Self {
+            image,
+            distribution: Distribution2D::new(&func, width, height),
+            rotation: Frame::new(
+                Vec3f::new(1.0, 0.0, 0.0),
+                Vec3f::new(0.0, 1.0, 0.0),
+                Vec3f::new(0.0, 0.0, 1.0),
+            ),
+            scene_radius: 0.0,
+        }
+    }
+
+    /// Turns the map by `rotation`, see [Frame::from_rotation].
+    /// Scenes with y up want the map turned -90 degrees around x.
+    pub fn with_rotation(self, rotation: Frame) -> Self {
+        Self { rotation, ..self }
+    }
+
+    /// The radiance of the texel at image coordinates `st`.
+    fn lookup(&self, st: &Point2f) -> Color3f {
+        let x = (st.x * self.image.width() as f64).floor() as i64;
+        let y = (st.y * self.image.height() as f64).floor() as i64;
+        self.image.get(x, y, WrapMode::Clamp)
+    }
+
+    /// The image coordinates and sine of the polar angle of the world direction `w`.
+    fn to_image(&self, w: &Vec3f) -> (Point2f, f64) {
+        let w = self.rotation.world_to_local(&w.normalize());
+        let theta = w.z.clamp(-1.0, 1.0).acos();
+        let phi = w.y.atan2(w.x).rem_euclid(2.0 * PI);
+        (Point2f::new(phi / (2.0 * PI), theta / PI), theta.sin())
+    }
+}
+
+impl Light for EnvironmentLight {
+    fn sample_li(&self, reference: &Interaction, u: &Point2f) -> Option<LightSample> {
+        let (st, map_pdf) = self.distribution.sample_continuous(u);
+        if map_pdf == 0.0 {
+            return None;
+        }
+
+        let (theta, phi) = (st.y * PI, st.x * 2.0 * PI);
+        let sin_theta = theta.sin();
+        if sin_theta == 0.0 {
+            return None;
+        }
+        let w = Vec3f::new(sin_theta * phi.cos(), sin_theta * phi.sin(), theta.cos());
+        let wi = self.rotation.local_to_world(&w);
+
+        // The map covers 2pi by pi radians, squeezed by sin(theta) towards the poles
+        let pdf = map_pdf / (2.0 * PI * PI * sin_theta);
+        Some(LightSample::from_direction(
+            self.lookup(&st),
+            pdf,
+            &reference.p,
+            &wi,
+        ))
+    }
+
+    fn pdf_li(&self, _reference: &Interaction, wi: &Vec3f) -> f64 {
+        let (st, sin_theta) = self.to_image(wi);
+        if sin_theta == 0.0 {
+            return 0.0;
+        }
+        self.distribution.pdf(&st) / (2.0 * PI * PI * sin_theta)
+    }
+
+    /// What falls onto the scene's bounding sphere, known after [Light::preprocess].
+    fn power(&self) -> Color3f {
+        let (width, height) = (self.image.width(), self.image.height());
+        let mut sum = Color3f::default();
+        let mut weight = 0.0;
+        for y in 0..height {
+            let sin_theta = (PI * (y as f64 + 0.5) / height as f64).sin();
+            for x in 0..width {
+                sum += self.image.get(x as i64, y as i64, WrapMode::Clamp) * sin_theta;
+                weight += sin_theta;
+            }
+        }
+        let average = sum / weight;
+        average * (4.0 * PI * PI * self.scene_radius * self.scene_radius)
+    }
+
+    fn is_delta(&self) -> bool {
+        false
+    }
+
+    fn le(&self, ray: &Ray) -> Color3f {
+        self.lookup(&self.to_image(&ray.d).0)
+    }
+
+    fn preprocess(&mut self, scene_bounds: &Bounds3f) {
+        self.scene_radius = scene_bounds.bounding_sphere().1;
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::Point3f;
+
+    /// A dim sky with one bright texel.
+    fn sky() -> Image<Color3f> {
+        let (width, height) = (16, 8);
+        let mut texels = vec![Color3f::new(0.1, 0.1, 0.1); width * height];
+        texels[2 * width + 5] = Color3f::new(100.0, 90.0, 80.0);
+        Image::new(width, height, texels)
+    }
+
+    fn reference() -> Interaction<'static> {
+        let up = Vec3f::new(0.0, 0.0, 1.0);
+        Interaction::new_on_surface(Point3f::default(), 1.0, up, up)
+    }
+
+    #[test]
+    fn samples_match_lookups() {
+        let light = EnvironmentLight::new(sky())
+            .with_rotation(Frame::from_rotation(Vec3f::new(1.0, 1.0, 0.0), 30.0));
+        let reference = reference();
+
+        let n = 64;
+        let mut bright = 0;
+        for i in 0..n {
+            for j in 0..n {
+                let u = Point2f::new((i as f64 + 0.5) / n as f64, (j as f64 + 0.5) / n as f64);
+                let sample = light.sample_li(&reference, &u).unwrap();
+                let ray = Ray::new(reference.p, sample.wi, 0.0, f64::INFINITY);
+                assert_eq!(sample.li, light.le(&ray));
+                let pdf = light.pdf_li(&reference, &sample.wi);
+                assert!(
+                    (sample.pdf - pdf).abs() < 1e-6 * pdf,
+                    "{} {}",
+                    sample.pdf,
+                    pdf
+                );
+                if sample.li.x > 1.0 {
+                    bright += 1;
+                }
+            }
+        }
+        // The bright texel gets most of the samples
+        assert!(bright > n * n / 2, "{}", bright);
+    }
+
+    #[test]
+    fn estimates_irradiance() {
+        // A uniform sky gives pi L on any surface
+        let gray = Color3f::new(0.5, 0.5, 0.5);
+        let light = EnvironmentLight::new(Image::new(32, 16, vec![gray; 512]));
+        let reference = reference();
+
+        let n = 128;
+        let mut irradiance = 0.0;
+        for i in 0..n {
+            for j in 0..n {
+                let u = Point2f::new((i as f64 + 0.5) / n as f64, (j as f64 + 0.5) / n as f64);
+                let sample = light.sample_li(&reference, &u).unwrap();
+                irradiance += sample.li.x * sample.wi.z.max(0.0) / sample.pdf;
+            }
+        }
+        irradiance /= (n * n) as f64;
+        assert!((irradiance - PI * 0.5).abs() < 1e-2, "{}", irradiance);
+    }
+
+    #[test]
+    fn orientation() {
+        let light = EnvironmentLight::new(sky());
+        let le = |d: Vec3f| light.le(&Ray::new(Point3f::default(), d, 0.0, f64::INFINITY));
+        // The bright texel is in the third row down and the sixth column across
+        let (theta, phi) = (2.5 / 8.0 * PI, 5.5 / 16.0 * 2.0 * PI);
+        let d = Vec3f::new(
+            theta.sin() * phi.cos(),
+            theta.sin() * phi.sin(),
+            theta.cos(),
+        );
+        assert_eq!(le(d), Color3f::new(100.0, 90.0, 80.0));
+        assert_eq!(le(Vec3f::new(0.0, 0.0, -1.0)), Color3f::new(0.1, 0.1, 0.1));
+
+        // Tilting the map to y up
+        let tilted = light.with_rotation(Frame::from_rotation(Vec3f::new(1.0, 0.0, 0.0), -90.0));
+        let d = Vec3f::new(d.x, d.z, -d.y);
+        assert_eq!(
+            tilted.le(&Ray::new(Point3f::default(), d, 0.0, f64::INFINITY)),
+            Color3f::new(100.0, 90.0, 80.0)
+        );
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
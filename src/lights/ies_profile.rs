@@ -0,0 +1,391 @@
+use std::f64::consts::PI;
+use std::fmt;
+use std::path::Path;
+
+use crate::core::vector::Vec3f;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// The measured distribution of light from a luminaire, as published in IES LM-63 files
+/// by lighting manufacturers. Only type C photometry, by far the most common, is supported.
+///
+/// Directions are given in the profile's own frame, with z along the luminaire's axis
+/// at 0 degrees vertical, usually straight down, and x at 0 degrees horizontal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IesProfile {
+    /// Ascending, in degrees from the axis
+    vertical_angles: Vec<f64>,
+
+    /// Ascending, in degrees counterclockwise from x around the axis
+    horizontal_angles: Vec<f64>,
+
+    /// The intensity at every vertical angle for each horizontal angle in turn,
+    /// in watts per steradian
+    intensities: Vec<f64>,
+}
+
+#[derive(Debug)]
+pub enum IesError {
+    Io(std::io::Error),
+
+    /// The file is damaged, or uses a feature that isn't supported
+    Format(String),
+}
+
+/// The luminous efficacy defining the candela, converting photometric units to radiometric ones.
+pub const LUMENS_PER_WATT: f64 = 683.0;
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// How many steps along each angle [IesProfile::integrate] takes.
+const INTEGRATION_STEPS: usize = 256;
+
+fn format_error(message: &str) -> IesError {
+    IesError::Format(message.to_string())
+}
+
+impl IesProfile {
+    pub fn read(path: &Path) -> Result<Self, IesError> {
+        // Headers are often Latin-1, which only matters to keywords we skip
+        let bytes = std::fs::read(path)?;
+        Self::parse(&String::from_utf8_lossy(&bytes))
+    }
+
+    /// Parses the text of an LM-63 file from any of its 1986 to 2019 revisions.
+    pub fn parse(text: &str) -> Result<Self, IesError> {
+        // Keywords come before the tilt line, numbers after it
+        let mut lines = text.lines();
+        let tilt = loop {
+            let Some(line) = lines.next() else {
+                return Err(format_error("missing TILT line"));
+            };
+            if let Some(tilt) = line.trim().strip_prefix("TILT=") {
+                break tilt.trim();
+            }
+        };
+        let rest: Vec<&str> = lines.collect();
+        let mut numbers = rest
+            .iter()
+            .flat_map(|line| line.split(|c: char| c.is_whitespace() || c == ','))
+            .filter(|token| !token.is_empty())
+            .map(|token| token.parse::<f64>());
+        let mut next = || match numbers.next() {
+            Some(Ok(number)) => Ok(number),
+            Some(Err(_)) => Err(format_error("invalid number")),
+            None => Err(format_error("truncated data")),
+        };
+
+        match tilt {
+            "NONE" => {}
+            // Lamp tilt only matters to luminaires mounted at an angle, which we don't track
+            "INCLUDE" => {
+                let _geometry = next()?;
+                let count = next()? as usize;
+                for _ in 0..2 * count {
+                    next()?;
+                }
+            }
+            _ => return Err(format_error("only inline tilt data is supported")),
+        }
+
+        let _lamp_count = next()?;
+        let _lumens_per_lamp = next()?;
+        let candela_multiplier = next()?;
+        let vertical_count = next()? as usize;
+        let horizontal_count = next()? as usize;
+        let photometric_type = next()?;
+        let _units = next()?;
+        let _dimensions = [next()?, next()?, next()?];
+        let ballast_factor = next()?;
+        let ballast_lamp_factor = next()?;
+        let _input_watts = next()?;
+
+        if photometric_type != 1.0 {
+            return Err(format_error("only type C photometry is supported"));
+        }
+        if vertical_count == 0 || horizontal_count == 0 {
+            return Err(format_error("no measurements"));
+        }
+
+        let vertical_angles = (0..vertical_count)
+            .map(|_| next())
+            .collect::<Result<Vec<_>, _>>()?;
+        let horizontal_angles = (0..horizontal_count)
+            .map(|_| next())
+            .collect::<Result<Vec<_>, _>>()?;
+        let is_ascending = |angles: &[f64]| angles.windows(2).all(|pair| pair[0] < pair[1]);
+        if !is_ascending(&vertical_angles) || !is_ascending(&horizontal_angles) {
+            return Err(format_error("angles must be ascending"));
+        }
+
+        let scale = candela_multiplier * ballast_factor * ballast_lamp_factor / LUMENS_PER_WATT;
+        let intensities = (0..vertical_count * horizontal_count)
+            .map(|_| next().map(|candela| candela * scale))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            vertical_angles,
+            horizontal_angles,
+            intensities,
+        })
+    }
+
+    /// The intensity, in watts per steradian, along the normalized direction `w`
+    /// in the profile's frame. Interpolates linearly between the measured angles,
+    /// and is zero beyond the vertical ones.
+    pub fn intensity(&self, w: &Vec3f) -> f64 {
+        let vertical = w.z.clamp(-1.0, 1.0).acos().to_degrees();
+        let Some((v, dv)) = Self::locate(&self.vertical_angles, vertical) else {
+            return 0.0;
+        };
+        let horizontal = self.fold_horizontal(w.y.atan2(w.x).to_degrees().rem_euclid(360.0));
+        let (h, next_h, dh) = self.locate_horizontal(horizontal);
+
+        let at = |h: usize, v: usize| {
+            let v = v.min(self.vertical_angles.len() - 1);
+            self.intensities[h * self.vertical_angles.len() + v]
+        };
+        let along = |h: usize| at(h, v) * (1.0 - dv) + at(h, v + 1) * dv;
+        along(h) * (1.0 - dh) + along(next_h) * dh
+    }
+
+    /// The largest measured intensity, in watts per steradian.
+    pub fn max_intensity(&self) -> f64 {
+        self.intensities.iter().copied().fold(0.0, f64::max)
+    }
+
+    /// The intensity integrated over all directions, each weighted by `weight`
+    /// of the direction in the profile's frame. Gives the radiant power with a weight of 1.0.
+    pub fn integrate(&self, weight: impl Fn(&Vec3f) -> f64) -> f64 {
+        let d_theta = PI / INTEGRATION_STEPS as f64;
+        let d_phi = 2.0 * PI / INTEGRATION_STEPS as f64;
+        let mut sum = 0.0;
+        for i in 0..INTEGRATION_STEPS {
+            let theta = (i as f64 + 0.5) * d_theta;
+            let (sin_theta, cos_theta) = theta.sin_cos();
+            for j in 0..INTEGRATION_STEPS {
+                let phi = (j as f64 + 0.5) * d_phi;
+                let w = Vec3f::new(sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta);
+                sum += self.intensity(&w) * weight(&w) * sin_theta;
+            }
+        }
+        sum * d_theta * d_phi
+    }
+
+    /// Brings a horizontal angle in [0.0, 360.0) into the measured range,
+    /// using the symmetry implied by the range.
+    fn fold_horizontal(&self, angle: f64) -> f64 {
+        let first = self.horizontal_angles[0];
+        let last = *self.horizontal_angles.last().unwrap();
+        if first == 90.0 && last == 270.0 {
+            // Symmetric about the plane through 90 and 270 degrees
+            return if angle < 90.0 {
+                180.0 - angle
+            } else if angle > 270.0 {
+                540.0 - angle
+            } else {
+                angle
+            };
+        }
+        // Symmetric about the plane through 0 and 180 degrees, and then through 90 and 270
+        let angle = if last <= 180.0 && angle > 180.0 {
+            360.0 - angle
+        } else {
+            angle
+        };
+        if last <= 90.0 && angle > 90.0 {
+            180.0 - angle
+        } else {
+            angle
+        }
+    }
+
+    /// The horizontal measurements on either side of the folded `angle`, and how far it is
+    /// from the first towards the second. Sets covering the whole circle without repeating
+    /// their first angle at its end wrap around between their last and first angles.
+    fn locate_horizontal(&self, angle: f64) -> (usize, usize, f64) {
+        let angles = &self.horizontal_angles;
+        let last_index = angles.len() - 1;
+        let (first, last) = (angles[0], angles[last_index]);
+        let full_circle = last > 180.0 && !(first == 90.0 && last == 270.0);
+        if full_circle && (angle > last || angle < first) {
+            let gap = 360.0 + first - last;
+            let past_last = (angle - last).rem_euclid(360.0);
+            return (last_index, 0, past_last / gap);
+        }
+
+        match Self::locate(angles, angle) {
+            Some((h, dh)) => (h, (h + 1).min(last_index), dh),
+            // Rounding can leave folded angles just past the ends
+            None if angle < first => (0, 0, 0.0),
+            None => (last_index, last_index, 0.0),
+        }
+    }
+
+    /// The measurement just below `x` in the ascending `angles` and how far `x` is
+    /// towards the next one, [None] if `x` is out of their range.
+    fn locate(angles: &[f64], x: f64) -> Option<(usize, f64)> {
+        if angles.len() == 1 {
+            return Some((0, 0.0));
+        }
+        if x < angles[0] || x > *angles.last().unwrap() {
+            return None;
+        }
+        let i = (angles.partition_point(|&angle| angle <= x).max(1) - 1).min(angles.len() - 2);
+        Some((i, (x - angles[i]) / (angles[i + 1] - angles[i])))
+    }
+}
+
+impl fmt::Display for IesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IesError::Io(error) => write!(f, "could not read IES profile: {}", error),
+            IesError::Format(message) => write!(f, "malformed IES profile: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for IesError {}
+
+impl From<std::io::Error> for IesError {
+    fn from(error: std::io::Error) -> Self {
+        IesError::Io(error)
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A downlight measured at a few angles, brighter towards +x.
+    const DOWNLIGHT: &str = "IESNA:LM-63-2002
+[TEST] 12345
+[MANUFAC] Example Lighting
+[LUMCAT] DL-1
+TILT=NONE
+1 1000 2.0 3 3 1 2 0.1 0.1 0.0
+1.0 0.5 20
+0 45 90
+0 90 180
+500 300 0
+500 200 0
+500 100 0
+";
+
+    fn direction(vertical: f64, horizontal: f64) -> Vec3f {
+        let (theta, phi) = (vertical.to_radians(), horizontal.to_radians());
+        Vec3f::new(
+            theta.sin() * phi.cos(),
+            theta.sin() * phi.sin(),
+            theta.cos(),
+        )
+    }
+
+    fn candela(profile: &IesProfile, vertical: f64, horizontal: f64) -> f64 {
+        profile.intensity(&direction(vertical, horizontal)) * LUMENS_PER_WATT
+    }
+
+    #[test]
+    fn parses_and_converts() {
+        let profile = IesProfile::parse(DOWNLIGHT).unwrap();
+        assert_eq!(profile.vertical_angles, vec![0.0, 45.0, 90.0]);
+        assert_eq!(profile.horizontal_angles, vec![0.0, 90.0, 180.0]);
+
+        // Scaled by the multiplier and the ballast-lamp factor
+        assert!((candela(&profile, 0.0, 0.0) - 500.0).abs() < 1e-9);
+        assert!((profile.max_intensity() - 500.0 / LUMENS_PER_WATT).abs() < 1e-12);
+        assert!((candela(&profile, 45.0, 0.0) - 300.0).abs() < 1e-9);
+        assert!((candela(&profile, 45.0, 180.0) - 100.0).abs() < 1e-9);
+        // Nothing above the horizon
+        assert_eq!(candela(&profile, 120.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn interpolates() {
+        let profile = IesProfile::parse(DOWNLIGHT).unwrap();
+        assert!((candela(&profile, 22.5, 0.0) - 400.0).abs() < 1e-9);
+        assert!((candela(&profile, 45.0, 45.0) - 250.0).abs() < 1e-9);
+        assert!((candela(&profile, 22.5, 135.0) - 325.0).abs() < 1e-9);
+
+        // Mirrored across the plane through 0 and 180 degrees
+        assert!((candela(&profile, 45.0, 270.0) - 200.0).abs() < 1e-9);
+        assert!((candela(&profile, 45.0, 315.0) - 250.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn symmetries() {
+        // Axially symmetric, with tilt data and comma separated values
+        let round = IesProfile::parse(
+            "IESNA91\nTILT=INCLUDE\n1\n2\n0 90\n1 1\n1,-1,1,3,1,1,1,0,0,0\n1,1,0\n0,90,180\n0\n100 50 0\n",
+        )
+        .unwrap();
+        let c = candela(&round, 90.0, 0.0);
+        assert!((c - 50.0).abs() < 1e-9);
+        for horizontal in [37.0, 123.0, 300.0] {
+            assert!((candela(&round, 90.0, horizontal) - c).abs() < 1e-9);
+        }
+        // An isotropic source of I watts per steradian emits 4 pi I
+        let isotropic =
+            IesProfile::parse("TILT=NONE\n1 -1 1 2 1 1 1 0 0 0\n1 1 0\n0 180\n0\n683 683\n")
+                .unwrap();
+        assert!((isotropic.integrate(|_| 1.0) - 4.0 * PI).abs() < 1e-3);
+
+        // Quadrant symmetric
+        let quadrant =
+            IesProfile::parse("TILT=NONE\n1 -1 1 2 2 1 1 0 0 0\n1 1 0\n0 90\n0 90\n10 20 30 40\n")
+                .unwrap();
+        let (c0, c90) = (
+            candela(&quadrant, 90.0, 0.0),
+            candela(&quadrant, 90.0, 90.0),
+        );
+        assert!((candela(&quadrant, 90.0, 180.0) - c0).abs() < 1e-9);
+        assert!((candela(&quadrant, 90.0, 270.0) - c90).abs() < 1e-9);
+        assert!((candela(&quadrant, 90.0, 135.0) - (c0 + c90) / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn wraps_around_full_circle() {
+        // Measured every 15 degrees up to 345, brightening all the way round
+        let angles: Vec<String> = (0..24).map(|i| (i * 15).to_string()).collect();
+        let intensities: Vec<String> = (0..24)
+            .flat_map(|i| [i * 10; 2])
+            .map(|c| c.to_string())
+            .collect();
+        let text = format!(
+            "TILT=NONE\n1 -1 1 2 24 1 1 0 0 0\n1 1 0\n0 90\n{}\n{}\n",
+            angles.join(" "),
+            intensities.join(" ")
+        );
+        let profile = IesProfile::parse(&text).unwrap();
+
+        assert!((candela(&profile, 45.0, 337.5) - 225.0).abs() < 1e-9);
+        // Between the last angle and 360, which is 0 again
+        assert!((candela(&profile, 45.0, 352.5) - 115.0).abs() < 1e-9);
+        assert!((candela(&profile, 45.0, 359.0) - 230.0 / 15.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rejects_unsupported() {
+        assert!(IesProfile::parse("IESNA:LM-63-2002\n1 2 3\n").is_err());
+        assert!(IesProfile::parse("TILT=lamp.tlt\n").is_err());
+        // Type B photometry
+        assert!(IesProfile::parse("TILT=NONE\n1 -1 1 1 1 2 1 0 0 0\n1 1 0\n0\n0\n1\n").is_err());
+        // Truncated candela values
+        let truncated = &DOWNLIGHT[..DOWNLIGHT.len() - 10];
+        assert!(IesProfile::parse(truncated).is_err());
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,9 @@
+pub mod diffuse_area_light;
+pub mod distant_light;
+pub mod environment_light;
+pub mod ies_profile;
+pub mod point_light;
+pub mod sky_light;
+pub mod sky_model;
+pub mod spot_light;
+pub mod sun_light;
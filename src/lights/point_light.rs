@@ -0,0 +1,158 @@
+use std::f64::consts::PI;
+
+use crate::core::bounds::{Bounds3f, DirectionCone};
+use crate::core::frame::Frame;
+use crate::core::interaction::Interaction;
+use crate::core::light::{Light, LightBounds, LightSample};
+use crate::core::vector::{Color3f, Point2f, Point3f, Vec3f};
+use crate::lights::ies_profile::IesProfile;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// An infinitely small [Light] shining equally in all directions,
+/// or following a measured [IesProfile].
+pub struct PointLight {
+    position: Point3f,
+    intensity: Color3f,
+    profile: Option<IesProfile>,
+
+    /// Takes directions from the profile's frame to the world
+    frame: Frame,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl PointLight {
+    /// `intensity` is the power emitted per unit solid angle.
+    pub fn new(position: Point3f, intensity: Color3f) -> Self {
+        Self {
+            position,
+            intensity,
+            profile: None,
+            frame: Frame::from_normal(Vec3f::new(0.0, 0.0, 1.0)),
+        }
+    }
+
+    /// Shines as measured in `profile`, with `intensity` as a tint or scale on the measured
+    /// intensity, white for the luminaire as specified. `frame` has `n` along the luminaire's
+    /// axis, at 0 degrees vertical, and `s` at 0 degrees horizontal.
+    pub fn with_profile(self, profile: IesProfile, frame: Frame) -> Self {
+        Self {
+            profile: Some(profile),
+            frame,
+            ..self
+        }
+    }
+
+    /// The intensity sent along the normalized direction `w`.
+    fn intensity_towards(&self, w: &Vec3f) -> Color3f {
+        match &self.profile {
+            Some(profile) => self.intensity * profile.intensity(&self.frame.world_to_local(w)),
+            None => self.intensity,
+        }
+    }
+}
+
+impl Light for PointLight {
+    fn sample_li(&self, reference: &Interaction, _u: &Point2f) -> Option<LightSample> {
+        let distance_sq = (self.position - reference.p).length_sq();
+        if distance_sq == 0.0 {
+            return None;
+        }
+        let sample = LightSample::from_point(Color3f::default(), 1.0, &reference.p, &self.position);
+        let li = self.intensity_towards(&-sample.wi) / distance_sq;
+        if li.is_black() {
+            return None;
+        }
+        Some(LightSample { li, ..sample })
+    }
+
+    fn pdf_li(&self, _reference: &Interaction, _wi: &Vec3f) -> f64 {
+        0.0
+    }
+
+    fn power(&self) -> Color3f {
+        match &self.profile {
+            Some(profile) => self.intensity * profile.integrate(|_| 1.0),
+            None => self.intensity * (4.0 * PI),
+        }
+    }
+
+    fn is_delta(&self) -> bool {
+        true
+    }
+
+    fn bounds(&self) -> Option<LightBounds> {
+        Some(LightBounds {
+            bounds: Bounds3f::from_point(self.position),
+            normals: DirectionCone::entire_sphere(),
+            cos_theta_e: 0.0,
+            phi: self.intensity.average()
+                * 4.0
+                * PI
+                * self.profile.as_ref().map_or(1.0, |p| p.max_intensity()),
+            two_sided: false,
+        })
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverse_square_falloff() {
+        let light = PointLight::new(Point3f::new(0.0, 2.0, 0.0), Color3f::new(4.0, 8.0, 12.0));
+        let n = Vec3f::new(0.0, 1.0, 0.0);
+        let reference = Interaction::new_on_surface(Point3f::default(), 1.0, n, n);
+
+        let sample = light.sample_li(&reference, &Point2f::default()).unwrap();
+        assert_eq!(sample.li, Color3f::new(1.0, 2.0, 3.0));
+        assert_eq!(sample.wi, n);
+        assert_eq!(sample.pdf, 1.0);
+        assert_eq!(light.pdf_li(&reference, &n), 0.0);
+
+        assert!((light.power() - Color3f::new(16.0, 32.0, 48.0) * PI).length() < 1e-12);
+    }
+
+    #[test]
+    fn profile() {
+        // Twice as bright straight down as sideways, and dark upwards
+        let profile =
+            IesProfile::parse("TILT=NONE\n1 -1 1 3 1 1 1 0 0 0\n1 1 0\n0 90 180\n0\n1366 683 0\n")
+                .unwrap();
+        let down = Frame::from_normal(Vec3f::new(0.0, -1.0, 0.0));
+        let light = PointLight::new(Point3f::default(), Color3f::new(1.0, 0.5, 0.25))
+            .with_profile(profile, down);
+
+        let li_at = |p: Point3f| {
+            let reference = Interaction::new_on_surface(p, 1.0, -p, -p);
+            light
+                .sample_li(&reference, &Point2f::default())
+                .map_or(Color3f::default(), |sample| sample.li)
+        };
+        let below = li_at(Point3f::new(0.0, -2.0, 0.0));
+        assert!((below - Color3f::new(0.5, 0.25, 0.125)).length() < 1e-12);
+        let beside = li_at(Point3f::new(0.0, 0.0, 1.0));
+        assert!((beside - Color3f::new(1.0, 0.5, 0.25)).length() < 1e-12);
+        assert!(li_at(Point3f::new(0.0, 3.0, 0.0)).is_black());
+
+        // The intensity falls linearly from 2 to 0 with the angle theta,
+        // integrating 2 pi (2 - 2 theta / pi) sin(theta) over theta to 4 pi
+        assert!((light.power().x - 4.0 * PI).abs() < 1e-3);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
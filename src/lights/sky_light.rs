@@ -0,0 +1,158 @@
+use std::f64::consts::PI;
+
+use crate::core::bounds::Bounds3f;
+use crate::core::frame::Frame;
+use crate::core::image::Image;
+use crate::core::interaction::Interaction;
+use crate::core::light::{Light, LightSample};
+use crate::core::ray::Ray;
+use crate::core::vector::{Color3f, Point2f, Vec3f};
+use crate::lights::environment_light::EnvironmentLight;
+use crate::lights::sky_model::{sky_radiance, sun_radiance, SUN_ANGULAR_RADIUS};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A procedural clear sky (Preetham et al. 1999) over a diffuse ground, surrounding the scene
+/// like an [EnvironmentLight] with z up. The sun's disk is left out,
+/// pair it with a [SunLight](crate::lights::sun_light::SunLight) in the same direction.
+pub struct SkyLight {
+    environment: EnvironmentLight,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// The size of the map the sky is baked into.
+const SKY_WIDTH: usize = 256;
+const SKY_HEIGHT: usize = 128;
+
+impl SkyLight {
+    /// `sun` points towards the sun, see [sun_direction](crate::lights::sky_model::sun_direction)
+    /// for placing it by location and time. `turbidity` runs from about 2.0 for very clear
+    /// to 10.0 for hazy skies, and the ground reflects `ground_albedo` of the light falling on it.
+    pub fn new(sun: Vec3f, turbidity: f64, ground_albedo: Color3f) -> Self {
+        let sun = sun.normalize();
+        let direction = |x: usize, y: usize| {
+            let theta = PI * (y as f64 + 0.5) / SKY_HEIGHT as f64;
+            let phi = 2.0 * PI * (x as f64 + 0.5) / SKY_WIDTH as f64;
+            Vec3f::new(
+                theta.sin() * phi.cos(),
+                theta.sin() * phi.sin(),
+                theta.cos(),
+            )
+        };
+
+        let mut texels = Vec::with_capacity(SKY_WIDTH * SKY_HEIGHT);
+        for y in 0..SKY_HEIGHT / 2 {
+            for x in 0..SKY_WIDTH {
+                texels.push(sky_radiance(&sun, turbidity, &direction(x, y)));
+            }
+        }
+
+        // The ground glows with the light of the sky and the sun reflected off it
+        let texel_solid_angle = |y: usize| {
+            let theta = PI * (y as f64 + 0.5) / SKY_HEIGHT as f64;
+            (2.0 * PI / SKY_WIDTH as f64) * (PI / SKY_HEIGHT as f64) * theta.sin()
+        };
+        let mut irradiance = Color3f::default();
+        for y in 0..SKY_HEIGHT / 2 {
+            for x in 0..SKY_WIDTH {
+                let cos_theta = direction(x, y).z;
+                irradiance += texels[y * SKY_WIDTH + x] * (cos_theta * texel_solid_angle(y));
+            }
+        }
+        let sun_solid_angle = 2.0 * PI * (1.0 - SUN_ANGULAR_RADIUS.to_radians().cos());
+        irradiance += sun_radiance(&sun, turbidity) * (sun_solid_angle * sun.z.max(0.0));
+        let ground = ground_albedo * irradiance / PI;
+        texels.resize(SKY_WIDTH * SKY_HEIGHT, ground);
+
+        Self {
+            environment: EnvironmentLight::new(Image::new(SKY_WIDTH, SKY_HEIGHT, texels)),
+        }
+    }
+
+    /// Turns the sky by `rotation`, see [EnvironmentLight::with_rotation].
+    pub fn with_rotation(self, rotation: Frame) -> Self {
+        Self {
+            environment: self.environment.with_rotation(rotation),
+        }
+    }
+}
+
+impl Light for SkyLight {
+    fn sample_li(&self, reference: &Interaction, u: &Point2f) -> Option<LightSample> {
+        self.environment.sample_li(reference, u)
+    }
+
+    fn pdf_li(&self, reference: &Interaction, wi: &Vec3f) -> f64 {
+        self.environment.pdf_li(reference, wi)
+    }
+
+    fn power(&self) -> Color3f {
+        self.environment.power()
+    }
+
+    fn is_delta(&self) -> bool {
+        false
+    }
+
+    fn le(&self, ray: &Ray) -> Color3f {
+        self.environment.le(ray)
+    }
+
+    fn preprocess(&mut self, scene_bounds: &Bounds3f) {
+        self.environment.preprocess(scene_bounds);
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::Point3f;
+
+    fn le(light: &SkyLight, d: Vec3f) -> Color3f {
+        light.le(&Ray::new(Point3f::default(), d, 0.0, f64::INFINITY))
+    }
+
+    #[test]
+    fn sky_and_ground() {
+        let sun = Vec3f::new(1.0, 0.0, 1.0).normalize();
+        let gray = Color3f::new(0.3, 0.3, 0.3);
+        let light = SkyLight::new(sun, 3.0, gray);
+
+        let up = Vec3f::new(0.0, 0.0, 1.0);
+        let zenith = le(&light, up);
+        assert!((zenith - sky_radiance(&sun, 3.0, &up)).length() < 0.05 * zenith.length());
+        // The sky around the sun outshines the rest
+        assert!(le(&light, Vec3f::new(1.0, 0.0, 0.8)).y > zenith.y);
+
+        // The ground is lit mostly by the sun, and uniformly so
+        let ground = le(&light, Vec3f::new(0.3, 0.2, -1.0));
+        assert_eq!(ground, le(&light, Vec3f::new(-0.5, 0.1, -0.3)));
+        assert!(ground.y > zenith.y);
+        let black = SkyLight::new(sun, 3.0, Color3f::default());
+        assert!(le(&black, -up).is_black());
+        assert!((le(&black, up) - zenith).length() < 1e-9);
+
+        // Importance sampling favors the sunny side of the sky
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let reference = Interaction::new_on_surface(Point3f::default(), 1.0, n, n);
+        let sample = light
+            .sample_li(&reference, &Point2f::new(0.5, 0.1))
+            .unwrap();
+        assert!((light.pdf_li(&reference, &sample.wi) - sample.pdf).abs() < 1e-6 * sample.pdf);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
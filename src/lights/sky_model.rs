@@ -0,0 +1,219 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use crate::core::vector::{Color3f, Vec3f};
+
+//////////////////////////
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// The angle the sun's disk spans from its center to its edge, in degrees.
+pub const SUN_ANGULAR_RADIUS: f64 = 0.2666;
+
+/// The luminance of the sun above the atmosphere, in the same thousands of candela
+/// per square meter as the sky.
+const SUN_LUMINANCE: f64 = 2.0e6;
+
+/// The wavelengths standing in for red, green and blue in the sun's attenuation, in micrometers.
+const RGB_WAVELENGTHS: [f64; 3] = [0.68, 0.55, 0.44];
+
+/// The range of turbidities the sky model was fitted to.
+const MIN_TURBIDITY: f64 = 1.7;
+const MAX_TURBIDITY: f64 = 10.0;
+
+/// The direction of the sun, with x east, y north and z up, for an observer at `latitude`
+/// and `longitude` degrees (north and east positive) on day `day_of_year` (1 to 365)
+/// at `utc_hours` (0.0 to 24.0). Follows NOAA's low accuracy equations,
+/// good to a fraction of a degree.
+pub fn sun_direction(latitude: f64, longitude: f64, day_of_year: u32, utc_hours: f64) -> Vec3f {
+    let year = 2.0 * PI / 365.0 * (day_of_year as f64 - 1.0 + (utc_hours - 12.0) / 24.0);
+    let equation_of_time = 229.18
+        * (0.000075 + 0.001868 * year.cos()
+            - 0.032077 * year.sin()
+            - 0.014615 * (2.0 * year).cos()
+            - 0.040849 * (2.0 * year).sin());
+    let declination = 0.006918 - 0.399912 * year.cos() + 0.070257 * year.sin()
+        - 0.006758 * (2.0 * year).cos()
+        + 0.000907 * (2.0 * year).sin()
+        - 0.002697 * (3.0 * year).cos()
+        + 0.00148 * (3.0 * year).sin();
+
+    // Minutes of true solar time, four per degree of longitude
+    let solar_minutes = utc_hours * 60.0 + equation_of_time + 4.0 * longitude;
+    let hour_angle = (solar_minutes / 4.0 - 180.0).to_radians();
+
+    let latitude = latitude.to_radians();
+    let (sin_lat, cos_lat) = latitude.sin_cos();
+    let (sin_dec, cos_dec) = declination.sin_cos();
+    Vec3f::new(
+        -cos_dec * hour_angle.sin(),
+        cos_lat * sin_dec - sin_lat * cos_dec * hour_angle.cos(),
+        sin_lat * sin_dec + cos_lat * cos_dec * hour_angle.cos(),
+    )
+    .normalize()
+}
+
+/// The Perez et al. sky luminance distribution, relative to the zenith up to normalization.
+fn perez(coefficients: &[f64; 5], cos_theta: f64, gamma: f64) -> f64 {
+    let [a, b, c, d, e] = *coefficients;
+    // Blows up at the horizon otherwise
+    let cos_theta = cos_theta.max(1e-2);
+    (1.0 + a * (b / cos_theta).exp()) * (1.0 + c * (d * gamma).exp() + e * gamma.cos().powi(2))
+}
+
+/// Preetham et al.'s clear sky, with the sun along the normalized `sun` and `turbidity`
+/// from about 2.0 for very clear to 10.0 for hazy skies.
+/// Returns the radiance seen along the normalized `w` above the horizon, without the sun itself,
+/// in linear sRGB scaled to thousands of candela per square meter.
+/// Suns below the horizon are treated as setting, so the model stays in its fitted range.
+pub fn sky_radiance(sun: &Vec3f, turbidity: f64, w: &Vec3f) -> Color3f {
+    let t = turbidity.clamp(MIN_TURBIDITY, MAX_TURBIDITY);
+    let theta_sun = sun.z.clamp(0.0, 1.0).acos().min(FRAC_PI_2);
+    let gamma = w.dot(sun).clamp(-1.0, 1.0).acos();
+    let cos_theta = w.z;
+
+    let luminance = [
+        0.1787 * t - 1.4630,
+        -0.3554 * t + 0.4275,
+        -0.0227 * t + 5.3251,
+        0.1206 * t - 2.5771,
+        -0.0670 * t + 0.3703,
+    ];
+    let x = [
+        -0.0193 * t - 0.2592,
+        -0.0665 * t + 0.0008,
+        -0.0004 * t + 0.2125,
+        -0.0641 * t - 0.8989,
+        -0.0033 * t + 0.0452,
+    ];
+    let y = [
+        -0.0167 * t - 0.2608,
+        -0.0950 * t + 0.0092,
+        -0.0079 * t + 0.2102,
+        -0.0441 * t - 1.6537,
+        -0.0109 * t + 0.0529,
+    ];
+
+    let chi = (4.0 / 9.0 - t / 120.0) * (PI - 2.0 * theta_sun);
+    let zenith_luminance = ((4.0453 * t - 4.9710) * chi.tan() - 0.2155 * t + 2.4192).max(0.0);
+    let cubic = |c: [f64; 4]| ((c[0] * theta_sun + c[1]) * theta_sun + c[2]) * theta_sun + c[3];
+    let zenith_x = t * t * cubic([0.00166, -0.00375, 0.00209, 0.0])
+        + t * cubic([-0.02903, 0.06377, -0.03202, 0.00394])
+        + cubic([0.11693, -0.21196, 0.06052, 0.25886]);
+    let zenith_y = t * t * cubic([0.00275, -0.00610, 0.00317, 0.0])
+        + t * cubic([-0.04214, 0.08970, -0.04153, 0.00516])
+        + cubic([0.15346, -0.26756, 0.06670, 0.26688]);
+
+    let relative = |coefficients: &[f64; 5]| {
+        perez(coefficients, cos_theta, gamma) / perez(coefficients, 1.0, theta_sun)
+    };
+    xyy_to_rgb(
+        zenith_x * relative(&x),
+        zenith_y * relative(&y),
+        zenith_luminance * relative(&luminance),
+    )
+}
+
+/// Converts CIE xyY to linear sRGB, clipping colors outside of its gamut.
+fn xyy_to_rgb(x: f64, y: f64, luminance: f64) -> Color3f {
+    if y <= 0.0 {
+        return Color3f::default();
+    }
+    let cx = x / y * luminance;
+    let cz = (1.0 - x - y) / y * luminance;
+    Color3f::new(
+        3.2406 * cx - 1.5372 * luminance - 0.4986 * cz,
+        -0.9689 * cx + 1.8758 * luminance + 0.0415 * cz,
+        0.0557 * cx - 0.2040 * luminance + 1.0570 * cz,
+    )
+    .map(|c| c.max(0.0))
+}
+
+/// The radiance of the sun's disk seen through the atmosphere from the ground,
+/// in the same units as [sky_radiance]. Light is scattered away by air molecules and by haze,
+/// more so at short wavelengths and low in the sky. Black below the horizon.
+pub fn sun_radiance(sun: &Vec3f, turbidity: f64) -> Color3f {
+    if sun.z <= 0.0 {
+        return Color3f::default();
+    }
+    let t = turbidity.clamp(MIN_TURBIDITY, MAX_TURBIDITY);
+    let theta_degrees = sun.z.min(1.0).acos().to_degrees();
+    let relative_air_mass = 1.0 / (sun.z + 0.15 * (93.885 - theta_degrees).powf(-1.253));
+
+    // Angstrom's haze coefficient
+    let beta = 0.04608 * t - 0.04586;
+    let [r, g, b] = RGB_WAVELENGTHS.map(|lambda: f64| {
+        let rayleigh = (-0.008735 * lambda.powf(-4.08) * relative_air_mass).exp();
+        let aerosol = (-beta * lambda.powf(-1.3) * relative_air_mass).exp();
+        rayleigh * aerosol
+    });
+    Color3f::new(r, g, b) * SUN_LUMINANCE
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elevation(d: &Vec3f) -> f64 {
+        d.z.asin().to_degrees()
+    }
+
+    #[test]
+    fn sun_positions() {
+        // Overhead at the equator at noon on the equinox
+        let noon = sun_direction(0.0, 0.0, 80, 12.0);
+        assert!(elevation(&noon) > 88.0, "{:?}", noon);
+
+        // Due south at about 45 degrees from Paris-like latitudes, longitude shifting noon
+        let paris = sun_direction(45.0, 30.0, 80, 10.0);
+        assert!((elevation(&paris) - 45.0).abs() < 2.0, "{:?}", paris);
+        assert!(paris.y < 0.0 && paris.x.abs() < 0.05);
+
+        // Rising in the east, setting in the west, and gone at midnight
+        assert!(sun_direction(45.0, 0.0, 172, 7.0).x > 0.5);
+        assert!(sun_direction(45.0, 0.0, 172, 17.0).x < -0.5);
+        assert!(sun_direction(45.0, 0.0, 172, 0.0).z < 0.0);
+
+        // Higher in the summer
+        let summer = sun_direction(45.0, 0.0, 172, 12.0);
+        let winter = sun_direction(45.0, 0.0, 355, 12.0);
+        assert!((elevation(&summer) - 68.4).abs() < 1.0);
+        assert!((elevation(&winter) - 21.6).abs() < 1.0);
+    }
+
+    #[test]
+    fn clear_sky() {
+        let sun = Vec3f::new(0.0, -1.0, 1.0).normalize();
+        let zenith = sky_radiance(&sun, 2.5, &Vec3f::new(0.0, 0.0, 1.0));
+        // Blue overhead
+        assert!(zenith.z > zenith.x, "{:?}", zenith);
+
+        // Brightest around the sun, and brighter towards the horizon than opposite the sun
+        let near_sun = sky_radiance(&sun, 2.5, &Vec3f::new(0.0, -1.0, 0.9).normalize());
+        let opposite = sky_radiance(&sun, 2.5, &Vec3f::new(0.0, 1.0, 1.0).normalize());
+        assert!(near_sun.y > zenith.y && zenith.y > opposite.y * 0.5);
+
+        // Haze brightens and whitens the sky
+        let hazy = sky_radiance(&sun, 8.0, &Vec3f::new(0.0, 0.0, 1.0));
+        assert!(hazy.y > zenith.y);
+        assert!(hazy.z / hazy.x < zenith.z / zenith.x);
+    }
+
+    #[test]
+    fn sunsets_are_red() {
+        let overhead = sun_radiance(&Vec3f::new(0.0, 0.0, 1.0), 3.0);
+        let low = sun_radiance(&Vec3f::new(1.0, 0.0, 0.05).normalize(), 3.0);
+        assert!(overhead.x > low.x && overhead.z > low.z);
+        assert!(low.x / low.z > overhead.x / overhead.z);
+        assert!(overhead.z / overhead.x > 0.5);
+        assert!(sun_radiance(&Vec3f::new(1.0, 0.0, -0.1).normalize(), 3.0).is_black());
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
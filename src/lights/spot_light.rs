@@ -0,0 +1,237 @@
+use std::f64::consts::PI;
+
+use crate::core::bounds::{Bounds3f, DirectionCone};
+use crate::core::frame::Frame;
+use crate::core::interaction::Interaction;
+use crate::core::light::{Light, LightBounds, LightSample};
+use crate::core::vector::{Color3f, Point2f, Point3f, Vec3f};
+use crate::lights::ies_profile::IesProfile;
+use crate::math::smoothstep;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A point [Light] shining in a cone, at full intensity within `falloff_start` degrees
+/// of its axis and fading out smoothly until `total_width` degrees.
+/// Within the cone it can follow a measured [IesProfile].
+pub struct SpotLight {
+    position: Point3f,
+    direction: Vec3f,
+    intensity: Color3f,
+    cos_total_width: f64,
+    cos_falloff_start: f64,
+    profile: Option<IesProfile>,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl SpotLight {
+    /// `intensity` is the power emitted per unit solid angle along the axis.
+    /// Both angles are measured from the axis, in degrees.
+    pub fn new(
+        position: Point3f,
+        direction: Vec3f,
+        intensity: Color3f,
+        total_width: f64,
+        falloff_start: f64,
+    ) -> Self {
+        let falloff_start = falloff_start.min(total_width);
+        Self {
+            position,
+            direction: direction.normalize(),
+            intensity,
+            cos_total_width: total_width.to_radians().cos(),
+            cos_falloff_start: falloff_start.to_radians().cos(),
+            profile: None,
+        }
+    }
+
+    /// Shines as measured in `profile`, aimed along the spot's axis,
+    /// with `intensity` as a tint or scale on the measured intensity,
+    /// white for the luminaire as specified. The profile's 0 degrees horizontal
+    /// lies along an arbitrary direction across the axis, which suits symmetric profiles,
+    /// see [PointLight::with_profile](crate::lights::point_light::PointLight::with_profile)
+    /// to place others.
+    pub fn with_profile(self, profile: IesProfile) -> Self {
+        Self {
+            profile: Some(profile),
+            ..self
+        }
+    }
+
+    /// Takes directions from the profile's frame to the world.
+    fn frame(&self) -> Frame {
+        Frame::from_normal(self.direction)
+    }
+
+    /// The intensity sent along the normalized direction `w`, before the falloff.
+    fn intensity_towards(&self, w: &Vec3f) -> Color3f {
+        match &self.profile {
+            Some(profile) => self.intensity * profile.intensity(&self.frame().world_to_local(w)),
+            None => self.intensity,
+        }
+    }
+
+    /// The fraction of the intensity sent along the normalized direction `w`.
+    fn falloff(&self, w: &Vec3f) -> f64 {
+        smoothstep(
+            w.dot(&self.direction),
+            self.cos_total_width,
+            self.cos_falloff_start,
+        )
+    }
+}
+
+impl Light for SpotLight {
+    fn sample_li(&self, reference: &Interaction, _u: &Point2f) -> Option<LightSample> {
+        let distance_sq = (self.position - reference.p).length_sq();
+        if distance_sq == 0.0 {
+            return None;
+        }
+        let sample = LightSample::from_point(Color3f::default(), 1.0, &reference.p, &self.position);
+        let falloff = self.falloff(&-sample.wi);
+        if falloff == 0.0 {
+            return None;
+        }
+        let li = self.intensity_towards(&-sample.wi) * (falloff / distance_sq);
+        if li.is_black() {
+            return None;
+        }
+        Some(LightSample { li, ..sample })
+    }
+
+    fn pdf_li(&self, _reference: &Interaction, _wi: &Vec3f) -> f64 {
+        0.0
+    }
+
+    /// The smoothstep falloff integrates to half the solid angle it covers.
+    fn power(&self) -> Color3f {
+        if let Some(profile) = &self.profile {
+            // The profile's z is the spot's axis
+            let falloff = |w: &Vec3f| smoothstep(w.z, self.cos_total_width, self.cos_falloff_start);
+            return self.intensity * profile.integrate(falloff);
+        }
+        let full = 1.0 - self.cos_falloff_start;
+        let fading = 0.5 * (self.cos_falloff_start - self.cos_total_width);
+        self.intensity * (2.0 * PI * (full + fading))
+    }
+
+    fn is_delta(&self) -> bool {
+        true
+    }
+
+    /// Counts the full intensity in every direction, leaving the cone to the bounds.
+    fn bounds(&self) -> Option<LightBounds> {
+        let falloff_start = self.cos_falloff_start.acos();
+        let falloff_width = self.cos_total_width.acos() - falloff_start;
+        Some(LightBounds {
+            bounds: Bounds3f::from_point(self.position),
+            normals: DirectionCone::new(self.direction, self.cos_falloff_start),
+            cos_theta_e: falloff_width.cos(),
+            phi: self.intensity.average()
+                * 4.0
+                * PI
+                * self.profile.as_ref().map_or(1.0, |p| p.max_intensity()),
+            two_sided: false,
+        })
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn li_at(light: &SpotLight, p: Point3f) -> Color3f {
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let reference = Interaction::new_on_surface(p, 1.0, n, n);
+        light
+            .sample_li(&reference, &Point2f::default())
+            .map_or(Color3f::default(), |sample| sample.li)
+    }
+
+    #[test]
+    fn cone_falloff() {
+        let white = Color3f::new(1.0, 1.0, 1.0);
+        let down = Vec3f::new(0.0, 0.0, -1.0);
+        let light = SpotLight::new(Point3f::new(0.0, 0.0, 1.0), down, white, 45.0, 30.0);
+
+        // Full intensity on the axis and inside the inner cone
+        assert_eq!(li_at(&light, Point3f::default()), white);
+        let inner = 20f64.to_radians().tan();
+        assert_eq!(
+            li_at(&light, Point3f::new(inner, 0.0, 0.0)),
+            white * (1.0 / (1.0 + inner * inner))
+        );
+
+        // Fading in between
+        let edge = 37.5f64.to_radians().tan();
+        let li = li_at(&light, Point3f::new(0.0, edge, 0.0)) * (1.0 + edge * edge);
+        assert!(li.x > 0.1 && li.x < 0.9);
+
+        // Dark outside the cone
+        assert!(li_at(&light, Point3f::new(1.5, 0.0, 0.0)).is_black());
+        assert!(li_at(&light, Point3f::new(0.0, 0.0, 2.0)).is_black());
+    }
+
+    #[test]
+    fn power() {
+        let white = Color3f::new(1.0, 1.0, 1.0);
+        let down = Vec3f::new(0.0, 0.0, -1.0);
+        let p = Point3f::default();
+
+        // A hemisphere without falloff sends half of what a point light does
+        let hemisphere = SpotLight::new(p, down, white, 90.0, 90.0);
+        assert!((hemisphere.power() - white * (2.0 * PI)).length() < 1e-12);
+
+        // Integrate the falloff numerically over the cone
+        let light = SpotLight::new(p, down, white, 50.0, 20.0);
+        let steps = 100_000;
+        let d_theta = 50f64.to_radians() / steps as f64;
+        let power = (0..steps)
+            .map(|i| {
+                let theta = (i as f64 + 0.5) * d_theta;
+                let w = Vec3f::new(theta.sin(), 0.0, -theta.cos());
+                light.falloff(&w) * 2.0 * PI * theta.sin() * d_theta
+            })
+            .sum::<f64>();
+        assert!((light.power().x - power).abs() < 1e-6);
+    }
+
+    #[test]
+    fn profile() {
+        let white = Color3f::new(1.0, 1.0, 1.0);
+        let down = Vec3f::new(0.0, 0.0, -1.0);
+        let p = Point3f::new(0.0, 0.0, 1.0);
+        // A uniform 2 watts per steradian within 30 degrees of the axis
+        let narrow = IesProfile::parse(
+            "TILT=NONE\n1 -1 1 3 1 1 1 0 0 0\n1 1 0\n0 30 30.001\n0\n1366 1366 0\n",
+        )
+        .unwrap();
+        let light = SpotLight::new(p, down, white, 60.0, 60.0).with_profile(narrow);
+
+        assert_eq!(li_at(&light, Point3f::default()), white * 2.0);
+        // Cut off by the profile before the cone
+        assert!(li_at(&light, Point3f::new(1.0, 0.0, 0.0)).is_black());
+
+        // A uniform profile leaves the cone's power as it was
+        let uniform =
+            IesProfile::parse("TILT=NONE\n1 -1 1 2 1 1 1 0 0 0\n1 1 0\n0 180\n0\n683 683\n")
+                .unwrap();
+        let plain = SpotLight::new(p, down, white, 50.0, 20.0);
+        let profiled = SpotLight::new(p, down, white, 50.0, 20.0).with_profile(uniform);
+        assert!((profiled.power().x - plain.power().x).abs() < 1e-2 * plain.power().x);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
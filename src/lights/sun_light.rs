@@ -0,0 +1,179 @@
+use std::f64::consts::PI;
+
+use crate::core::bounds::Bounds3f;
+use crate::core::frame::Frame;
+use crate::core::interaction::Interaction;
+use crate::core::light::{Light, LightSample};
+use crate::core::ray::Ray;
+use crate::core::vector::{Color3f, Point2f, Vec3f};
+use crate::lights::sky_model::{sun_radiance, SUN_ANGULAR_RADIUS};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// The sun's disk as seen through the atmosphere, matching a
+/// [SkyLight](crate::lights::sky_light::SkyLight) with the same sun and turbidity.
+/// Unlike a [DistantLight](crate::lights::distant_light::DistantLight)
+/// it has a size, casting slightly soft shadows, and can be seen by camera rays.
+pub struct SunLight {
+    /// Around the direction towards the sun
+    frame: Frame,
+
+    radiance: Color3f,
+    cos_angular_radius: f64,
+    scene_radius: f64,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl SunLight {
+    /// `sun` points towards the sun, with z up.
+    pub fn new(sun: Vec3f, turbidity: f64) -> Self {
+        let sun = sun.normalize();
+        Self {
+            frame: Frame::from_normal(sun),
+            radiance: sun_radiance(&sun, turbidity),
+            cos_angular_radius: SUN_ANGULAR_RADIUS.to_radians().cos(),
+            scene_radius: 0.0,
+        }
+    }
+
+    /// Turns the sun along with the sky, see [Frame::from_rotation].
+    pub fn with_rotation(self, rotation: Frame) -> Self {
+        let sun = rotation.local_to_world(&self.frame.n);
+        Self {
+            frame: Frame::from_normal(sun.normalize()),
+            ..self
+        }
+    }
+
+    fn solid_angle(&self) -> f64 {
+        2.0 * PI * (1.0 - self.cos_angular_radius)
+    }
+
+    fn covers(&self, w: &Vec3f) -> bool {
+        w.normalize().dot(&self.frame.n) >= self.cos_angular_radius
+    }
+}
+
+impl Light for SunLight {
+    fn sample_li(&self, reference: &Interaction, u: &Point2f) -> Option<LightSample> {
+        if self.radiance.is_black() {
+            return None;
+        }
+        // Uniformly over the cone of the disk
+        let cos_theta = 1.0 - u.x * (1.0 - self.cos_angular_radius);
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let phi = 2.0 * PI * u.y;
+        let wi = self.frame.local_to_world(&Vec3f::new(
+            sin_theta * phi.cos(),
+            sin_theta * phi.sin(),
+            cos_theta,
+        ));
+        Some(LightSample::from_direction(
+            self.radiance,
+            1.0 / self.solid_angle(),
+            &reference.p,
+            &wi,
+        ))
+    }
+
+    fn pdf_li(&self, _reference: &Interaction, wi: &Vec3f) -> f64 {
+        if self.radiance.is_black() || !self.covers(wi) {
+            return 0.0;
+        }
+        1.0 / self.solid_angle()
+    }
+
+    /// What falls onto the scene's bounding sphere, known after [Light::preprocess].
+    fn power(&self) -> Color3f {
+        self.radiance * (self.solid_angle() * PI * self.scene_radius * self.scene_radius)
+    }
+
+    fn is_delta(&self) -> bool {
+        false
+    }
+
+    fn le(&self, ray: &Ray) -> Color3f {
+        if self.covers(&ray.d) {
+            self.radiance
+        } else {
+            Color3f::default()
+        }
+    }
+
+    fn preprocess(&mut self, scene_bounds: &Bounds3f) {
+        self.scene_radius = scene_bounds.bounding_sphere().1;
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::Point3f;
+
+    #[test]
+    fn disk() {
+        let sun = Vec3f::new(0.0, 1.0, 1.0).normalize();
+        let light = SunLight::new(sun, 3.0);
+        let up = Vec3f::new(0.0, 0.0, 1.0);
+        let reference = Interaction::new_on_surface(Point3f::default(), 1.0, up, up);
+
+        for u in [
+            Point2f::new(0.0, 0.0),
+            Point2f::new(0.99, 0.3),
+            Point2f::new(0.5, 0.8),
+        ] {
+            let sample = light.sample_li(&reference, &u).unwrap();
+            assert_eq!(sample.li, light.radiance);
+            assert!(sample.wi.dot(&sun) >= light.cos_angular_radius - 1e-12);
+            assert_eq!(light.pdf_li(&reference, &sample.wi), sample.pdf);
+            let ray = Ray::new(reference.p, sample.wi, 0.0, f64::INFINITY);
+            assert_eq!(light.le(&ray), sample.li);
+        }
+        assert_eq!(light.pdf_li(&reference, &up), 0.0);
+        assert!(light
+            .le(&Ray::new(reference.p, up, 0.0, f64::INFINITY))
+            .is_black());
+
+        // Irradiance of a disk this small is nearly radiance times solid angle
+        let cos_sun = sun.z;
+        let expected = light.radiance.y * light.solid_angle() * cos_sun;
+        let sample = light
+            .sample_li(&reference, &Point2f::new(0.5, 0.5))
+            .unwrap();
+        assert!((sample.li.y * sample.wi.z / sample.pdf - expected).abs() < 1e-3 * expected);
+
+        // Set below the horizon
+        let night = SunLight::new(Vec3f::new(0.0, 1.0, -0.5), 3.0);
+        assert!(night
+            .sample_li(&reference, &Point2f::new(0.5, 0.5))
+            .is_none());
+    }
+
+    #[test]
+    fn rotation() {
+        let light = SunLight::new(Vec3f::new(0.0, 0.0, 1.0), 3.0)
+            .with_rotation(Frame::from_rotation(Vec3f::new(1.0, 0.0, 0.0), -90.0));
+        let y_up = Ray::new(
+            Point3f::default(),
+            Vec3f::new(0.0, 1.0, 0.0),
+            0.0,
+            f64::INFINITY,
+        );
+        assert!(!light.le(&y_up).is_black());
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
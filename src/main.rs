@@ -1,14 +1,10 @@
-mod accelerators;
-mod cameras;
-mod core;
-mod films;
-mod integrators;
-mod math;
-mod primitives;
-mod samplers;
-
-use crate::core::primitive::Primitive;
-use crate::core::vector::{Point3f, Vec3f};
+use rustrace::core::light::Light;
+use rustrace::core::primitive::Primitive;
+use rustrace::core::vector::{Color3f, Point3f, Vec3f};
+use rustrace::{
+    accelerators, cameras, core, films, integrators, lights, materials, primitives, samplers,
+    textures,
+};
 
 fn build_camera() -> cameras::perspective_camera::PerspectiveCamera {
     let origin = Point3f::new(0.0, 0.0, 0.0);
@@ -37,14 +33,24 @@ fn main() {
     let camera = build_camera();
 
     let sphere = primitives::sphere::Sphere::new(Vec3f::new(10.0, 0.0, 0.0), 3.0);
-    let mut environment = Vec::new();
-    environment.push(&sphere as &dyn Primitive);
+    let albedo = textures::constant_texture::ConstantTexture::new(Color3f::new(0.8, 0.8, 0.8));
+    let material = materials::lambertian_material::LambertianMaterial::new(&albedo);
+    let diffuse_sphere =
+        primitives::geometric_primitive::GeometricPrimitive::new(&sphere, &material);
+    let environment = vec![&diffuse_sphere as &dyn Primitive];
     let mut accelerator = accelerators::simple_list::SimpleList::new(&environment);
 
+    let light = lights::point_light::PointLight::new(
+        Point3f::new(0.0, 10.0, 0.0),
+        Color3f::new(100.0, 100.0, 100.0),
+    );
+    let lights = vec![&light as &dyn Light];
+
     let mut film = films::png_film::PngFilm::new(100, 100);
 
     let mut rustrace =
         core::rustrace::RusTrace::new(&mut integrator, &camera, &mut accelerator, &mut film);
+    rustrace.load_lights(&lights);
 
     rustrace.render();
 }
@@ -0,0 +1,268 @@
+use crate::core::bsdf::Bsdf;
+use crate::core::bssrdf::Bssrdf;
+use crate::core::interaction::Interaction;
+use crate::core::material::Material;
+use crate::core::texture::Texture;
+use crate::core::vector::{Color3f, Point2f, Vec3f};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Adds surface detail to another [Material] by perturbing the shading geometry
+/// of each [Interaction] before handing it over, without changing the true surface.
+pub struct BumpMappedMaterial<'a> {
+    base: &'a dyn Material,
+    perturbation: Perturbation<'a>,
+}
+
+/// Where the perturbed normals come from.
+#[derive(Clone, Copy)]
+pub enum Perturbation<'a> {
+    /// A tangent space normal map, with red along the shading frame's first tangent,
+    /// green along its second and blue along the normal, each remapped from [0.0, 1.0]
+    /// to [-1.0, 1.0]. It should be read with a linear encoding.
+    NormalMap(&'a dyn Texture<Color3f>),
+
+    /// A height map, displacing the surface along its normal by the texture value
+    BumpMap(&'a dyn Texture<f64>),
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// The step in `u` and `v` for finite differences of height maps.
+const BUMP_DELTA: f64 = 5e-4;
+
+/// The smallest cosine with the geometric normal that reflections about shading normals keep.
+const MIN_REFLECTION_COS: f64 = 1e-2;
+
+impl<'a> BumpMappedMaterial<'a> {
+    pub fn new(base: &'a dyn Material, perturbation: Perturbation<'a>) -> Self {
+        Self { base, perturbation }
+    }
+
+    fn perturb<'i>(&self, interaction: &Interaction<'i>) -> Interaction<'i> {
+        let Some(n) = interaction.n else {
+            return *interaction;
+        };
+        let perturbed = match self.perturbation {
+            Perturbation::NormalMap(map) => normal_map(map, interaction),
+            Perturbation::BumpMap(height) => bump_map(height, interaction),
+        };
+        let Some((ns, dpdu, dpdv)) = perturbed else {
+            return *interaction;
+        };
+
+        let ns = keep_reflection_visible(ns, n.normalize(), interaction.wo);
+        let shading = interaction.shading;
+        interaction.with_shading(ns, dpdu, dpdv, shading.dndu, shading.dndv)
+    }
+}
+
+/// Returns the mapped shading normal and matching derivatives.
+fn normal_map(
+    map: &dyn Texture<Color3f>,
+    interaction: &Interaction,
+) -> Option<(Vec3f, Vec3f, Vec3f)> {
+    let frame = interaction.shading_frame()?;
+    let local = map.evaluate(interaction) * 2.0 - Color3f::new(1.0, 1.0, 1.0);
+    if local.length_sq() == 0.0 {
+        return None;
+    }
+    let ns = frame.local_to_world(&local.normalize());
+
+    // Keep dpdu as close to the original as the new normal allows
+    let shading = interaction.shading;
+    let dpdu = shading.dpdu - ns * ns.dot(&shading.dpdu);
+    let dpdu = if dpdu.length_sq() > 0.0 {
+        dpdu
+    } else {
+        ns.cross(&frame.t)
+    };
+    let dpdv = ns.cross(&dpdu).normalize() * shading.dpdv.length();
+    Some((ns, dpdu, dpdv))
+}
+
+/// Returns the displaced shading normal and derivatives, from finite differences of `height`.
+/// Surfaces without a parameterization can't be bump mapped.
+fn bump_map(height: &dyn Texture<f64>, interaction: &Interaction) -> Option<(Vec3f, Vec3f, Vec3f)> {
+    let shading = interaction.shading;
+    if shading.dpdu.length_sq() == 0.0 || shading.dpdv.length_sq() == 0.0 {
+        return None;
+    }
+
+    let displace = height.evaluate(interaction);
+    let shifted = |dp: Vec3f, duv: Point2f| {
+        let shifted = Interaction {
+            p: interaction.p + dp,
+            uv: interaction.uv + duv,
+            ..*interaction
+        };
+        height.evaluate(&shifted)
+    };
+    let u_displace = shifted(shading.dpdu * BUMP_DELTA, Point2f::new(BUMP_DELTA, 0.0));
+    let v_displace = shifted(shading.dpdv * BUMP_DELTA, Point2f::new(0.0, BUMP_DELTA));
+
+    let n = shading.n.normalize();
+    let dpdu = shading.dpdu + n * ((u_displace - displace) / BUMP_DELTA) + shading.dndu * displace;
+    let dpdv = shading.dpdv + n * ((v_displace - displace) / BUMP_DELTA) + shading.dndv * displace;
+
+    let ns = dpdu.cross(&dpdv);
+    if ns.length_sq() == 0.0 {
+        return None;
+    }
+    // The parameterization may run either way around the normal
+    let ns = ns.normalize();
+    let ns = if ns.dot(&n) < 0.0 { -ns } else { ns };
+    Some((ns, dpdu, dpdv))
+}
+
+/// Tilts `ns` towards the geometric normal `ng` until the mirror reflection of `wo` about it
+/// stays above the true surface. Otherwise strongly perturbed normals send light into
+/// the surface at grazing angles, leaving black fringes.
+fn keep_reflection_visible(ns: Vec3f, ng: Vec3f, wo: Vec3f) -> Vec3f {
+    // Work on the side `wo` is on
+    let side = if ng.dot(&wo) < 0.0 { -1.0 } else { 1.0 };
+    let (ns, ng) = (ns * side, ng * side);
+    let ns = if ns.dot(&ng) < 0.0 { -ns } else { ns };
+
+    // Reflecting about ng itself keeps the angle of wo, so that always works
+    let threshold = MIN_REFLECTION_COS.min(wo.dot(&ng));
+    let reflects_above = |n: Vec3f| (n * (2.0 * n.dot(&wo)) - wo).dot(&ng) >= threshold;
+    if reflects_above(ns) {
+        return ns * side;
+    }
+
+    let blend = |a: f64| (ns * (1.0 - a) + ng * a).normalize();
+    let (mut lo, mut hi) = (0.0, 1.0);
+    for _ in 0..32 {
+        let mid = 0.5 * (lo + hi);
+        if reflects_above(blend(mid)) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    blend(hi) * side
+}
+
+impl<'a> Material for BumpMappedMaterial<'a> {
+    fn get_bsdf(&self, interaction: &Interaction) -> Option<Bsdf> {
+        self.base.get_bsdf(&self.perturb(interaction))
+    }
+
+    fn get_bssrdf(&self, interaction: &Interaction) -> Option<Box<dyn Bssrdf>> {
+        self.base.get_bssrdf(&self.perturb(interaction))
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::Point3f;
+    use crate::materials::lambertian_material::LambertianMaterial;
+    use crate::textures::constant_texture::ConstantTexture;
+
+    /// Rises by half a unit per unit of u.
+    struct Ramp;
+
+    impl Texture<f64> for Ramp {
+        fn evaluate(&self, interaction: &Interaction) -> f64 {
+            0.5 * interaction.uv.x
+        }
+    }
+
+    /// A flat patch in the xy plane, parameterized along x and y, seen from `wo`.
+    fn patch(wo: Vec3f) -> Interaction<'static> {
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let zero = Vec3f::default();
+        Interaction::new_on_surface(Point3f::default(), 1.0, n, wo)
+            .with_uv(Point2f::new(0.3, 0.6))
+            .with_derivatives(
+                Vec3f::new(1.0, 0.0, 0.0),
+                Vec3f::new(0.0, 1.0, 0.0),
+                zero,
+                zero,
+            )
+    }
+
+    fn shading_normal(material: &BumpMappedMaterial, interaction: &Interaction) -> Vec3f {
+        material.get_bsdf(interaction).unwrap().frame().n
+    }
+
+    #[test]
+    fn normal_map() {
+        let albedo = ConstantTexture::new(Color3f::new(0.5, 0.5, 0.5));
+        let base = LambertianMaterial::new(&albedo);
+        let up = Vec3f::new(0.0, 0.0, 1.0);
+
+        let flat = ConstantTexture::new(Color3f::new(0.5, 0.5, 1.0));
+        let material = BumpMappedMaterial::new(&base, Perturbation::NormalMap(&flat));
+        assert!((shading_normal(&material, &patch(up)) - up).length() < 1e-12);
+
+        // Leaning towards the first tangent, which follows dpdu
+        let tilted = ConstantTexture::new(Color3f::new(0.75, 0.5, 1.0));
+        let material = BumpMappedMaterial::new(&base, Perturbation::NormalMap(&tilted));
+        let n = shading_normal(&material, &patch(up));
+        assert!((n - Vec3f::new(0.5, 0.0, 1.0).normalize()).length() < 1e-12);
+    }
+
+    #[test]
+    fn bump_map() {
+        let albedo = ConstantTexture::new(Color3f::new(0.5, 0.5, 0.5));
+        let base = LambertianMaterial::new(&albedo);
+        let up = Vec3f::new(0.0, 0.0, 1.0);
+
+        // Constant heights raise the surface without tilting it
+        let constant = ConstantTexture::new(0.2);
+        let material = BumpMappedMaterial::new(&base, Perturbation::BumpMap(&constant));
+        assert!((shading_normal(&material, &patch(up)) - up).length() < 1e-9);
+
+        // A slope of 0.5 along x leans the normal back against it
+        let material = BumpMappedMaterial::new(&base, Perturbation::BumpMap(&Ramp));
+        let n = shading_normal(&material, &patch(up));
+        assert!((n - Vec3f::new(-0.5, 0.0, 1.0).normalize()).length() < 1e-9);
+
+        // Without a parameterization there is nothing to bump
+        let bare = Interaction::new_on_surface(Point3f::default(), 1.0, up, up);
+        assert!((shading_normal(&material, &bare) - up).length() < 1e-12);
+    }
+
+    #[test]
+    fn reflections_stay_above_surface() {
+        let albedo = ConstantTexture::new(Color3f::new(0.5, 0.5, 0.5));
+        let base = LambertianMaterial::new(&albedo);
+        let steep = ConstantTexture::new(Color3f::new(0.95, 0.5, 0.6));
+        let material = BumpMappedMaterial::new(&base, Perturbation::NormalMap(&steep));
+
+        let ng = Vec3f::new(0.0, 0.0, 1.0);
+        for wo in [
+            Vec3f::new(1.0, 0.0, 0.05),
+            Vec3f::new(1.0, 0.3, 0.5),
+            Vec3f::new(0.0, 1.0, 0.1),
+            Vec3f::new(-1.0, 0.0, 0.2),
+        ] {
+            let wo = wo.normalize();
+            let ns = shading_normal(&material, &patch(wo));
+            let reflected = ns * (2.0 * ns.dot(&wo)) - wo;
+            assert!(
+                reflected.dot(&ng) >= MIN_REFLECTION_COS.min(wo.z) - 1e-6,
+                "{:?}",
+                wo
+            );
+            assert!(ns.dot(&ng) > 0.0);
+        }
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,175 @@
+use crate::bxdfs::conductor_bxdf::ConductorBxDF;
+use crate::core::bsdf::Bsdf;
+use crate::core::fresnel::{ComplexIor, ThinFilm};
+use crate::core::interaction::Interaction;
+use crate::core::material::Material;
+use crate::core::microfacet::{roughness_to_alpha, MicrofacetDistribution};
+use crate::core::texture::Texture;
+use crate::microfacets::trowbridge_reitz_distribution::TrowbridgeReitzDistribution;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A metallic [Material], described by its complex index of refraction.
+/// [ComplexIor] has presets for common metals.
+pub struct ConductorMaterial<'a, D> {
+    ior: ComplexIor,
+
+    /// Builds the distribution of microfacet normals from its alpha along x and y
+    distribution: fn(f64, f64) -> D,
+
+    /// Perceptual roughness in [0.0, 1.0] along the x and y axes of the shading frame,
+    /// [None] for a perfect mirror
+    roughness: Option<(&'a dyn Texture<f64>, &'a dyn Texture<f64>)>,
+
+    /// The thickness in nanometers and index of refraction of an optional film on the metal
+    film: Option<(&'a dyn Texture<f64>, f64)>,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<'a> ConductorMaterial<'a, TrowbridgeReitzDistribution> {
+    /// Creates a perfect mirror, for which the distribution makes no difference.
+    pub fn new(ior: ComplexIor) -> Self {
+        Self {
+            ior,
+            distribution: TrowbridgeReitzDistribution::new,
+            roughness: None,
+            film: None,
+        }
+    }
+
+    /// Creates a perfect mirror from optical constants measured at `wavelengths`
+    /// in nanometers, sorted, as listed in tables of metals.
+    pub fn from_spectrum(wavelengths: &[f64], eta: &[f64], k: &[f64]) -> Self {
+        Self::new(ComplexIor::from_spectrum(wavelengths, eta, k))
+    }
+}
+
+impl<'a, D: MicrofacetDistribution> ConductorMaterial<'a, D> {
+    /// Creates a rough surface with microfacet normals following `distribution`,
+    /// given the alpha along the x and y axes mapped from `roughness_x` and `roughness_y`.
+    pub fn new_rough(
+        ior: ComplexIor,
+        distribution: fn(f64, f64) -> D,
+        roughness_x: &'a dyn Texture<f64>,
+        roughness_y: &'a dyn Texture<f64>,
+    ) -> Self {
+        Self {
+            ior,
+            distribution,
+            roughness: Some((roughness_x, roughness_y)),
+            film: None,
+        }
+    }
+
+    /// Adds a thin film of varying `thickness` in nanometers,
+    /// with `eta` its index of refraction relative to the outside.
+    pub fn with_thin_film(self, thickness: &'a dyn Texture<f64>, eta: f64) -> Self {
+        Self {
+            film: Some((thickness, eta)),
+            ..self
+        }
+    }
+}
+
+impl<'a, D: MicrofacetDistribution + 'static> Material for ConductorMaterial<'a, D> {
+    fn get_bsdf(&self, interaction: &Interaction) -> Option<Bsdf> {
+        let frame = interaction.shading_frame()?;
+
+        let (alpha_x, alpha_y) = self.roughness.map_or((0.0, 0.0), |(x, y)| {
+            (
+                roughness_to_alpha(x.evaluate(interaction)),
+                roughness_to_alpha(y.evaluate(interaction)),
+            )
+        });
+        let distribution = (self.distribution)(alpha_x, alpha_y);
+
+        let bxdf = ConductorBxDF::new(self.ior, distribution);
+        let bxdf = match self.film {
+            Some((thickness, eta)) => {
+                let thickness = thickness.evaluate(interaction).max(0.0);
+                bxdf.with_thin_film(ThinFilm::new(thickness, eta))
+            }
+            None => bxdf,
+        };
+
+        Some(Bsdf::new(frame, Box::new(bxdf)))
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::bxdf::BxDF;
+    use crate::core::fresnel::fr_conductor;
+    use crate::core::vector::{Color3f, Point2f, Point3f, Vec3f};
+    use crate::microfacets::beckmann_distribution::BeckmannDistribution;
+    use crate::textures::constant_texture::ConstantTexture;
+
+    #[test]
+    fn builds_conductor_bxdf() {
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let wo = Vec3f::new(0.6, 0.0, 0.8);
+        let wi = Vec3f::new(-0.48, 0.36, 0.8);
+        let interaction = Interaction::new_on_surface(Point3f::default(), 1.0, n, wo);
+
+        // The evaluated roughness along each axis becomes the distribution's alpha
+        let roughness_x = ConstantTexture::new(0.04);
+        let roughness_y = ConstantTexture::new(0.25);
+        let material = ConductorMaterial::new_rough(
+            ComplexIor::GOLD,
+            BeckmannDistribution::new,
+            &roughness_x,
+            &roughness_y,
+        );
+        let bsdf = material.get_bsdf(&interaction).unwrap();
+        let frame = bsdf.frame();
+        let distribution = BeckmannDistribution::new(0.2, 0.5);
+        let expected = ConductorBxDF::new(ComplexIor::GOLD, distribution)
+            .f(&frame.world_to_local(&wo), &frame.world_to_local(&wi));
+        assert!(!expected.is_black());
+        assert!((bsdf.f(&wo, &wi) - expected).length() < 1e-12);
+
+        // Without roughness it is a mirror tinted by the metal's Fresnel term
+        let material = ConductorMaterial::new(ComplexIor::COPPER);
+        let bsdf = material.get_bsdf(&interaction).unwrap();
+        let sample = bsdf.sample_f(&wo, 0.5, &Point2f::default()).unwrap();
+        assert!(sample.specular);
+        assert!((sample.wi - Vec3f::new(-0.6, 0.0, 0.8)).length() < 1e-12);
+        let fresnel = fr_conductor(0.8, &ComplexIor::COPPER);
+        assert!((sample.f * 0.8 - fresnel).length() < 1e-12);
+    }
+
+    #[test]
+    fn measured_spectrum() {
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let wo = Vec3f::new(0.6, 0.0, 0.8);
+        let interaction = Interaction::new_on_surface(Point3f::default(), 1.0, n, wo);
+
+        // Reflects with the constants found at the wavelengths color channels stand in for
+        let wavelengths = [450.0, 550.0, 650.0];
+        let material =
+            ConductorMaterial::from_spectrum(&wavelengths, &[1.5, 0.5, 0.2], &[1.9, 2.5, 3.6]);
+        let bsdf = material.get_bsdf(&interaction).unwrap();
+        let sample = bsdf.sample_f(&wo, 0.5, &Point2f::default()).unwrap();
+        let ior = ComplexIor::new(
+            Color3f::new(0.26, 0.68, 1.35),
+            Color3f::new(3.38, 2.392, 1.99),
+        );
+        assert!((sample.f * 0.8 - fr_conductor(0.8, &ior)).length() < 1e-9);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,187 @@
+use crate::bxdfs::dielectric_bxdf::DielectricBxDF;
+use crate::core::bsdf::Bsdf;
+use crate::core::fresnel::ThinFilm;
+use crate::core::interaction::Interaction;
+use crate::core::material::Material;
+use crate::core::microfacet::{roughness_to_alpha, MicrofacetDistribution};
+use crate::core::texture::Texture;
+use crate::microfacets::trowbridge_reitz_distribution::TrowbridgeReitzDistribution;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A transparent [Material] such as glass or water.
+/// Assumes the [Primitive](crate::core::primitive::Primitive) it is applied to
+/// is closed and reports outward facing normals.
+pub struct DielectricMaterial<'a, D> {
+    /// The index of refraction of the interior, relative to the exterior
+    eta: f64,
+
+    /// Builds the distribution of microfacet normals from its alpha along x and y
+    distribution: fn(f64, f64) -> D,
+
+    /// Perceptual roughness in [0.0, 1.0] along the x and y axes of the shading frame,
+    /// [None] for a perfectly smooth interface
+    roughness: Option<(&'a dyn Texture<f64>, &'a dyn Texture<f64>)>,
+
+    /// The thickness in nanometers and index of refraction of an optional film on the outside
+    film: Option<(&'a dyn Texture<f64>, f64)>,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<'a> DielectricMaterial<'a, TrowbridgeReitzDistribution> {
+    /// Creates a perfectly smooth interface, for which the distribution makes no difference.
+    pub fn new(eta: f64) -> Self {
+        Self {
+            eta,
+            distribution: TrowbridgeReitzDistribution::new,
+            roughness: None,
+            film: None,
+        }
+    }
+}
+
+impl<'a, D: MicrofacetDistribution> DielectricMaterial<'a, D> {
+    /// Creates a rough surface with microfacet normals following `distribution`,
+    /// given the alpha along the x and y axes mapped from `roughness_x` and `roughness_y`.
+    pub fn new_rough(
+        eta: f64,
+        distribution: fn(f64, f64) -> D,
+        roughness_x: &'a dyn Texture<f64>,
+        roughness_y: &'a dyn Texture<f64>,
+    ) -> Self {
+        Self {
+            eta,
+            distribution,
+            roughness: Some((roughness_x, roughness_y)),
+            film: None,
+        }
+    }
+
+    /// Adds a thin film of varying `thickness` in nanometers,
+    /// with `eta` its index of refraction relative to the outside.
+    pub fn with_thin_film(self, thickness: &'a dyn Texture<f64>, eta: f64) -> Self {
+        Self {
+            film: Some((thickness, eta)),
+            ..self
+        }
+    }
+}
+
+impl<'a, D: MicrofacetDistribution + 'static> Material for DielectricMaterial<'a, D> {
+    fn get_bsdf(&self, interaction: &Interaction) -> Option<Bsdf> {
+        let frame = interaction.shading_frame()?;
+
+        let (alpha_x, alpha_y) = self.roughness.map_or((0.0, 0.0), |(x, y)| {
+            (
+                roughness_to_alpha(x.evaluate(interaction)),
+                roughness_to_alpha(y.evaluate(interaction)),
+            )
+        });
+        let distribution = (self.distribution)(alpha_x, alpha_y);
+
+        let bxdf = DielectricBxDF::new(self.eta, distribution);
+        let bxdf = match self.film {
+            Some((thickness, eta)) => {
+                let thickness = thickness.evaluate(interaction).max(0.0);
+                bxdf.with_thin_film(ThinFilm::new(thickness, eta))
+            }
+            None => bxdf,
+        };
+
+        Some(Bsdf::new(frame, Box::new(bxdf)))
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::bxdf::BxDF;
+    use crate::core::primitive::Primitive;
+    use crate::core::ray::Ray;
+    use crate::core::vector::{Point2f, Point3f, Vec3f};
+    use crate::microfacets::beckmann_distribution::BeckmannDistribution;
+    use crate::primitives::sphere::Sphere;
+    use crate::textures::constant_texture::ConstantTexture;
+
+    #[test]
+    fn refracts_out_of_sphere_interior() {
+        let sphere = Sphere::new(Point3f::new(0.0, 0.0, 0.0), 1.0);
+        let material = DielectricMaterial::new(1.5);
+
+        // Same setup as the sphere's inside out hit, the normal points away from the ray
+        let ray = Ray::new(
+            Point3f::new(0.0, 0.0, 0.0),
+            Vec3f::new(0.0, 1.0, 0.0),
+            0.0,
+            100.0,
+        );
+        let interaction = sphere.test(&ray).unwrap();
+        let bsdf = material.get_bsdf(&interaction).unwrap();
+
+        let sample = bsdf
+            .sample_f(&interaction.wo, 0.999, &Point2f::default())
+            .unwrap();
+        assert!((sample.wi - Vec3f::new(0.0, 1.0, 0.0)).length() < 1e-9);
+        assert_eq!(sample.eta, 1.0 / 1.5);
+    }
+
+    #[test]
+    fn builds_rough_dielectric_bxdf() {
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let wo = Vec3f::new(0.6, 0.0, 0.8);
+        let interaction = Interaction::new_on_surface(Point3f::default(), 1.0, n, wo);
+
+        // The evaluated roughness along each axis becomes the distribution's alpha
+        let roughness_x = ConstantTexture::new(0.09);
+        let roughness_y = ConstantTexture::new(0.36);
+        let material = DielectricMaterial::new_rough(
+            1.5,
+            BeckmannDistribution::new,
+            &roughness_x,
+            &roughness_y,
+        );
+        let bsdf = material.get_bsdf(&interaction).unwrap();
+        let frame = bsdf.frame();
+        let expected = DielectricBxDF::new(1.5, BeckmannDistribution::new(0.3, 0.6));
+        for wi in [
+            Vec3f::new(-0.48, 0.36, 0.8),
+            Vec3f::new(-0.3, 0.2, -0.9).normalize(),
+        ] {
+            let f = expected.f(&frame.world_to_local(&wo), &frame.world_to_local(&wi));
+            assert!(!f.is_black());
+            assert!((bsdf.f(&wo, &wi) - f).length() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn soap_bubble_is_iridescent() {
+        let thickness = ConstantTexture::new(350.0);
+        let material = DielectricMaterial::new(1.0).with_thin_film(&thickness, 1.33);
+
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let wo = Vec3f::new(0.6, 0.0, 0.8);
+        let interaction = Interaction::new_on_surface(Point3f::default(), 1.0, n, wo);
+        let bsdf = material.get_bsdf(&interaction).unwrap();
+
+        // A bare interface with eta 1.0 would not reflect at all
+        let sample = bsdf.sample_f(&wo, 0.0, &Point2f::default()).unwrap();
+        assert!(sample.wi.z > 0.0);
+        assert!(sample.f.max_component() > 0.0);
+        assert!((sample.f.x - sample.f.y).abs() > 1e-3 || (sample.f.y - sample.f.z).abs() > 1e-3);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,76 @@
+use crate::bxdfs::lambertian_bxdf::LambertianBxDF;
+use crate::core::bsdf::Bsdf;
+use crate::core::interaction::Interaction;
+use crate::core::material::Material;
+use crate::core::texture::Texture;
+use crate::core::vector::Color3f;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A perfectly diffuse [Material].
+pub struct LambertianMaterial<'a> {
+    albedo: &'a dyn Texture<Color3f>,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<'a> LambertianMaterial<'a> {
+    pub fn new(albedo: &'a dyn Texture<Color3f>) -> Self {
+        Self { albedo }
+    }
+}
+
+impl<'a> Material for LambertianMaterial<'a> {
+    fn get_bsdf(&self, interaction: &Interaction) -> Option<Bsdf> {
+        let frame = interaction.shading_frame()?;
+        let albedo = self.albedo.evaluate(interaction);
+        Some(Bsdf::new(frame, Box::new(LambertianBxDF::new(albedo))))
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::{Point2f, Point3f, Vec3f};
+    use crate::textures::constant_texture::ConstantTexture;
+
+    #[test]
+    fn scatters_about_world_normal() {
+        let albedo = ConstantTexture::new(Color3f::new(0.5, 0.5, 0.5));
+        let material = LambertianMaterial::new(&albedo);
+
+        let n = Vec3f::new(1.0, 0.0, 0.0);
+        let wo = Vec3f::new(1.0, 1.0, 0.0).normalize();
+        let interaction = Interaction::new_on_surface(Point3f::default(), 1.0, n, wo);
+        let bsdf = material.get_bsdf(&interaction).unwrap();
+
+        let sample = bsdf.sample_f(&wo, 0.5, &Point2f::new(0.3, 0.7)).unwrap();
+        assert!(sample.wi.dot(&n) > 0.0);
+        assert!(bsdf.f(&wo, &-n).is_black());
+        assert!((bsdf.pdf(&wo, &n) - std::f64::consts::FRAC_1_PI).abs() < 1e-9);
+    }
+
+    #[test]
+    fn no_bsdf_in_media() {
+        let albedo = ConstantTexture::new(Color3f::new(0.5, 0.5, 0.5));
+        let material = LambertianMaterial::new(&albedo);
+
+        let interaction =
+            Interaction::new_in_media(Point3f::default(), 1.0, Vec3f::new(0.0, 0.0, 1.0));
+        assert!(material.get_bsdf(&interaction).is_none());
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,138 @@
+use crate::bxdfs::dielectric_bxdf::DielectricBxDF;
+use crate::bxdfs::layered_bxdf::LayeredBxDF;
+use crate::core::bsdf::Bsdf;
+use crate::core::interaction::Interaction;
+use crate::core::material::Material;
+use crate::core::microfacet::roughness_to_alpha;
+use crate::core::texture::Texture;
+use crate::core::vector::Color3f;
+use crate::microfacets::trowbridge_reitz_distribution::TrowbridgeReitzDistribution;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A clear dielectric coating over any other [Material],
+/// for varnished wood, coated plastic or car paint.
+/// Whatever the base transmits is absorbed.
+pub struct LayeredMaterial<'a> {
+    base: &'a dyn Material,
+
+    /// The index of refraction of the coating
+    eta: f64,
+
+    /// Perceptual roughness of the coating in [0.0, 1.0], [None] for a perfectly smooth one
+    roughness: Option<&'a dyn Texture<f64>>,
+
+    /// The thickness of the coating
+    thickness: &'a dyn Texture<f64>,
+
+    /// The absorption coefficient of the coating, per unit thickness
+    absorption: &'a dyn Texture<Color3f>,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<'a> LayeredMaterial<'a> {
+    pub fn new(
+        base: &'a dyn Material,
+        eta: f64,
+        thickness: &'a dyn Texture<f64>,
+        absorption: &'a dyn Texture<Color3f>,
+    ) -> Self {
+        Self {
+            base,
+            eta,
+            roughness: None,
+            thickness,
+            absorption,
+        }
+    }
+
+    pub fn new_rough(
+        base: &'a dyn Material,
+        eta: f64,
+        roughness: &'a dyn Texture<f64>,
+        thickness: &'a dyn Texture<f64>,
+        absorption: &'a dyn Texture<Color3f>,
+    ) -> Self {
+        Self {
+            base,
+            eta,
+            roughness: Some(roughness),
+            thickness,
+            absorption,
+        }
+    }
+}
+
+impl<'a> Material for LayeredMaterial<'a> {
+    fn get_bsdf(&self, interaction: &Interaction) -> Option<Bsdf> {
+        // The coating follows the base's shading frame
+        let base = self.base.get_bsdf(interaction)?;
+        let frame = *base.frame();
+
+        let alpha = self.roughness.map_or(0.0, |roughness| {
+            roughness_to_alpha(roughness.evaluate(interaction))
+        });
+        let top = DielectricBxDF::new(self.eta, TrowbridgeReitzDistribution::new(alpha, alpha));
+
+        let thickness = self.thickness.evaluate(interaction).max(0.0);
+        let absorption = self.absorption.evaluate(interaction).map(|a| a.max(0.0));
+
+        Some(Bsdf::new(
+            frame,
+            Box::new(LayeredBxDF::new(
+                top,
+                base.into_bxdf(),
+                thickness,
+                absorption,
+            )),
+        ))
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::{Point2f, Point3f, Vec3f};
+    use crate::materials::lambertian_material::LambertianMaterial;
+    use crate::textures::constant_texture::ConstantTexture;
+
+    #[test]
+    fn coats_base_material() {
+        let albedo = ConstantTexture::new(Color3f::new(0.8, 0.2, 0.2));
+        let base = LambertianMaterial::new(&albedo);
+        let thickness = ConstantTexture::new(0.1);
+        let absorption = ConstantTexture::new(Color3f::default());
+        let material = LayeredMaterial::new(&base, 1.5, &thickness, &absorption);
+
+        let n = Vec3f::new(1.0, 0.0, 0.0);
+        let wo = Vec3f::new(1.0, 1.0, 0.0).normalize();
+        let interaction = Interaction::new_on_surface(Point3f::default(), 1.0, n, wo);
+        let bsdf = material.get_bsdf(&interaction).unwrap();
+
+        // The smooth coating gives a mirror reflection
+        let reflected = bsdf.sample_f(&wo, 0.0, &Point2f::new(0.3, 0.7)).unwrap();
+        assert!(reflected.specular);
+        assert!((reflected.wi - Vec3f::new(1.0, -1.0, 0.0).normalize()).length() < 1e-9);
+
+        // Over the base's diffuse color
+        let wi = Vec3f::new(1.0, 0.0, 0.5).normalize();
+        let f = bsdf.f(&wo, &wi);
+        assert!(f.x > f.y && f.y > 0.0);
+        assert!(bsdf.f(&wo, &-n).is_black());
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,8 @@
+pub mod bump_mapped_material;
+pub mod conductor_material;
+pub mod dielectric_material;
+pub mod lambertian_material;
+pub mod layered_material;
+pub mod oren_nayar_material;
+pub mod principled_material;
+pub mod subsurface_material;
@@ -0,0 +1,86 @@
+use crate::bxdfs::oren_nayar_bxdf::OrenNayarBxDF;
+use crate::core::bsdf::Bsdf;
+use crate::core::interaction::Interaction;
+use crate::core::material::Material;
+use crate::core::texture::Texture;
+use crate::core::vector::Color3f;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A rough diffuse [Material], for clay-like surfaces.
+pub struct OrenNayarMaterial<'a> {
+    albedo: &'a dyn Texture<Color3f>,
+
+    /// The standard deviation of the microfacet angle, in degrees
+    sigma: &'a dyn Texture<f64>,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<'a> OrenNayarMaterial<'a> {
+    pub fn new(albedo: &'a dyn Texture<Color3f>, sigma: &'a dyn Texture<f64>) -> Self {
+        Self { albedo, sigma }
+    }
+}
+
+impl<'a> Material for OrenNayarMaterial<'a> {
+    fn get_bsdf(&self, interaction: &Interaction) -> Option<Bsdf> {
+        let frame = interaction.shading_frame()?;
+        let albedo = self.albedo.evaluate(interaction);
+        let sigma = self.sigma.evaluate(interaction);
+        Some(Bsdf::new(
+            frame,
+            Box::new(OrenNayarBxDF::new(albedo, sigma)),
+        ))
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_1_PI;
+
+    use super::*;
+    use crate::core::bxdf::BxDF;
+    use crate::core::vector::{Point3f, Vec3f};
+    use crate::textures::constant_texture::ConstantTexture;
+
+    #[test]
+    fn roughness_from_texture() {
+        let albedo = ConstantTexture::new(Color3f::new(0.5, 0.5, 0.5));
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let wo = Vec3f::new(0.6, 0.0, 0.8);
+        let wi = Vec3f::new(-0.48, 0.36, 0.8);
+        let interaction = Interaction::new_on_surface(Point3f::default(), 1.0, n, wo);
+
+        let sigma = ConstantTexture::new(20.0);
+        let material = OrenNayarMaterial::new(&albedo, &sigma);
+        let bsdf = material.get_bsdf(&interaction).unwrap();
+        let frame = bsdf.frame();
+        let expected = OrenNayarBxDF::new(Color3f::new(0.5, 0.5, 0.5), 20.0)
+            .f(&frame.world_to_local(&wo), &frame.world_to_local(&wi));
+        assert!((bsdf.f(&wo, &wi) - expected).length() < 1e-12);
+
+        // Perfectly smooth facets reflect like a Lambertian surface
+        let smooth = ConstantTexture::new(0.0);
+        let material = OrenNayarMaterial::new(&albedo, &smooth);
+        let bsdf = material.get_bsdf(&interaction).unwrap();
+        for wi in [wi, n, Vec3f::new(0.0, 0.6, 0.8)] {
+            let f = bsdf.f(&wo, &wi);
+            assert!((f - Color3f::new(0.5, 0.5, 0.5) * FRAC_1_PI).length() < 1e-12);
+        }
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,126 @@
+use crate::bxdfs::principled_bxdf::{PrincipledBxDF, PrincipledParameters};
+use crate::core::bsdf::Bsdf;
+use crate::core::interaction::Interaction;
+use crate::core::material::Material;
+use crate::core::texture::Texture;
+use crate::core::vector::Color3f;
+use crate::textures::constant_texture::ConstantTexture;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// The textures feeding each [PrincipledParameters] input.
+/// Start from [PrincipledTextures::new] and override the inputs an asset authors.
+#[derive(Clone, Copy)]
+pub struct PrincipledTextures<'a> {
+    pub base_color: &'a dyn Texture<Color3f>,
+    pub metallic: &'a dyn Texture<f64>,
+    pub roughness: &'a dyn Texture<f64>,
+    pub specular: &'a dyn Texture<f64>,
+    pub specular_tint: &'a dyn Texture<f64>,
+    pub anisotropic: &'a dyn Texture<f64>,
+    pub sheen: &'a dyn Texture<f64>,
+    pub sheen_tint: &'a dyn Texture<f64>,
+    pub clearcoat: &'a dyn Texture<f64>,
+    pub clearcoat_gloss: &'a dyn Texture<f64>,
+    pub transmission: &'a dyn Texture<f64>,
+}
+
+/// A [Material] following the Disney principled BSDF,
+/// matching what assets from glTF and similar pipelines are authored against.
+pub struct PrincipledMaterial<'a> {
+    textures: PrincipledTextures<'a>,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+static ZERO: ConstantTexture<f64> = ConstantTexture::new(0.0);
+static HALF: ConstantTexture<f64> = ConstantTexture::new(0.5);
+static ONE: ConstantTexture<f64> = ConstantTexture::new(1.0);
+
+impl<'a> PrincipledTextures<'a> {
+    /// A rough dielectric with the given base color and the model's other defaults.
+    pub fn new(base_color: &'a dyn Texture<Color3f>) -> Self {
+        Self {
+            base_color,
+            metallic: &ZERO,
+            roughness: &HALF,
+            specular: &HALF,
+            specular_tint: &ZERO,
+            anisotropic: &ZERO,
+            sheen: &ZERO,
+            sheen_tint: &HALF,
+            clearcoat: &ZERO,
+            clearcoat_gloss: &ONE,
+            transmission: &ZERO,
+        }
+    }
+
+    pub fn evaluate(&self, interaction: &Interaction) -> PrincipledParameters {
+        let unit = |texture: &dyn Texture<f64>| texture.evaluate(interaction).clamp(0.0, 1.0);
+
+        PrincipledParameters {
+            base_color: self.base_color.evaluate(interaction),
+            metallic: unit(self.metallic),
+            roughness: unit(self.roughness),
+            specular: unit(self.specular),
+            specular_tint: unit(self.specular_tint),
+            anisotropic: unit(self.anisotropic),
+            sheen: unit(self.sheen),
+            sheen_tint: unit(self.sheen_tint),
+            clearcoat: unit(self.clearcoat),
+            clearcoat_gloss: unit(self.clearcoat_gloss),
+            transmission: unit(self.transmission),
+        }
+    }
+}
+
+impl<'a> PrincipledMaterial<'a> {
+    pub fn new(textures: PrincipledTextures<'a>) -> Self {
+        Self { textures }
+    }
+}
+
+impl<'a> Material for PrincipledMaterial<'a> {
+    fn get_bsdf(&self, interaction: &Interaction) -> Option<Bsdf> {
+        let frame = interaction.shading_frame()?;
+        let parameters = self.textures.evaluate(interaction);
+        Some(Bsdf::new(frame, Box::new(PrincipledBxDF::new(&parameters))))
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::{Point3f, Vec3f};
+
+    #[test]
+    fn defaults_match_parameters() {
+        let base_color = ConstantTexture::new(Color3f::new(0.8, 0.8, 0.8));
+        let textures = PrincipledTextures::new(&base_color);
+
+        let interaction = Interaction::new_on_surface(
+            Point3f::default(),
+            1.0,
+            Vec3f::new(0.0, 0.0, 1.0),
+            Vec3f::new(0.0, 0.0, 1.0),
+        );
+        assert_eq!(
+            textures.evaluate(&interaction),
+            PrincipledParameters::default()
+        );
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
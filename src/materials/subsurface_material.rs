@@ -0,0 +1,243 @@
+use crate::bssrdfs::random_walk_bssrdf::{coefficients_from_albedo, RandomWalkBssrdf};
+use crate::bxdfs::dielectric_bxdf::DielectricBxDF;
+use crate::core::bsdf::Bsdf;
+use crate::core::bssrdf::Bssrdf;
+use crate::core::interaction::Interaction;
+use crate::core::material::Material;
+use crate::core::microfacet::roughness_to_alpha;
+use crate::core::texture::Texture;
+use crate::core::vector::Color3f;
+use crate::microfacets::trowbridge_reitz_distribution::TrowbridgeReitzDistribution;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A translucent [Material] such as skin, marble, wax or milk.
+/// Light refracted through the surface takes a random walk through the interior,
+/// so the [Primitive](crate::core::primitive::Primitive) it is applied to
+/// must be closed and report outward facing normals.
+pub struct SubsurfaceMaterial<'a> {
+    /// The index of refraction of the interior, relative to the exterior
+    eta: f64,
+
+    /// Perceptual roughness in [0.0, 1.0], [None] for a perfectly smooth surface
+    roughness: Option<&'a dyn Texture<f64>>,
+
+    coefficients: Coefficients<'a>,
+}
+
+/// How the medium inside is described.
+enum Coefficients<'a> {
+    /// Absorption and scattering coefficients, per unit distance once multiplied by `scale`
+    Sigma {
+        sigma_a: &'a dyn Texture<Color3f>,
+        sigma_s: &'a dyn Texture<Color3f>,
+        scale: f64,
+    },
+
+    /// The color after many scattering events, and how far light travels to get there
+    Albedo {
+        albedo: &'a dyn Texture<Color3f>,
+        mean_free_path: &'a dyn Texture<Color3f>,
+    },
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<'a> SubsurfaceMaterial<'a> {
+    /// `scale` converts the coefficients to scene units, as they are usually measured per mm.
+    pub fn new(
+        eta: f64,
+        sigma_a: &'a dyn Texture<Color3f>,
+        sigma_s: &'a dyn Texture<Color3f>,
+        scale: f64,
+    ) -> Self {
+        Self {
+            eta,
+            roughness: None,
+            coefficients: Coefficients::Sigma {
+                sigma_a,
+                sigma_s,
+                scale,
+            },
+        }
+    }
+
+    pub fn new_from_albedo(
+        eta: f64,
+        albedo: &'a dyn Texture<Color3f>,
+        mean_free_path: &'a dyn Texture<Color3f>,
+    ) -> Self {
+        Self {
+            eta,
+            roughness: None,
+            coefficients: Coefficients::Albedo {
+                albedo,
+                mean_free_path,
+            },
+        }
+    }
+
+    pub fn with_roughness(self, roughness: &'a dyn Texture<f64>) -> Self {
+        Self {
+            roughness: Some(roughness),
+            ..self
+        }
+    }
+
+    fn boundary(&self, interaction: &Interaction) -> DielectricBxDF<TrowbridgeReitzDistribution> {
+        let alpha = self.roughness.map_or(0.0, |roughness| {
+            roughness_to_alpha(roughness.evaluate(interaction))
+        });
+        DielectricBxDF::new(self.eta, TrowbridgeReitzDistribution::new(alpha, alpha))
+    }
+}
+
+impl<'a> Material for SubsurfaceMaterial<'a> {
+    fn get_bsdf(&self, interaction: &Interaction) -> Option<Bsdf> {
+        let frame = interaction.shading_frame()?;
+        Some(Bsdf::new(frame, Box::new(self.boundary(interaction))))
+    }
+
+    fn get_bssrdf(&self, interaction: &Interaction) -> Option<Box<dyn Bssrdf>> {
+        interaction.n?;
+
+        let non_negative = |c: Color3f| c.map(|v| v.max(0.0));
+        let (sigma_a, sigma_s) = match &self.coefficients {
+            Coefficients::Sigma {
+                sigma_a,
+                sigma_s,
+                scale,
+            } => (
+                non_negative(sigma_a.evaluate(interaction)) * *scale,
+                non_negative(sigma_s.evaluate(interaction)) * *scale,
+            ),
+            Coefficients::Albedo {
+                albedo,
+                mean_free_path,
+            } => coefficients_from_albedo(
+                &albedo.evaluate(interaction),
+                &mean_free_path.evaluate(interaction),
+            ),
+        };
+
+        Some(Box::new(RandomWalkBssrdf::new(
+            self.boundary(interaction),
+            sigma_a,
+            sigma_s,
+        )))
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accelerators::simple_list::SimpleList;
+    use crate::core::primitive::Primitive;
+    use crate::core::ray::Ray;
+    use crate::core::vector::{Point2f, Point3f, Vec3f};
+    use crate::materials::lambertian_material::LambertianMaterial;
+    use crate::primitives::geometric_primitive::GeometricPrimitive;
+    use crate::primitives::sphere::Sphere;
+    use crate::textures::constant_texture::ConstantTexture;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn refracted_light_leaves_elsewhere() {
+        let albedo = ConstantTexture::new(Color3f::new(0.8, 0.6, 0.4));
+        let mean_free_path = ConstantTexture::new(Color3f::new(0.5, 0.3, 0.2));
+        let material = SubsurfaceMaterial::new_from_albedo(1.33, &albedo, &mean_free_path);
+
+        let sphere = Sphere::new(Point3f::new(0.0, 0.0, 0.0), 1.0);
+        let primitives = vec![&sphere as &dyn Primitive];
+        let accelerator = SimpleList::new(&primitives);
+
+        let ray = Ray::new(
+            Point3f::new(0.0, 0.0, 2.0),
+            Vec3f::new(0.0, 0.0, -1.0),
+            0.0,
+            100.0,
+        );
+        let entry = sphere.test(&ray).unwrap();
+
+        // Refract into the surface, then walk through it
+        let bsdf = material.get_bsdf(&entry).unwrap();
+        let refracted = bsdf
+            .sample_f(&entry.wo, 0.999, &Point2f::default())
+            .unwrap();
+        assert!(refracted.wi.z < 0.0);
+
+        let bssrdf = material.get_bssrdf(&entry).unwrap();
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let exit = (0..100)
+            .find_map(|_| bssrdf.sample_exit(&entry, &refracted.wi, &accelerator, &mut rng))
+            .unwrap();
+        assert!((exit.interaction.p - entry.p).length() > 1e-3);
+        let leaving = exit
+            .bsdf
+            .sample_f(&exit.interaction.wo, 0.5, &Point2f::new(0.3, 0.6))
+            .unwrap();
+        assert!(leaving.wi.dot(&exit.interaction.n.unwrap()) > 0.0);
+    }
+
+    #[test]
+    fn walks_pass_through_objects_inside() {
+        let albedo = ConstantTexture::new(Color3f::new(0.9, 0.9, 0.9));
+        let mean_free_path = ConstantTexture::new(Color3f::new(0.2, 0.2, 0.2));
+        let material = SubsurfaceMaterial::new_from_albedo(1.33, &albedo, &mean_free_path);
+        let sphere = Sphere::new(Point3f::new(0.0, 0.0, 0.0), 1.0);
+        let medium = GeometricPrimitive::new(&sphere, &material);
+
+        // A diffuse ball suspended in the middle of the medium
+        let reflectance = ConstantTexture::new(Color3f::new(0.5, 0.5, 0.5));
+        let diffuse = LambertianMaterial::new(&reflectance);
+        let core = Sphere::new(Point3f::new(0.0, 0.0, 0.0), 0.5);
+        let suspended = GeometricPrimitive::new(&core, &diffuse);
+        let primitives = vec![&medium as &dyn Primitive, &suspended as &dyn Primitive];
+        let accelerator = SimpleList::new(&primitives);
+
+        let ray = Ray::new(
+            Point3f::new(0.0, 0.0, 2.0),
+            Vec3f::new(0.0, 0.0, -1.0),
+            0.0,
+            100.0,
+        );
+        let entry = medium.test(&ray).unwrap();
+        let bssrdf = material.get_bssrdf(&entry).unwrap();
+
+        // Walks only leave through the surface of the medium they entered
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let down = Vec3f::new(0.0, 0.0, -1.0);
+        let exits: Vec<_> = (0..200)
+            .filter_map(|_| bssrdf.sample_exit(&entry, &down, &accelerator, &mut rng))
+            .collect();
+        assert!(exits.len() > 50);
+        for exit in &exits {
+            assert!((exit.interaction.p.length() - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn no_bssrdf_in_media() {
+        let sigma = ConstantTexture::new(Color3f::new(1.0, 1.0, 1.0));
+        let material = SubsurfaceMaterial::new(1.33, &sigma, &sigma, 1.0);
+
+        let interaction =
+            Interaction::new_in_media(Point3f::default(), 1.0, Vec3f::new(0.0, 0.0, 1.0));
+        assert!(material.get_bssrdf(&interaction).is_none());
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,157 @@
+use std::ops::{Add, Div, Mul, Sub};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A complex number, just enough to evaluate Fresnel terms of absorbing media.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl Complex {
+    pub fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    pub fn from_real(re: f64) -> Self {
+        Self { re, im: 0.0 }
+    }
+
+    /// The squared magnitude
+    pub fn norm(&self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+
+    pub fn abs(&self) -> f64 {
+        self.norm().sqrt()
+    }
+
+    pub fn conj(&self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+
+    /// The principal square root
+    pub fn sqrt(&self) -> Self {
+        let n = self.abs();
+        if n == 0.0 {
+            return Self::default();
+        }
+
+        let t1 = (0.5 * (n + self.re.abs())).sqrt();
+        let t2 = 0.5 * self.im / t1;
+        if self.re >= 0.0 {
+            Self::new(t1, t2)
+        } else {
+            Self::new(t2.abs(), t1.copysign(self.im))
+        }
+    }
+
+    /// e^(i * theta)
+    pub fn from_phase(theta: f64) -> Self {
+        Self::new(theta.cos(), theta.sin())
+    }
+
+    pub fn exp(&self) -> Self {
+        Self::from_phase(self.im) * self.re.exp()
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl Mul<f64> for Complex {
+    type Output = Self;
+
+    fn mul(self, scalar: f64) -> Self {
+        Self::new(self.re * scalar, self.im * scalar)
+    }
+}
+
+impl Div for Complex {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        let scale = 1.0 / other.norm();
+        Self::new(
+            scale * (self.re * other.re + self.im * other.im),
+            scale * (self.im * other.re - self.re * other.im),
+        )
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Complex::new(1.0, 2.0);
+        let b = Complex::new(3.0, -1.0);
+        assert_eq!(a + b, Complex::new(4.0, 1.0));
+        assert_eq!(a - b, Complex::new(-2.0, 3.0));
+        assert_eq!(a * b, Complex::new(5.0, 5.0));
+        let q = (a * b) / b;
+        assert!((q - a).abs() < 1e-12);
+    }
+
+    #[test]
+    fn sqrt() {
+        for z in [
+            Complex::new(4.0, 0.0),
+            Complex::new(-4.0, 0.0),
+            Complex::new(3.0, 4.0),
+            Complex::new(-3.0, -4.0),
+        ] {
+            let root = z.sqrt();
+            assert!(root.re >= 0.0);
+            assert!((root * root - z).abs() < 1e-12, "{:?}", z);
+        }
+    }
+
+    #[test]
+    fn exp() {
+        let z = Complex::new(1.0, std::f64::consts::PI).exp();
+        assert!((z - Complex::from_real(-std::f64::consts::E)).abs() < 1e-12);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
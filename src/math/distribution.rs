@@ -0,0 +1,191 @@
+use crate::core::vector::Point2f;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A piecewise-constant density over [0.0, 1.0) proportional to a tabulated function,
+/// for importance sampling things like image rows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distribution1D {
+    func: Vec<f64>,
+    cdf: Vec<f64>,
+    integral: f64,
+}
+
+/// A piecewise-constant density over [0.0, 1.0)^2, sampled by picking a row
+/// from the marginal density and then a column within it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distribution2D {
+    conditional: Vec<Distribution1D>,
+    marginal: Distribution1D,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl Distribution1D {
+    /// Negative values count as their magnitude. A function that is zero everywhere
+    /// is sampled uniformly, but reports a density of 0.0.
+    pub fn new(func: &[f64]) -> Self {
+        assert!(!func.is_empty(), "distribution must not be empty");
+        let func: Vec<f64> = func.iter().map(|f| f.abs()).collect();
+        let n = func.len() as f64;
+
+        let mut cdf = Vec::with_capacity(func.len() + 1);
+        cdf.push(0.0);
+        for f in &func {
+            cdf.push(cdf.last().unwrap() + f / n);
+        }
+        let integral = *cdf.last().unwrap();
+
+        for (i, c) in cdf.iter_mut().enumerate() {
+            *c = if integral > 0.0 {
+                *c / integral
+            } else {
+                i as f64 / n
+            };
+        }
+        Self {
+            func,
+            cdf,
+            integral,
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.func.len()
+    }
+
+    /// The average of the function over [0.0, 1.0).
+    pub fn integral(&self) -> f64 {
+        self.integral
+    }
+
+    /// Maps `u` in [0.0, 1.0) to a point, returned with its density and the piece it lies in.
+    pub fn sample_continuous(&self, u: f64) -> (f64, f64, usize) {
+        let offset = (self.cdf.partition_point(|&c| c <= u).max(1) - 1).min(self.count() - 1);
+
+        let width = self.cdf[offset + 1] - self.cdf[offset];
+        let du = if width > 0.0 {
+            (u - self.cdf[offset]) / width
+        } else {
+            0.0
+        };
+        let pdf = if self.integral > 0.0 {
+            self.func[offset] / self.integral
+        } else {
+            0.0
+        };
+        ((offset as f64 + du) / self.count() as f64, pdf, offset)
+    }
+
+    /// The density of [Distribution1D::sample_continuous] returning `x`.
+    pub fn pdf(&self, x: f64) -> f64 {
+        if self.integral == 0.0 {
+            return 0.0;
+        }
+        self.func[self.piece(x)] / self.integral
+    }
+
+    fn piece(&self, x: f64) -> usize {
+        ((x * self.count() as f64).max(0.0) as usize).min(self.count() - 1)
+    }
+}
+
+impl Distribution2D {
+    /// `func` holds `height` rows of `width` values each, with `u` running along rows.
+    pub fn new(func: &[f64], width: usize, height: usize) -> Self {
+        assert_eq!(func.len(), width * height, "value count must match size");
+        let conditional: Vec<Distribution1D> =
+            func.chunks(width).map(Distribution1D::new).collect();
+        let marginal: Vec<f64> = conditional.iter().map(|row| row.integral()).collect();
+        Self {
+            conditional,
+            marginal: Distribution1D::new(&marginal),
+        }
+    }
+
+    /// Maps `u` in [0.0, 1.0)^2 to a point, returned with its density.
+    pub fn sample_continuous(&self, u: &Point2f) -> (Point2f, f64) {
+        let (y, pdf_y, row) = self.marginal.sample_continuous(u.y);
+        let (x, pdf_x, _) = self.conditional[row].sample_continuous(u.x);
+        (Point2f::new(x, y), pdf_x * pdf_y)
+    }
+
+    /// The density of [Distribution2D::sample_continuous] returning `p`.
+    pub fn pdf(&self, p: &Point2f) -> f64 {
+        if self.marginal.integral() == 0.0 {
+            return 0.0;
+        }
+        let row = &self.conditional[self.marginal.piece(p.y)];
+        row.func[row.piece(p.x)] / self.marginal.integral()
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_proportionally() {
+        let distribution = Distribution1D::new(&[1.0, 3.0, 0.0, 4.0]);
+        assert_eq!(distribution.integral(), 2.0);
+
+        // A quarter of the samples land in the first piece, none in the empty third
+        let (x, pdf, offset) = distribution.sample_continuous(0.0625);
+        assert_eq!((x, pdf, offset), (0.125, 0.5, 0));
+        let (x, pdf, offset) = distribution.sample_continuous(0.5);
+        assert_eq!((x, pdf, offset), (0.75, 2.0, 3));
+        let (x, _, offset) = distribution.sample_continuous(0.999);
+        assert!(x < 1.0 && offset == 3);
+
+        assert_eq!(distribution.pdf(0.3), 1.5);
+        assert_eq!(distribution.pdf(0.6), 0.0);
+        assert_eq!(distribution.pdf(1.0), 2.0);
+    }
+
+    #[test]
+    fn zero_function() {
+        let distribution = Distribution1D::new(&[0.0, 0.0]);
+        let (x, pdf, offset) = distribution.sample_continuous(0.75);
+        assert_eq!((x, pdf, offset), (0.75, 0.0, 1));
+        assert_eq!(distribution.pdf(0.75), 0.0);
+    }
+
+    #[test]
+    fn two_dimensions() {
+        // Only the right half of the bottom row and the top left corner are lit
+        let distribution = Distribution2D::new(&[1.0, 0.0, 0.0, 3.0], 2, 2);
+
+        let n = 100;
+        let mut bottom = 0;
+        for i in 0..n {
+            for j in 0..n {
+                let u = Point2f::new((i as f64 + 0.5) / n as f64, (j as f64 + 0.5) / n as f64);
+                let (p, pdf) = distribution.sample_continuous(&u);
+                assert!((pdf - distribution.pdf(&p)).abs() < 1e-12);
+                if p.y >= 0.5 {
+                    assert!(p.x >= 0.5);
+                    assert_eq!(pdf, 3.0);
+                    bottom += 1;
+                } else {
+                    assert!(p.x < 0.5);
+                    assert_eq!(pdf, 1.0);
+                }
+            }
+        }
+        assert_eq!(bottom, 3 * n * n / 4);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
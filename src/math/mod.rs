@@ -1,4 +1,11 @@
+pub mod complex;
+pub mod distribution;
+pub mod noise;
+pub mod sampling;
+
 use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 
 pub enum QuadraticSolution {
     None,
@@ -20,3 +27,125 @@ pub fn solve_quadratic(a: f64, b: f64, c: f64) -> Option<QuadraticSolution> {
         },
     })
 }
+
+pub fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    (1.0 - t) * a + t * b
+}
+
+/// Eases from 0.0 at `a` to 1.0 at `b` with a cubic, clamped outside of them.
+pub fn smoothstep(x: f64, a: f64, b: f64) -> f64 {
+    if a == b {
+        return if x < a { 0.0 } else { 1.0 };
+    }
+    let t = ((x - a) / (b - a)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Hashes `values` by their bits, for random decisions that must be repeatable.
+pub fn hash_floats(values: &[f64]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for value in values {
+        value.to_bits().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Maps the hash of `values` to a uniformly distributed number in [0.0, 1.0).
+pub fn hash_to_unit(values: &[f64]) -> f64 {
+    (hash_floats(values) >> 11) as f64 / (1_u64 << 53) as f64
+}
+
+/// The error function, accurate to about 1.5e-7 (Abramowitz and Stegun 7.1.26).
+pub fn erf(x: f64) -> f64 {
+    let a1 = 0.254829592;
+    let a2 = -0.284496736;
+    let a3 = 1.421413741;
+    let a4 = -1.453152027;
+    let a5 = 1.061405429;
+    let p = 0.3275911;
+
+    let sign = x.signum();
+    let x = x.abs();
+    let t = 1.0 / (1.0 + p * x);
+    let y = 1.0 - ((((a5 * t + a4) * t + a3) * t + a2) * t + a1) * t * (-x * x).exp();
+    sign * y
+}
+
+/// The inverse of [erf] (Giles 2010).
+pub fn erf_inv(x: f64) -> f64 {
+    let x = x.clamp(-0.99999, 0.99999);
+    let mut w = -((1.0 - x) * (1.0 + x)).ln();
+
+    let p = if w < 5.0 {
+        w -= 2.5;
+        [
+            2.810_226_36e-08,
+            3.432_739_39e-07,
+            -3.523_387_7e-06,
+            -4.391_506_54e-06,
+            0.000_218_580_87,
+            -0.001_253_725_03,
+            -0.004_177_681_64,
+            0.246_640_727,
+            1.501_409_41,
+        ]
+        .iter()
+        .fold(0.0, |p, c| c + p * w)
+    } else {
+        w = w.sqrt() - 3.0;
+        [
+            -0.000_200_214_257,
+            0.000_100_950_558,
+            0.001_349_343_22,
+            -0.003_673_428_44,
+            0.005_739_507_73,
+            -0.007_622_461_3,
+            0.009_438_870_47,
+            1.001_674_06,
+            2.832_976_82,
+        ]
+        .iter()
+        .fold(0.0, |p, c| c + p * w)
+    };
+    p * x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_is_repeatable() {
+        assert_eq!(hash_floats(&[1.0, 2.0]), hash_floats(&[1.0, 2.0]));
+        assert_ne!(hash_floats(&[1.0, 2.0]), hash_floats(&[2.0, 1.0]));
+
+        let mean = (0..10_000)
+            .map(|i| hash_to_unit(&[i as f64]))
+            .inspect(|u| assert!((0.0..1.0).contains(u)))
+            .sum::<f64>()
+            / 10_000.0;
+        assert!((mean - 0.5).abs() < 1e-2);
+    }
+
+    #[test]
+    fn smoothstep_eases() {
+        assert_eq!(smoothstep(-1.0, 0.0, 2.0), 0.0);
+        assert_eq!(smoothstep(1.0, 0.0, 2.0), 0.5);
+        assert_eq!(smoothstep(3.0, 0.0, 2.0), 1.0);
+        assert_eq!(smoothstep(0.5, 1.0, 1.0), 0.0);
+    }
+
+    #[test]
+    fn erf_known_values() {
+        assert!(erf(0.0).abs() < 1e-7);
+        assert!((erf(1.0) - 0.842_700_79).abs() < 1e-6);
+        assert!((erf(-0.5) + 0.520_499_88).abs() < 1e-6);
+    }
+
+    #[test]
+    fn erf_inv_round_trip() {
+        for x in [-0.9, -0.3, 0.0, 0.2, 0.75, 0.99] {
+            assert!((erf(erf_inv(x)) - x).abs() < 1e-5, "{}", x);
+        }
+    }
+}
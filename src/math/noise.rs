@@ -0,0 +1,195 @@
+use crate::core::vector::Point3f;
+
+//////////////////////////
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// Scrambles the coordinates of a lattice cell into 64 well mixed bits,
+/// so noise needs no permutation tables and never repeats.
+fn hash_cell(x: i64, y: i64, z: i64) -> u64 {
+    let mut h = (x as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+        ^ (y as u64).wrapping_mul(0xc2b2_ae3d_27d4_eb4f)
+        ^ (z as u64).wrapping_mul(0x1656_67b1_9e37_79f9);
+    // The SplitMix64 finalizer
+    h ^= h >> 30;
+    h = h.wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    h ^= h >> 27;
+    h = h.wrapping_mul(0x94d0_49bb_1331_11eb);
+    h ^ (h >> 31)
+}
+
+/// Dots `(x, y, z)` with one of twelve gradients along the edges of a cube, picked by `h`.
+fn gradient(h: u64, x: f64, y: f64, z: f64) -> f64 {
+    let h = h & 15;
+    let u = if h < 8 { x } else { y };
+    let v = match h {
+        0..=3 => y,
+        12 | 14 => x,
+        _ => z,
+    };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+/// Perlin's quintic ease curve, keeping noise smooth across cells.
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+/// Gradient noise (Perlin 2002), smoothly varying about once per unit distance,
+/// roughly in [-1.0, 1.0] and 0.0 at integer coordinates.
+pub fn perlin(p: &Point3f) -> f64 {
+    let (x0, y0, z0) = (p.x.floor(), p.y.floor(), p.z.floor());
+    let (dx, dy, dz) = (p.x - x0, p.y - y0, p.z - z0);
+    let (x0, y0, z0) = (x0 as i64, y0 as i64, z0 as i64);
+
+    let corner = |i: i64, j: i64, k: i64| {
+        gradient(
+            hash_cell(x0 + i, y0 + j, z0 + k),
+            dx - i as f64,
+            dy - j as f64,
+            dz - k as f64,
+        )
+    };
+
+    let (u, v, w) = (fade(dx), fade(dy), fade(dz));
+    lerp(
+        w,
+        lerp(
+            v,
+            lerp(u, corner(0, 0, 0), corner(1, 0, 0)),
+            lerp(u, corner(0, 1, 0), corner(1, 1, 0)),
+        ),
+        lerp(
+            v,
+            lerp(u, corner(0, 0, 1), corner(1, 0, 1)),
+            lerp(u, corner(0, 1, 1), corner(1, 1, 1)),
+        ),
+    )
+}
+
+/// Sums `octaves` of `noise`, each at twice the frequency and `roughness` times the amplitude
+/// of the last, normalized by the total amplitude.
+fn fractal(p: &Point3f, octaves: usize, roughness: f64, noise: impl Fn(&Point3f) -> f64) -> f64 {
+    let (mut sum, mut total, mut amplitude, mut frequency) = (0.0, 0.0, 1.0, 1.0);
+    for _ in 0..octaves.max(1) {
+        sum += amplitude * noise(&(*p * frequency));
+        total += amplitude;
+        amplitude *= roughness;
+        frequency *= 2.0;
+    }
+    sum / total
+}
+
+/// Fractal Brownian motion: [perlin] noise with finer detail layered on, roughly in [-1.0, 1.0].
+/// `roughness` in (0.0, 1.0) sets how strong the detail is, 0.5 being typical.
+pub fn fbm(p: &Point3f, octaves: usize, roughness: f64) -> f64 {
+    fractal(p, octaves, roughness, perlin)
+}
+
+/// Like [fbm], but summing the absolute value of each octave,
+/// giving billowy, creased patterns in [0.0, 1.0].
+pub fn turbulence(p: &Point3f, octaves: usize, roughness: f64) -> f64 {
+    fractal(p, octaves, roughness, |p| perlin(p).abs()).min(1.0)
+}
+
+/// Cellular noise (Worley 1996) over one randomly placed feature point per unit cell.
+/// Returns the distances to the closest and second closest feature points.
+pub fn worley(p: &Point3f) -> (f64, f64) {
+    let (x0, y0, z0) = (p.x.floor() as i64, p.y.floor() as i64, p.z.floor() as i64);
+    let unit = |bits: u64| (bits & ((1 << 21) - 1)) as f64 / (1 << 21) as f64;
+
+    let (mut f1, mut f2) = (f64::INFINITY, f64::INFINITY);
+    for z in z0 - 1..=z0 + 1 {
+        for y in y0 - 1..=y0 + 1 {
+            for x in x0 - 1..=x0 + 1 {
+                let h = hash_cell(x, y, z);
+                let feature = Point3f::new(
+                    x as f64 + unit(h),
+                    y as f64 + unit(h >> 21),
+                    z as f64 + unit(h >> 42),
+                );
+                let d = (feature - *p).length();
+                if d < f1 {
+                    f2 = f1;
+                    f1 = d;
+                } else if d < f2 {
+                    f2 = d;
+                }
+            }
+        }
+    }
+    (f1, f2)
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::Vec3f;
+
+    fn points() -> impl Iterator<Item = Point3f> {
+        (0..10_000).map(|i| {
+            let i = i as f64;
+            Point3f::new(i * 0.137 - 300.0, (i * 0.291).sin() * 50.0, i * 0.0173)
+        })
+    }
+
+    #[test]
+    fn perlin_range_and_lattice() {
+        let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+        for p in points() {
+            let n = perlin(&p);
+            assert_eq!(n, perlin(&p));
+            min = min.min(n);
+            max = max.max(n);
+        }
+        assert!(min >= -1.1 && max <= 1.1, "range [{}, {}]", min, max);
+        assert!(min < -0.4 && max > 0.4, "range [{}, {}]", min, max);
+
+        assert_eq!(perlin(&Point3f::new(3.0, -7.0, 12.0)), 0.0);
+    }
+
+    #[test]
+    fn perlin_is_continuous() {
+        for p in points().take(1000) {
+            let q = p + Vec3f::new(1e-7, -1e-7, 1e-7);
+            assert!((perlin(&p) - perlin(&q)).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn fractal_ranges() {
+        for p in points() {
+            let f = fbm(&p, 6, 0.5);
+            assert!((-1.1..=1.1).contains(&f));
+            let t = turbulence(&p, 6, 0.5);
+            assert!((0.0..=1.0).contains(&t));
+        }
+        // A single octave is plain noise
+        let p = Point3f::new(0.3, 0.6, 0.9);
+        assert_eq!(fbm(&p, 1, 0.5), perlin(&p));
+    }
+
+    #[test]
+    fn worley_distances() {
+        for p in points().take(1000) {
+            let (f1, f2) = worley(&p);
+            assert_eq!((f1, f2), worley(&p));
+            assert!(0.0 <= f1 && f1 <= f2);
+            // There is always a feature point in the containing cell
+            assert!(f1 < 3f64.sqrt());
+        }
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,258 @@
+use std::f64::consts::{FRAC_1_PI, FRAC_PI_2, FRAC_PI_4, PI};
+
+use crate::core::vector::{Point2f, Point3f, Vec3f};
+
+//////////////////////////
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// Maps a uniform sample in [0.0, 1.0)^2 onto the unit disk,
+/// preserving relative areas (Shirley and Chiu's concentric mapping).
+pub fn sample_uniform_disk_concentric(u: &Point2f) -> Point2f {
+    let offset = Point2f::new(2.0 * u.x - 1.0, 2.0 * u.y - 1.0);
+    if offset.x == 0.0 && offset.y == 0.0 {
+        return Point2f::default();
+    }
+
+    let (r, theta) = if offset.x.abs() > offset.y.abs() {
+        (offset.x, FRAC_PI_4 * (offset.y / offset.x))
+    } else {
+        (offset.y, FRAC_PI_2 - FRAC_PI_4 * (offset.x / offset.y))
+    };
+    Point2f::new(r * theta.cos(), r * theta.sin())
+}
+
+/// Maps a uniform sample in [0.0, 1.0)^2 onto the unit disk using polar coordinates.
+pub fn sample_uniform_disk_polar(u: &Point2f) -> Point2f {
+    let r = u.x.sqrt();
+    let theta = 2.0 * PI * u.y;
+    Point2f::new(r * theta.cos(), r * theta.sin())
+}
+
+/// Samples the +z hemisphere proportionally to the cosine of the angle with z.
+pub fn sample_cosine_hemisphere(u: &Point2f) -> Vec3f {
+    let d = sample_uniform_disk_concentric(u);
+    let z = (1.0 - d.x * d.x - d.y * d.y).max(0.0).sqrt();
+    Vec3f::new(d.x, d.y, z)
+}
+
+pub fn cosine_hemisphere_pdf(cos_theta: f64) -> f64 {
+    cos_theta * FRAC_1_PI
+}
+
+/// Samples a direction uniformly over the unit sphere.
+pub fn sample_uniform_sphere(u: &Point2f) -> Vec3f {
+    let z = 1.0 - 2.0 * u.x;
+    let r = (1.0 - z * z).max(0.0).sqrt();
+    let phi = 2.0 * PI * u.y;
+    Vec3f::new(r * phi.cos(), r * phi.sin(), z)
+}
+
+pub fn uniform_sphere_pdf() -> f64 {
+    FRAC_1_PI / 4.0
+}
+
+/// Picks barycentric coordinates uniformly over the area of a triangle.
+pub fn sample_uniform_triangle(u: &Point2f) -> [f64; 3] {
+    let su0 = u.x.sqrt();
+    let b0 = 1.0 - su0;
+    let b1 = u.y * su0;
+    [b0, b1, 1.0 - b0 - b1]
+}
+
+/// The angle between normalized vectors, accurate even when they are nearly parallel.
+fn angle_between(a: &Vec3f, b: &Vec3f) -> f64 {
+    if a.dot(b) < 0.0 {
+        PI - 2.0 * ((*a + *b).length() / 2.0).min(1.0).asin()
+    } else {
+        2.0 * ((*b - *a).length() / 2.0).min(1.0).asin()
+    }
+}
+
+/// The part of `v` perpendicular to the normalized `w`.
+fn gram_schmidt(v: &Vec3f, w: &Vec3f) -> Vec3f {
+    *v - *w * v.dot(w)
+}
+
+/// The directions from `p` to the corners of triangle `v`, and the interior angles
+/// of the spherical triangle they span. [None] if `p` lies in the triangle's plane.
+fn spherical_triangle(v: &[Point3f; 3], p: &Point3f) -> Option<([Vec3f; 3], [f64; 3])> {
+    let [a, b, c] = v.map(|v| (v - *p).normalize());
+    let n_ab = a.cross(&b);
+    let n_bc = b.cross(&c);
+    let n_ca = c.cross(&a);
+    if n_ab.length_sq() == 0.0 || n_bc.length_sq() == 0.0 || n_ca.length_sq() == 0.0 {
+        return None;
+    }
+    let (n_ab, n_bc, n_ca) = (n_ab.normalize(), n_bc.normalize(), n_ca.normalize());
+
+    let alpha = angle_between(&n_ab, &-n_ca);
+    let beta = angle_between(&n_bc, &-n_ab);
+    let gamma = angle_between(&n_ca, &-n_bc);
+    Some(([a, b, c], [alpha, beta, gamma]))
+}
+
+/// The solid angle covered by triangle `v` as seen from `p`.
+pub fn spherical_triangle_area(v: &[Point3f; 3], p: &Point3f) -> f64 {
+    spherical_triangle(v, p).map_or(0.0, |(_, [alpha, beta, gamma])| {
+        (alpha + beta + gamma - PI).max(0.0)
+    })
+}
+
+/// Picks a direction uniformly over the solid angle of triangle `v` as seen from `p`
+/// (Arvo 1995). Returns the barycentric coordinates of the point seen along it,
+/// and the density with respect to solid angle.
+pub fn sample_spherical_triangle(
+    v: &[Point3f; 3],
+    p: &Point3f,
+    u: &Point2f,
+) -> Option<([f64; 3], f64)> {
+    let ([a, b, c], [alpha, _, _]) = spherical_triangle(v, p)?;
+    let area = spherical_triangle_area(v, p);
+    if area <= 0.0 {
+        return None;
+    }
+
+    // Pick the sub-triangle with the chosen fraction of the area, finding its third corner
+    // on the arc from a to c
+    let phi = PI + u.x * area - alpha;
+    let (sin_phi, cos_phi) = phi.sin_cos();
+    let (sin_alpha, cos_alpha) = alpha.sin_cos();
+    let k1 = cos_phi + cos_alpha;
+    let k2 = sin_phi - sin_alpha * a.dot(&b);
+    let cos_b = (k2 + (k2 * cos_phi - k1 * sin_phi) * cos_alpha)
+        / ((k2 * sin_phi + k1 * cos_phi) * sin_alpha);
+    let cos_b = cos_b.clamp(-1.0, 1.0);
+    let sin_b = (1.0 - cos_b * cos_b).max(0.0).sqrt();
+    let c_prime = a * cos_b + gram_schmidt(&c, &a).normalize() * sin_b;
+
+    // Then a point along the arc from b to it
+    let cos_theta = 1.0 - u.y * (1.0 - c_prime.dot(&b));
+    let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+    let w = b * cos_theta + gram_schmidt(&c_prime, &b).normalize() * sin_theta;
+
+    // Find where w hits the triangle
+    let e1 = v[1] - v[0];
+    let e2 = v[2] - v[0];
+    let s1 = w.cross(&e2);
+    let divisor = s1.dot(&e1);
+    if divisor == 0.0 {
+        return Some(([1.0 / 3.0; 3], 1.0 / area));
+    }
+    let s = *p - v[0];
+    let b1 = (s.dot(&s1) / divisor).clamp(0.0, 1.0);
+    let b2 = (w.dot(&s.cross(&e1)) / divisor).clamp(0.0, 1.0);
+    let (b1, b2) = if b1 + b2 > 1.0 {
+        (b1 / (b1 + b2), b2 / (b1 + b2))
+    } else {
+        (b1, b2)
+    };
+    Some(([1.0 - b1 - b2, b1, b2], 1.0 / area))
+}
+
+/// The multiple importance sampling weight for a sample drawn `nf` times from
+/// the density `f_pdf`, against `ng` draws from `g_pdf` (Veach's power heuristic).
+pub fn power_heuristic(nf: usize, f_pdf: f64, ng: usize, g_pdf: f64) -> f64 {
+    let f = nf as f64 * f_pdf;
+    let g = ng as f64 * g_pdf;
+    if f.is_infinite() {
+        return 1.0;
+    }
+    if f == 0.0 && g == 0.0 {
+        return 0.0;
+    }
+    (f * f) / (f * f + g * g)
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concentric_disk_in_bounds() {
+        for i in 0..=10 {
+            for j in 0..=10 {
+                let u = Point2f::new(i as f64 / 10.0, j as f64 / 10.0);
+                let d = sample_uniform_disk_concentric(&u);
+                assert!(d.x * d.x + d.y * d.y <= 1.0 + 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn cosine_hemisphere_is_normalized() {
+        let w = sample_cosine_hemisphere(&Point2f::new(0.3, 0.8));
+        assert!((w.length() - 1.0).abs() < 1e-9);
+        assert!(w.z >= 0.0);
+    }
+
+    #[test]
+    fn uniform_sphere_is_normalized() {
+        for u in [
+            Point2f::new(0.0, 0.0),
+            Point2f::new(0.3, 0.8),
+            Point2f::new(0.999, 0.5),
+        ] {
+            assert!((sample_uniform_sphere(&u).length() - 1.0).abs() < 1e-9);
+        }
+        assert!(sample_uniform_sphere(&Point2f::new(0.9, 0.1)).z < 0.0);
+    }
+
+    #[test]
+    fn spherical_triangle_octant() {
+        let v = [
+            Point3f::new(1.0, 0.0, 0.0),
+            Point3f::new(0.0, 1.0, 0.0),
+            Point3f::new(0.0, 0.0, 1.0),
+        ];
+        let origin = Point3f::default();
+        assert!((spherical_triangle_area(&v, &origin) - FRAC_PI_2).abs() < 1e-12);
+        assert_eq!(
+            spherical_triangle_area(&v, &Point3f::new(0.5, 0.5, 0.0)),
+            0.0
+        );
+
+        // The corner region b0 > 0.5 gets samples in proportion to its solid angle
+        let corner = [v[0], (v[0] + v[1]) * 0.5, (v[0] + v[2]) * 0.5];
+        let expected = spherical_triangle_area(&corner, &origin) / FRAC_PI_2;
+        let n = 200;
+        let mut inside = 0;
+        for i in 0..n {
+            for j in 0..n {
+                let u = Point2f::new((i as f64 + 0.5) / n as f64, (j as f64 + 0.5) / n as f64);
+                let (b, pdf) = sample_spherical_triangle(&v, &origin, &u).unwrap();
+                assert!((pdf - 1.0 / FRAC_PI_2).abs() < 1e-12);
+                assert!(b.iter().all(|b| (0.0..=1.0).contains(b)));
+                assert!((b.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+                if b[0] > 0.5 {
+                    inside += 1;
+                }
+            }
+        }
+        let fraction = inside as f64 / (n * n) as f64;
+        assert!(
+            (fraction - expected).abs() < 5e-3,
+            "{} vs {}",
+            fraction,
+            expected
+        );
+    }
+
+    #[test]
+    fn power_heuristic_weights_sum_to_one() {
+        let (a, b) = (0.7, 2.3);
+        let sum = power_heuristic(1, a, 1, b) + power_heuristic(1, b, 1, a);
+        assert!((sum - 1.0).abs() < 1e-12);
+        assert_eq!(power_heuristic(1, 1.0, 1, 0.0), 1.0);
+        assert_eq!(power_heuristic(1, 0.0, 1, 0.0), 0.0);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
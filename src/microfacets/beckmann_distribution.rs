@@ -0,0 +1,172 @@
+use std::f64::consts::PI;
+
+use crate::core::bxdf::{cos2_theta, cos_phi, cos_theta, sin_phi, tan_theta};
+use crate::core::microfacet::MicrofacetDistribution;
+use crate::core::vector::{Point2f, Vec3f};
+use crate::math::{erf, erf_inv};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// The Beckmann-Spizzichino distribution of microfacet normals,
+/// a Gaussian distribution of slopes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BeckmannDistribution {
+    alpha_x: f64,
+    alpha_y: f64,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+const FRAC_1_SQRT_PI: f64 = 0.564_189_583_547_756_3;
+
+impl BeckmannDistribution {
+    pub fn new(alpha_x: f64, alpha_y: f64) -> Self {
+        Self { alpha_x, alpha_y }
+    }
+
+    /// Samples the slopes visible from a direction with the given `cos_theta`,
+    /// for a distribution with unit roughness (Jakob 2014).
+    fn sample_unit_slopes(cos_theta: f64, u: &Point2f) -> (f64, f64) {
+        // Normal incidence sees every slope equally
+        if cos_theta > 0.9999 {
+            let r = (-(1.0 - u.x).ln()).sqrt();
+            let phi = 2.0 * PI * u.y;
+            return (r * phi.cos(), r * phi.sin());
+        }
+
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let tan_theta = sin_theta / cos_theta;
+        let cot_theta = 1.0 / tan_theta;
+
+        // Invert the CDF of the x slope by bisection guarded Newton iteration
+        let mut a = -1.0;
+        let mut c = erf(cot_theta);
+        let sample_x = u.x.max(1e-6);
+
+        let theta = cos_theta.acos();
+        let fit = 1.0 + theta * (-0.876 + theta * (0.4265 - 0.0594 * theta));
+        let mut b = c - (1.0 + c) * (1.0 - sample_x).powf(fit);
+
+        let normalization =
+            1.0 / (1.0 + c + FRAC_1_SQRT_PI * tan_theta * (-cot_theta * cot_theta).exp());
+
+        for _ in 0..10 {
+            if !(a..=c).contains(&b) {
+                b = 0.5 * (a + c);
+            }
+
+            let inv_erf = erf_inv(b);
+            let value = normalization
+                * (1.0 + b + FRAC_1_SQRT_PI * tan_theta * (-inv_erf * inv_erf).exp())
+                - sample_x;
+            if value.abs() < 1e-6 {
+                break;
+            }
+
+            let derivative = normalization * (1.0 - inv_erf * tan_theta);
+            if value > 0.0 {
+                c = b;
+            } else {
+                a = b;
+            }
+            b -= value / derivative;
+        }
+
+        (erf_inv(b), erf_inv(2.0 * u.y.max(1e-6) - 1.0))
+    }
+}
+
+impl MicrofacetDistribution for BeckmannDistribution {
+    fn d(&self, wm: &Vec3f) -> f64 {
+        let tan2_theta = tan_theta(wm).powi(2);
+        if tan2_theta.is_infinite() {
+            return 0.0;
+        }
+        let cos4_theta = cos2_theta(wm).powi(2);
+        if cos4_theta < 1e-16 {
+            return 0.0;
+        }
+
+        let e = tan2_theta
+            * ((cos_phi(wm) / self.alpha_x).powi(2) + (sin_phi(wm) / self.alpha_y).powi(2));
+        (-e).exp() / (PI * self.alpha_x * self.alpha_y * cos4_theta)
+    }
+
+    fn lambda(&self, w: &Vec3f) -> f64 {
+        let abs_tan_theta = tan_theta(w).abs();
+        if abs_tan_theta.is_infinite() {
+            return 0.0;
+        }
+        if abs_tan_theta == 0.0 {
+            return 0.0;
+        }
+
+        let alpha =
+            ((cos_phi(w) * self.alpha_x).powi(2) + (sin_phi(w) * self.alpha_y).powi(2)).sqrt();
+        let a = 1.0 / (alpha * abs_tan_theta);
+        (erf(a) - 1.0) / 2.0 + FRAC_1_SQRT_PI * (-a * a).exp() / (2.0 * a)
+    }
+
+    /// Samples the visible normals by stretching to unit roughness,
+    /// sampling slopes there and stretching back (Heitz and d'Eon 2014).
+    fn sample_wm(&self, w: &Vec3f, u: &Point2f) -> Vec3f {
+        let w = if w.z < 0.0 { -*w } else { *w };
+
+        let stretched = Vec3f::new(self.alpha_x * w.x, self.alpha_y * w.y, w.z).normalize();
+        let (slope_x, slope_y) = Self::sample_unit_slopes(cos_theta(&stretched), u);
+
+        // Rotate to the azimuth of w, then unstretch
+        let (cos_phi, sin_phi) = (cos_phi(&stretched), sin_phi(&stretched));
+        let rotated_x = cos_phi * slope_x - sin_phi * slope_y;
+        let rotated_y = sin_phi * slope_x + cos_phi * slope_y;
+
+        Vec3f::new(-self.alpha_x * rotated_x, -self.alpha_y * rotated_y, 1.0).normalize()
+    }
+
+    fn effectively_smooth(&self) -> bool {
+        self.alpha_x.max(self.alpha_y) < 1e-3
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::microfacets::test_utils::{
+        assert_normalized, assert_visible_normals_chi_square, assert_white_furnace,
+    };
+
+    #[test]
+    fn normalized() {
+        for (alpha_x, alpha_y) in [(0.1, 0.1), (0.3, 0.3), (0.7, 0.7), (1.0, 1.0), (0.2, 0.6)] {
+            assert_normalized(&BeckmannDistribution::new(alpha_x, alpha_y));
+        }
+    }
+
+    #[test]
+    fn white_furnace() {
+        for (alpha_x, alpha_y) in [(0.1, 0.1), (0.3, 0.3), (0.7, 0.7), (1.0, 1.0), (0.2, 0.6)] {
+            assert_white_furnace(&BeckmannDistribution::new(alpha_x, alpha_y));
+        }
+    }
+
+    #[test]
+    fn visible_normals_chi_square() {
+        for (alpha_x, alpha_y) in [(0.3, 0.3), (0.8, 0.8), (0.2, 0.6)] {
+            assert_visible_normals_chi_square(BeckmannDistribution::new(alpha_x, alpha_y));
+        }
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,5 @@
+pub mod beckmann_distribution;
+pub mod trowbridge_reitz_distribution;
+
+#[cfg(test)]
+pub mod test_utils;
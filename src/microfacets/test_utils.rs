@@ -0,0 +1,112 @@
+//! Numerical checks shared by the [MicrofacetDistribution] tests.
+
+use std::f64::consts::PI;
+
+use crate::bxdfs::test_utils::{assert_chi_square, test_directions};
+use crate::core::bxdf::{reflect, BsdfSample, BxDF};
+use crate::core::microfacet::MicrofacetDistribution;
+use crate::core::vector::{Color3f, Point2f, Vec3f};
+
+const THETA_STEPS: usize = 1_000;
+const PHI_STEPS: usize = 200;
+
+/// Integrates `f(wm)` over the +z hemisphere with the midpoint rule.
+fn integrate_hemisphere(f: impl Fn(&Vec3f) -> f64) -> f64 {
+    let d_theta = PI / 2.0 / THETA_STEPS as f64;
+    let d_phi = 2.0 * PI / PHI_STEPS as f64;
+
+    let mut total = 0.0;
+    for i in 0..THETA_STEPS {
+        let theta = (i as f64 + 0.5) * d_theta;
+        for j in 0..PHI_STEPS {
+            let phi = (j as f64 + 0.5) * d_phi;
+            let wm = Vec3f::new(
+                theta.sin() * phi.cos(),
+                theta.sin() * phi.sin(),
+                theta.cos(),
+            );
+            total += f(&wm) * theta.sin();
+        }
+    }
+    total * d_theta * d_phi
+}
+
+fn upper_directions() -> Vec<Vec3f> {
+    test_directions()
+        .into_iter()
+        .filter(|w| w.z > 0.0)
+        .collect()
+}
+
+/// The projected area of the microfacets must equal the macro surface's.
+pub fn assert_normalized(distribution: &dyn MicrofacetDistribution) {
+    let projected_area = integrate_hemisphere(|wm| distribution.d(wm) * wm.z);
+    assert!(
+        (projected_area - 1.0).abs() < 1e-2,
+        "projected area {}",
+        projected_area
+    );
+}
+
+/// The weak white furnace test (Heitz 2014):
+/// microfacets visible from any direction must project onto exactly the visible macro surface,
+/// so a perfect mirror without shadowing reflects all energy.
+/// With shadowing, single scattering must lose energy rather than create it.
+pub fn assert_white_furnace(distribution: &dyn MicrofacetDistribution) {
+    for wo in upper_directions() {
+        let visible = integrate_hemisphere(|wm| distribution.visible_d(&wo, wm));
+        assert!(
+            (visible - 1.0).abs() < 1e-2,
+            "weak white furnace {} for wo {:?}",
+            visible,
+            wo
+        );
+
+        // dwi = 4 |wo . wm| dwm cancels the 4 |wo . wm| of the reflection Jacobian
+        let reflected = integrate_hemisphere(|wm| {
+            let wi = reflect(&wo, wm);
+            if wi.z <= 0.0 || wo.dot(wm) <= 0.0 {
+                return 0.0;
+            }
+            distribution.d(wm) * distribution.g(&wo, &wi) * wo.dot(wm) / wo.z
+        });
+        assert!(
+            reflected <= 1.0 + 1e-3 && reflected > 0.0,
+            "white furnace {} for wo {:?}",
+            reflected,
+            wo
+        );
+    }
+}
+
+/// Exposes visible normal sampling as a [BxDF] so the chi-square test can be reused.
+struct VisibleNormals<D> {
+    distribution: D,
+}
+
+impl<D: MicrofacetDistribution> BxDF for VisibleNormals<D> {
+    fn f(&self, _wo: &Vec3f, _wi: &Vec3f) -> Color3f {
+        Color3f::new(1.0, 1.0, 1.0)
+    }
+
+    fn sample_f(&self, wo: &Vec3f, _uc: f64, u: &Point2f) -> Option<BsdfSample> {
+        let wm = self.distribution.sample_wm(wo, u);
+        let pdf = self.distribution.pdf(wo, &wm);
+        Some(BsdfSample::new(Color3f::new(1.0, 1.0, 1.0), wm, pdf))
+    }
+
+    fn pdf(&self, wo: &Vec3f, wm: &Vec3f) -> f64 {
+        if wm.z <= 0.0 {
+            return 0.0;
+        }
+        self.distribution.pdf(wo, wm)
+    }
+}
+
+/// Checks [MicrofacetDistribution::sample_wm] against [MicrofacetDistribution::pdf].
+pub fn assert_visible_normals_chi_square<D: MicrofacetDistribution + 'static>(distribution: D) {
+    let visible_normals = VisibleNormals { distribution };
+    for wo in test_directions() {
+        assert_chi_square(&visible_normals, &wo);
+    }
+}
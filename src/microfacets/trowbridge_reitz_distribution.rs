@@ -0,0 +1,125 @@
+use std::f64::consts::PI;
+
+use crate::core::bxdf::{cos2_theta, cos_phi, sin_phi, tan_theta};
+use crate::core::microfacet::MicrofacetDistribution;
+use crate::core::vector::{Point2f, Vec3f};
+use crate::math::lerp;
+use crate::math::sampling::sample_uniform_disk_polar;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// The Trowbridge-Reitz (GGX) distribution of microfacet normals.
+/// Has longer tails than [BeckmannDistribution](super::beckmann_distribution::BeckmannDistribution),
+/// which better matches most measured materials.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrowbridgeReitzDistribution {
+    alpha_x: f64,
+    alpha_y: f64,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl TrowbridgeReitzDistribution {
+    pub fn new(alpha_x: f64, alpha_y: f64) -> Self {
+        Self { alpha_x, alpha_y }
+    }
+}
+
+impl MicrofacetDistribution for TrowbridgeReitzDistribution {
+    fn d(&self, wm: &Vec3f) -> f64 {
+        let tan2_theta = tan_theta(wm).powi(2);
+        if tan2_theta.is_infinite() {
+            return 0.0;
+        }
+        let cos4_theta = cos2_theta(wm).powi(2);
+        if cos4_theta < 1e-16 {
+            return 0.0;
+        }
+
+        let e = tan2_theta
+            * ((cos_phi(wm) / self.alpha_x).powi(2) + (sin_phi(wm) / self.alpha_y).powi(2));
+        1.0 / (PI * self.alpha_x * self.alpha_y * cos4_theta * (1.0 + e).powi(2))
+    }
+
+    fn lambda(&self, w: &Vec3f) -> f64 {
+        let tan2_theta = tan_theta(w).powi(2);
+        if tan2_theta.is_infinite() {
+            return 0.0;
+        }
+        let alpha2 = (cos_phi(w) * self.alpha_x).powi(2) + (sin_phi(w) * self.alpha_y).powi(2);
+        ((1.0 + alpha2 * tan2_theta).sqrt() - 1.0) / 2.0
+    }
+
+    /// Samples the visible normals exactly (Heitz 2018).
+    fn sample_wm(&self, w: &Vec3f, u: &Point2f) -> Vec3f {
+        // Transform to the hemispherical configuration
+        let mut wh = Vec3f::new(self.alpha_x * w.x, self.alpha_y * w.y, w.z).normalize();
+        if wh.z < 0.0 {
+            wh = -wh;
+        }
+
+        let t1 = if wh.z < 0.99999 {
+            Vec3f::new(0.0, 0.0, 1.0).cross(&wh).normalize()
+        } else {
+            Vec3f::new(1.0, 0.0, 0.0)
+        };
+        let t2 = wh.cross(&t1);
+
+        // Warp a disk sample onto the visible part of the hemisphere
+        let mut p = sample_uniform_disk_polar(u);
+        let h = (1.0 - p.x * p.x).sqrt();
+        p.y = lerp((1.0 + wh.z) / 2.0, h, p.y);
+
+        let pz = (1.0 - p.x * p.x - p.y * p.y).max(0.0).sqrt();
+        let nh = t1 * p.x + t2 * p.y + wh * pz;
+
+        Vec3f::new(self.alpha_x * nh.x, self.alpha_y * nh.y, nh.z.max(1e-6)).normalize()
+    }
+
+    fn effectively_smooth(&self) -> bool {
+        self.alpha_x.max(self.alpha_y) < 1e-3
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::microfacets::test_utils::{
+        assert_normalized, assert_visible_normals_chi_square, assert_white_furnace,
+    };
+
+    #[test]
+    fn normalized() {
+        for (alpha_x, alpha_y) in [(0.1, 0.1), (0.3, 0.3), (0.7, 0.7), (1.0, 1.0), (0.2, 0.6)] {
+            assert_normalized(&TrowbridgeReitzDistribution::new(alpha_x, alpha_y));
+        }
+    }
+
+    #[test]
+    fn white_furnace() {
+        for (alpha_x, alpha_y) in [(0.1, 0.1), (0.3, 0.3), (0.7, 0.7), (1.0, 1.0), (0.2, 0.6)] {
+            assert_white_furnace(&TrowbridgeReitzDistribution::new(alpha_x, alpha_y));
+        }
+    }
+
+    #[test]
+    fn visible_normals_chi_square() {
+        for (alpha_x, alpha_y) in [(0.3, 0.3), (0.8, 0.8), (0.2, 0.6)] {
+            assert_visible_normals_chi_square(TrowbridgeReitzDistribution::new(alpha_x, alpha_y));
+        }
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,158 @@
+use crate::core::interaction::Interaction;
+use crate::core::light::AreaLight;
+use crate::core::material::Material;
+use crate::core::primitive::Primitive;
+use crate::core::ray::Ray;
+use crate::core::texture::Texture;
+use crate::math::hash_to_unit;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Cuts holes into another [Primitive] where its opacity texture is low,
+/// for foliage cards and chain-link fences.
+/// Masked hits are skipped inside [Primitive::test], so neither accelerators
+/// nor integrators need to know about them.
+pub struct AlphaMaskedPrimitive<'a> {
+    primitive: &'a dyn Primitive,
+
+    /// Opacity in [0.0, 1.0], 0.0 being fully transparent
+    alpha: &'a dyn Texture<f64>,
+
+    mode: AlphaMode,
+}
+
+/// How opacity between 0.0 and 1.0 is treated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlphaMode {
+    /// Hits are kept where the opacity is at least the cutoff, and skipped elsewhere
+    Cutoff(f64),
+
+    /// Hits are kept with probability equal to the opacity,
+    /// so that on average the surface lets through the rest of the light
+    Stochastic,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// How far past a skipped hit the search for the next one starts.
+const SKIP_EPSILON: f64 = 1e-9;
+
+impl<'a> AlphaMaskedPrimitive<'a> {
+    pub fn new(primitive: &'a dyn Primitive, alpha: &'a dyn Texture<f64>, mode: AlphaMode) -> Self {
+        Self {
+            primitive,
+            alpha,
+            mode,
+        }
+    }
+
+    fn is_opaque(&self, ray: &Ray, interaction: &Interaction) -> bool {
+        let alpha = self.alpha.evaluate(interaction);
+        match self.mode {
+            AlphaMode::Cutoff(cutoff) => alpha >= cutoff,
+            AlphaMode::Stochastic => {
+                if alpha >= 1.0 {
+                    return true;
+                }
+                if alpha <= 0.0 {
+                    return false;
+                }
+                // Hashed rather than random, so the same ray always sees the same surface
+                let (o, d, p) = (ray.o, ray.d, interaction.p);
+                hash_to_unit(&[o.x, o.y, o.z, d.x, d.y, d.z, p.x, p.y, p.z]) < alpha
+            }
+        }
+    }
+}
+
+impl<'a> Primitive for AlphaMaskedPrimitive<'a> {
+    fn test(&self, ray: &Ray) -> Option<Interaction<'_>> {
+        let mut interaction = self.primitive.test(ray)?;
+
+        while !self.is_opaque(ray, &interaction) {
+            let min_t = interaction.t + SKIP_EPSILON * interaction.t.abs().max(1.0);
+            let next = Ray::new(ray.o, ray.d, min_t, ray.max_t());
+            interaction = self.primitive.test(&next)?;
+        }
+
+        Some(interaction)
+    }
+
+    fn material(&self) -> Option<&dyn Material> {
+        self.primitive.material()
+    }
+
+    fn area_light(&self) -> Option<&dyn AreaLight> {
+        self.primitive.area_light()
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::{Point3f, Vec3f};
+    use crate::primitives::sphere::Sphere;
+    use crate::textures::constant_texture::ConstantTexture;
+
+    fn ray_from_below(x: f64) -> Ray {
+        Ray::new(
+            Point3f::new(x, 0.0, -5.0),
+            Vec3f::new(0.0, 0.0, 1.0),
+            0.0,
+            100.0,
+        )
+    }
+
+    #[test]
+    fn cutoff() {
+        let sphere = Sphere::new(Point3f::new(0.0, 0.0, 0.0), 1.0);
+        let alpha = ConstantTexture::new(0.4);
+
+        let opaque = AlphaMaskedPrimitive::new(&sphere, &alpha, AlphaMode::Cutoff(0.3));
+        let hit = opaque.test(&ray_from_below(0.0)).unwrap();
+        assert!(hit.is_eq(&sphere.test(&ray_from_below(0.0)).unwrap()));
+
+        let cut = AlphaMaskedPrimitive::new(&sphere, &alpha, AlphaMode::Cutoff(0.5));
+        assert!(cut.test(&ray_from_below(0.0)).is_none());
+    }
+
+    #[test]
+    fn stochastic() {
+        let sphere = Sphere::new(Point3f::new(0.0, 0.0, 0.0), 1.0);
+        let alpha = ConstantTexture::new(0.5);
+        let masked = AlphaMaskedPrimitive::new(&sphere, &alpha, AlphaMode::Stochastic);
+
+        let rays = 10_000;
+        let (mut front, mut back) = (0, 0);
+        for i in 0..rays {
+            let ray = ray_from_below(0.5 * i as f64 / rays as f64);
+            match masked.test(&ray) {
+                Some(hit) if hit.p.z < 0.0 => front += 1,
+                Some(_) => back += 1,
+                None => {}
+            }
+
+            // Repeatable for the same ray
+            let again = masked.test(&ray);
+            assert_eq!(masked.test(&ray).map(|hit| hit.t), again.map(|hit| hit.t));
+        }
+
+        // Half the rays stop at the front, half of the rest at the back
+        assert!((front as f64 / rays as f64 - 0.5).abs() < 2e-2);
+        assert!((back as f64 / rays as f64 - 0.25).abs() < 2e-2);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
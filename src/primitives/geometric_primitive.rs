@@ -0,0 +1,98 @@
+use crate::core::interaction::Interaction;
+use crate::core::light::AreaLight;
+use crate::core::material::Material;
+use crate::core::primitive::Primitive;
+use crate::core::ray::Ray;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Binds a [Material] to a shape, so that hits on it know what they are made of
+/// through [Interaction::material], and optionally an [AreaLight] making it glow.
+pub struct GeometricPrimitive<'a> {
+    shape: &'a dyn Primitive,
+    material: &'a dyn Material,
+    area_light: Option<&'a dyn AreaLight>,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<'a> GeometricPrimitive<'a> {
+    pub fn new(shape: &'a dyn Primitive, material: &'a dyn Material) -> Self {
+        Self {
+            shape,
+            material,
+            area_light: None,
+        }
+    }
+
+    /// `area_light` should cover the same shape, and be among the scene's lights
+    /// for the surface to light others.
+    pub fn with_area_light(self, area_light: &'a dyn AreaLight) -> Self {
+        Self {
+            area_light: Some(area_light),
+            ..self
+        }
+    }
+}
+
+impl<'a> Primitive for GeometricPrimitive<'a> {
+    fn test(&self, ray: &Ray) -> Option<Interaction<'_>> {
+        Some(self.shape.test(ray)?.with_primitive(self))
+    }
+
+    fn material(&self) -> Option<&dyn Material> {
+        Some(self.material)
+    }
+
+    fn area_light(&self) -> Option<&dyn AreaLight> {
+        self.area_light
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::{Color3f, Point3f, Vec3f};
+    use crate::materials::lambertian_material::LambertianMaterial;
+    use crate::primitives::sphere::Sphere;
+    use crate::textures::constant_texture::ConstantTexture;
+
+    #[test]
+    fn hits_know_their_material() {
+        let sphere = Sphere::new(Point3f::default(), 1.0);
+        let albedo = ConstantTexture::new(Color3f::new(0.5, 0.5, 0.5));
+        let material = LambertianMaterial::new(&albedo);
+        let primitive = GeometricPrimitive::new(&sphere, &material);
+
+        let ray = Ray::new(
+            Point3f::new(0.0, 0.0, 5.0),
+            Vec3f::new(0.0, 0.0, -1.0),
+            0.0,
+            100.0,
+        );
+        assert!(sphere.test(&ray).unwrap().material().is_none());
+
+        let hit = primitive.test(&ray).unwrap();
+        let found = hit.material().unwrap();
+        assert!(std::ptr::eq(
+            found as *const dyn Material as *const u8,
+            &material as *const LambertianMaterial as *const u8
+        ));
+        // The shape's surface details are kept
+        assert_eq!(hit.uv, sphere.test(&ray).unwrap().uv);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -1 +1,4 @@
-pub mod sphere;
\ No newline at end of file
+pub mod alpha_masked_primitive;
+pub mod geometric_primitive;
+pub mod sphere;
+pub mod triangle;
\ No newline at end of file
@@ -1,7 +1,13 @@
+use std::f64::consts::PI;
+
+use crate::core::bounds::{Bounds3f, DirectionCone};
+use crate::core::frame::Frame;
 use crate::core::interaction::Interaction;
 use crate::core::primitive::Primitive;
 use crate::core::ray::Ray;
-use crate::core::vector::{Point3f, Vec3f};
+use crate::core::shape::{Shape, ShapeSample};
+use crate::core::vector::{Point2f, Point3f, Vec3f};
+use crate::math::sampling::sample_uniform_sphere;
 use crate::math::{solve_quadratic, QuadraticSolution};
 
 /////////////////////
@@ -27,10 +33,59 @@ impl Sphere {
     fn normal_at_point(&self, p: Point3f) -> Vec3f {
         (p - self.center).normalize()
     }
+
+    /// Parameterizes the surface by spherical coordinates around z,
+    /// `u = phi / 2pi` and `v = theta / pi`, with `v = 0` at the top.
+    fn interaction_at(&self, p: Point3f, t: f64, wo: Vec3f) -> Interaction<'_> {
+        let d = p - self.center;
+        let phi = d.y.atan2(d.x).rem_euclid(2.0 * PI);
+        let cos_theta = (d.z / self.radius).clamp(-1.0, 1.0);
+        let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+        let uv = Point2f::new(phi / (2.0 * PI), cos_theta.acos() / PI);
+
+        let dpdu = Vec3f::new(-d.y, d.x, 0.0) * (2.0 * PI);
+        let dpdv = Vec3f::new(d.z * phi.cos(), d.z * phi.sin(), -self.radius * sin_theta) * PI;
+        // The normal is the position over the radius, so it changes alike
+        let (dndu, dndv) = (dpdu / self.radius, dpdv / self.radius);
+
+        Interaction::new_on_surface(p, t, self.normal_at_point(p), wo)
+            .with_uv(uv)
+            .with_derivatives(dpdu, dpdv, dndu, dndv)
+            .with_primitive(self)
+    }
+
+    /// The sine squared and one minus the cosine of the half angle of the cone the sphere
+    /// fills as seen from `reference`, [None] from inside or on the surface,
+    /// where the cone degenerates.
+    fn cone_from(&self, reference: &Point3f) -> Option<(f64, f64)> {
+        let distance_sq = (self.center - *reference).length_sq();
+        let sin2_theta_max = self.radius * self.radius / distance_sq;
+        if sin2_theta_max >= 1.0 - ON_SURFACE_EPSILON {
+            return None;
+        }
+        // Cancellation ruins 1 - cos for distant spheres, use its Taylor expansion instead
+        let one_minus_cos_theta_max = if sin2_theta_max < SMALL_SIN2_THETA {
+            sin2_theta_max / 2.0
+        } else {
+            1.0 - (1.0 - sin2_theta_max).sqrt()
+        };
+        Some((sin2_theta_max, one_minus_cos_theta_max))
+    }
 }
 
+/// How close to one the squared sine of the cone may get before the reference
+/// counts as lying on the surface, such as points the sphere itself was hit at.
+const ON_SURFACE_EPSILON: f64 = 1e-4;
+
+/// Keeps the rays [Shape::pdf] finds points with from hitting where they start.
+const PDF_RAY_EPSILON: f64 = 1e-6;
+
+/// Below this squared sine, cones around distant spheres are handled with small angle
+/// approximations.
+const SMALL_SIN2_THETA: f64 = 0.00068523;
+
 impl Primitive for Sphere {
-    fn test(&self, ray: &Ray) -> Option<Interaction> {
+    fn test(&self, ray: &Ray) -> Option<Interaction<'_>> {
         let a = ray.d.dot(&ray.d);
         let b = 2.0 * (ray.o - self.center).dot(&ray.d);
         let c = self.center.dot(&self.center) + ray.o.dot(&ray.o)
@@ -39,15 +94,10 @@ impl Primitive for Sphere {
 
         match solve_quadratic(a, b, c)? {
             QuadraticSolution::None => None,
-            QuadraticSolution::One { x } => {
-                let p = ray.at(x)?;
-                Some(Interaction::new_on_surface(
-                    p,
-                    x,
-                    self.normal_at_point(p),
-                    -ray.d,
-                ))
-            }
+            QuadraticSolution::One { x } => Some(
+                self.interaction_at(ray.at(x)?, x, -ray.d)
+                    .with_ray_differentials(ray),
+            ),
             QuadraticSolution::Two { x1, x2 } => {
                 let closest = x1.min(x2);
                 let maybe_location = ray.at(closest);
@@ -58,17 +108,99 @@ impl Primitive for Sphere {
                     }
                     Some(location) => (location, closest),
                 };
-                Some(Interaction::new_on_surface(
-                    p,
-                    t,
-                    self.normal_at_point(p),
-                    -ray.d,
-                ))
+                Some(
+                    self.interaction_at(p, t, -ray.d)
+                        .with_ray_differentials(ray),
+                )
             }
         }
     }
 }
 
+impl Shape for Sphere {
+    fn area(&self) -> f64 {
+        4.0 * PI * self.radius * self.radius
+    }
+
+    fn bounds(&self) -> Bounds3f {
+        let r = Vec3f::new(self.radius, self.radius, self.radius);
+        Bounds3f::new(self.center - r, self.center + r)
+    }
+
+    fn normal_bounds(&self) -> DirectionCone {
+        DirectionCone::entire_sphere()
+    }
+
+    fn sample_area(&self, u: &Point2f) -> Option<ShapeSample<'_>> {
+        let n = sample_uniform_sphere(u);
+        Some(ShapeSample {
+            interaction: self.interaction_at(self.center + n * self.radius, 0.0, n),
+            pdf: 1.0 / self.area(),
+        })
+    }
+
+    /// Picks uniformly within the cone of directions the sphere fills,
+    /// or by area from inside it.
+    fn sample(&self, reference: &Point3f, u: &Point2f) -> Option<ShapeSample<'_>> {
+        let Some((sin2_theta_max, one_minus_cos_theta_max)) = self.cone_from(reference) else {
+            return self.sample_area(u)?.area_to_solid_angle(reference);
+        };
+
+        let sin_theta_max = sin2_theta_max.sqrt();
+        let (cos_theta, sin2_theta) = if sin2_theta_max < SMALL_SIN2_THETA {
+            let sin2_theta = sin2_theta_max * u.x;
+            ((1.0 - sin2_theta).sqrt(), sin2_theta)
+        } else {
+            let cos_theta = 1.0 - one_minus_cos_theta_max * u.x;
+            (cos_theta, 1.0 - cos_theta * cos_theta)
+        };
+
+        // The angle at the center between the reference and the point seen along theta
+        let cos_alpha = sin2_theta / sin_theta_max
+            + cos_theta * (1.0 - sin2_theta / sin2_theta_max).max(0.0).sqrt();
+        let sin_alpha = (1.0 - cos_alpha * cos_alpha).max(0.0).sqrt();
+        let phi = 2.0 * PI * u.y;
+
+        let frame = Frame::from_normal((self.center - *reference).normalize());
+        let n = frame.local_to_world(&Vec3f::new(
+            -sin_alpha * phi.cos(),
+            -sin_alpha * phi.sin(),
+            -cos_alpha,
+        ));
+        let p = self.center + n * self.radius;
+        let to_reference = *reference - p;
+        Some(ShapeSample {
+            interaction: self.interaction_at(p, to_reference.length(), to_reference.normalize()),
+            pdf: 1.0 / (2.0 * PI * one_minus_cos_theta_max),
+        })
+    }
+
+    fn pdf(&self, reference: &Point3f, wi: &Vec3f) -> f64 {
+        let wi = wi.normalize();
+        let Some((_, one_minus_cos_theta_max)) = self.cone_from(reference) else {
+            // Starting a little off, as the reference is often on the sphere itself
+            let ray = Ray::new(*reference, wi, PDF_RAY_EPSILON, f64::INFINITY);
+            let Some(hit) = self.test(&ray) else {
+                return 0.0;
+            };
+            let sample = ShapeSample {
+                interaction: hit,
+                pdf: 1.0 / self.area(),
+            };
+            return sample
+                .area_to_solid_angle(reference)
+                .map_or(0.0, |sample| sample.pdf);
+        };
+
+        let to_center = self.center - *reference;
+        let cos_theta = wi.dot(&to_center) / to_center.length();
+        if cos_theta < 1.0 - one_minus_cos_theta_max {
+            return 0.0;
+        }
+        1.0 / (2.0 * PI * one_minus_cos_theta_max)
+    }
+}
+
 ////////////////////////
 // END IMPLEMENTATION //
 // BEGIN TESTS        //
@@ -163,6 +295,94 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn uv_and_derivatives() {
+        let sphere = Sphere::new(Point3f::new(1.0, 2.0, 3.0), 2.0);
+        let hit_towards = |target: Vec3f| {
+            let o = Point3f::new(1.0, 2.0, 3.0) + target * 10.0;
+            sphere.test(&Ray::new(o, -target, 0.0, 100.0)).unwrap()
+        };
+
+        let top = hit_towards(Vec3f::new(0.0, 0.0, 1.0));
+        assert!(top.uv.y.abs() < 1e-9);
+        let side = hit_towards(Vec3f::new(0.0, 1.0, 0.0));
+        assert!((side.uv.x - 0.25).abs() < 1e-9 && (side.uv.y - 0.5).abs() < 1e-9);
+
+        // Derivatives match finite differences of the parameterization
+        let hit = hit_towards(Vec3f::new(0.3, -0.5, 0.4).normalize());
+        let at = |u: f64, v: f64| {
+            let (phi, theta) = (2.0 * PI * u, PI * v);
+            Point3f::new(1.0, 2.0, 3.0)
+                + Vec3f::new(
+                    theta.sin() * phi.cos(),
+                    theta.sin() * phi.sin(),
+                    theta.cos(),
+                ) * 2.0
+        };
+        let h = 1e-6;
+        let (u, v) = (hit.uv.x, hit.uv.y);
+        let dpdu = (at(u + h, v) - at(u - h, v)) / (2.0 * h);
+        let dpdv = (at(u, v + h) - at(u, v - h)) / (2.0 * h);
+        assert!((at(u, v) - hit.p).length() < 1e-9);
+        assert!((dpdu - hit.dpdu).length() < 1e-5);
+        assert!((dpdv - hit.dpdv).length() < 1e-5);
+        assert!((hit.dndu * 2.0 - hit.dpdu).length() < 1e-9);
+
+        // The shading frame follows dpdu
+        let frame = hit.shading_frame().unwrap();
+        assert!((frame.s - hit.dpdu.normalize()).length() < 1e-9);
+        assert!((frame.n - hit.n.unwrap()).length() < 1e-9);
+
+        assert!(std::ptr::eq(
+            hit.primitive.unwrap() as *const dyn Primitive as *const u8,
+            &sphere as *const Sphere as *const u8
+        ));
+    }
+
+    #[test]
+    fn cone_sampling() {
+        let sphere = Sphere::new(Point3f::new(0.0, 0.0, 1.0), 1.0);
+        let reference = Point3f::new(0.0, 0.0, -2.0);
+        let cos_theta_max = (1.0 - 1.0 / 9.0_f64).sqrt();
+        let expected_pdf = 1.0 / (2.0 * PI * (1.0 - cos_theta_max));
+
+        let n = 64;
+        let mut mean_cos = 0.0;
+        for i in 0..n {
+            for j in 0..n {
+                let u = Point2f::new((i as f64 + 0.5) / n as f64, (j as f64 + 0.5) / n as f64);
+                let sample = sphere.sample(&reference, &u).unwrap();
+                let p = sample.interaction.p;
+                assert!(((p - sphere.center).length() - 1.0).abs() < 1e-9);
+                // Only the side facing the reference is picked
+                let wi = (p - reference).normalize();
+                assert!(sample.interaction.n.unwrap().dot(&wi) <= 1e-9);
+                assert!((sample.interaction.wo + wi).length() < 1e-9);
+                assert!((sample.pdf - expected_pdf).abs() < 1e-9);
+                assert!((sphere.pdf(&reference, &wi) - expected_pdf).abs() < 1e-9);
+                mean_cos += wi.z / (n * n) as f64;
+            }
+        }
+        // Uniform over the cone
+        assert!((mean_cos - 0.5 * (1.0 + cos_theta_max)).abs() < 1e-4);
+        assert_eq!(sphere.pdf(&reference, &Vec3f::new(1.0, 0.0, 1.0)), 0.0);
+
+        // From the center every point is equally far and faces it
+        let center = sphere.center;
+        let sample = sphere.sample(&center, &Point2f::new(0.3, 0.7)).unwrap();
+        assert!((sample.pdf - 1.0 / (4.0 * PI)).abs() < 1e-9);
+        let pdf = sphere.pdf(&center, &Vec3f::new(0.2, -0.4, 0.1));
+        assert!((pdf - 1.0 / (4.0 * PI)).abs() < 1e-9);
+
+        // From a point on the surface, across to the opposite point two away
+        let on_surface = center + Vec3f::new(0.6, 0.0, -0.8);
+        let pdf = sphere.pdf(&on_surface, &Vec3f::new(-0.6, 0.0, 0.8));
+        assert!((pdf - 1.0 / PI).abs() < 1e-9, "{}", pdf);
+        let sample = sphere.sample(&on_surface, &Point2f::new(0.3, 0.7)).unwrap();
+        let wi = (sample.interaction.p - on_surface).normalize();
+        assert!((sample.pdf - sphere.pdf(&on_surface, &wi)).abs() < 1e-6);
+    }
+
     #[test]
     fn test_outside_on_line_behind_no_hit() {
         let sphere = unit_sphere();
@@ -0,0 +1,266 @@
+use crate::core::bounds::{Bounds3f, DirectionCone};
+use crate::core::frame::Frame;
+use crate::core::interaction::Interaction;
+use crate::core::primitive::Primitive;
+use crate::core::ray::Ray;
+use crate::core::shape::{Shape, ShapeSample};
+use crate::core::vector::{Point2f, Point3f, Vec3f};
+use crate::math::sampling::{
+    sample_spherical_triangle, sample_uniform_triangle, spherical_triangle_area,
+};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A flat triangle, facing the side its vertices wind counterclockwise around.
+#[derive(Debug, PartialEq)]
+pub struct Triangle {
+    vertices: [Point3f; 3],
+    uvs: [Point2f; 3],
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+/// Triangles covering less solid angle than this are sampled by area,
+/// where spherical sampling loses precision.
+const MIN_SPHERICAL_SAMPLE_AREA: f64 = 3e-4;
+
+/// Triangles covering more solid angle than this are sampled by area,
+/// as they are seen nearly edge on from within their plane.
+const MAX_SPHERICAL_SAMPLE_AREA: f64 = 6.22;
+
+impl Triangle {
+    /// Parameterizes the surface with `(0, 0)`, `(1, 0)` and `(1, 1)` at the vertices
+    /// unless told otherwise.
+    pub fn new(p0: Point3f, p1: Point3f, p2: Point3f) -> Self {
+        Self {
+            vertices: [p0, p1, p2],
+            uvs: [
+                Point2f::new(0.0, 0.0),
+                Point2f::new(1.0, 0.0),
+                Point2f::new(1.0, 1.0),
+            ],
+        }
+    }
+
+    pub fn with_uvs(self, uvs: [Point2f; 3]) -> Self {
+        Self { uvs, ..self }
+    }
+
+    fn normal(&self) -> Vec3f {
+        let [p0, p1, p2] = self.vertices;
+        (p1 - p0).cross(&(p2 - p0)).normalize()
+    }
+
+    /// The point with barycentric coordinates `b`.
+    fn interaction_at(&self, b: [f64; 3], t: f64, wo: Vec3f) -> Interaction<'_> {
+        let [p0, p1, p2] = self.vertices;
+        let [uv0, uv1, uv2] = self.uvs;
+        let p = p0 * b[0] + p1 * b[1] + p2 * b[2];
+        let uv = uv0 * b[0] + uv1 * b[1] + uv2 * b[2];
+        let n = self.normal();
+
+        let (duv02, duv12) = (uv0 - uv2, uv1 - uv2);
+        let (dp02, dp12) = (p0 - p2, p1 - p2);
+        let determinant = duv02.x * duv12.y - duv02.y * duv12.x;
+        let (dpdu, dpdv) = if determinant.abs() < 1e-12 {
+            // Degenerate uvs, any frame around the normal does
+            let frame = Frame::from_normal(n);
+            (frame.s, frame.t)
+        } else {
+            (
+                (dp02 * duv12.y - dp12 * duv02.y) / determinant,
+                (dp12 * duv02.x - dp02 * duv12.x) / determinant,
+            )
+        };
+
+        let zero = Vec3f::default();
+        Interaction::new_on_surface(p, t, n, wo)
+            .with_uv(uv)
+            .with_derivatives(dpdu, dpdv, zero, zero)
+            .with_barycentrics(b)
+            .with_primitive(self)
+    }
+}
+
+impl Primitive for Triangle {
+    /// Möller and Trumbore's ray triangle intersection.
+    fn test(&self, ray: &Ray) -> Option<Interaction<'_>> {
+        let [p0, p1, p2] = self.vertices;
+        let (e1, e2) = (p1 - p0, p2 - p0);
+        let pvec = ray.d.cross(&e2);
+        let determinant = e1.dot(&pvec);
+        if determinant == 0.0 {
+            return None;
+        }
+
+        let s = ray.o - p0;
+        let b1 = s.dot(&pvec) / determinant;
+        if !(0.0..=1.0).contains(&b1) {
+            return None;
+        }
+        let qvec = s.cross(&e1);
+        let b2 = ray.d.dot(&qvec) / determinant;
+        if b2 < 0.0 || b1 + b2 > 1.0 {
+            return None;
+        }
+
+        let t = e2.dot(&qvec) / determinant;
+        ray.at(t)?;
+        Some(
+            self.interaction_at([1.0 - b1 - b2, b1, b2], t, -ray.d)
+                .with_ray_differentials(ray),
+        )
+    }
+}
+
+impl Shape for Triangle {
+    fn area(&self) -> f64 {
+        let [p0, p1, p2] = self.vertices;
+        0.5 * (p1 - p0).cross(&(p2 - p0)).length()
+    }
+
+    fn bounds(&self) -> Bounds3f {
+        let [p0, p1, p2] = self.vertices;
+        Bounds3f::from_point(p0)
+            .union(&Bounds3f::from_point(p1))
+            .union(&Bounds3f::from_point(p2))
+    }
+
+    fn normal_bounds(&self) -> DirectionCone {
+        DirectionCone::new(self.normal(), 1.0)
+    }
+
+    fn sample_area(&self, u: &Point2f) -> Option<ShapeSample<'_>> {
+        Some(ShapeSample {
+            interaction: self.interaction_at(sample_uniform_triangle(u), 0.0, self.normal()),
+            pdf: 1.0 / self.area(),
+        })
+    }
+
+    /// Picks uniformly within the solid angle the triangle covers, unless that is tiny or huge.
+    fn sample(&self, reference: &Point3f, u: &Point2f) -> Option<ShapeSample<'_>> {
+        let solid_angle = spherical_triangle_area(&self.vertices, reference);
+        if !(MIN_SPHERICAL_SAMPLE_AREA..=MAX_SPHERICAL_SAMPLE_AREA).contains(&solid_angle) {
+            return self.sample_area(u)?.area_to_solid_angle(reference);
+        }
+
+        let (b, pdf) = sample_spherical_triangle(&self.vertices, reference, u)?;
+        let [p0, p1, p2] = self.vertices;
+        let to_reference = *reference - (p0 * b[0] + p1 * b[1] + p2 * b[2]);
+        Some(ShapeSample {
+            interaction: self.interaction_at(b, to_reference.length(), to_reference.normalize()),
+            pdf,
+        })
+    }
+
+    fn pdf(&self, reference: &Point3f, wi: &Vec3f) -> f64 {
+        let ray = Ray::new(*reference, wi.normalize(), 0.0, f64::INFINITY);
+        let Some(hit) = self.test(&ray) else {
+            return 0.0;
+        };
+
+        let solid_angle = spherical_triangle_area(&self.vertices, reference);
+        if (MIN_SPHERICAL_SAMPLE_AREA..=MAX_SPHERICAL_SAMPLE_AREA).contains(&solid_angle) {
+            return 1.0 / solid_angle;
+        }
+        let sample = ShapeSample {
+            interaction: hit,
+            pdf: 1.0 / self.area(),
+        };
+        sample
+            .area_to_solid_angle(reference)
+            .map_or(0.0, |sample| sample.pdf)
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn right_triangle() -> Triangle {
+        Triangle::new(
+            Point3f::new(0.0, 0.0, 0.0),
+            Point3f::new(2.0, 0.0, 0.0),
+            Point3f::new(0.0, 2.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn hits() {
+        let triangle = right_triangle();
+        let down = Vec3f::new(0.0, 0.0, -1.0);
+
+        let hit = triangle
+            .test(&Ray::new(Point3f::new(0.5, 0.5, 3.0), down, 0.0, 100.0))
+            .unwrap();
+        assert!((hit.p - Point3f::new(0.5, 0.5, 0.0)).length() < 1e-12);
+        assert_eq!(hit.t, 3.0);
+        assert_eq!(hit.n, Some(Vec3f::new(0.0, 0.0, 1.0)));
+
+        // The parameterization follows the vertex uvs
+        assert!((hit.uv - Point2f::new(0.5, 0.25)).x.abs() < 1e-12);
+        assert!((hit.uv - Point2f::new(0.5, 0.25)).y.abs() < 1e-12);
+        assert!((hit.dpdu - Vec3f::new(2.0, 0.0, 0.0)).length() < 1e-12);
+        assert!((hit.dpdv - Vec3f::new(-2.0, 2.0, 0.0)).length() < 1e-12);
+        let b = hit.barycentrics.unwrap();
+        assert!((b[0] - 0.5).abs() + (b[1] - 0.25).abs() + (b[2] - 0.25).abs() < 1e-12);
+
+        // Misses outside the edges and beyond the ray
+        let outside = Ray::new(Point3f::new(1.5, 1.5, 3.0), down, 0.0, 100.0);
+        assert!(triangle.test(&outside).is_none());
+        let short = Ray::new(Point3f::new(0.5, 0.5, 3.0), down, 0.0, 2.0);
+        assert!(triangle.test(&short).is_none());
+        let parallel = Ray::new(
+            Point3f::new(0.5, 0.5, 0.0),
+            Vec3f::new(1.0, 0.0, 0.0),
+            0.0,
+            100.0,
+        );
+        assert!(triangle.test(&parallel).is_none());
+    }
+
+    #[test]
+    fn solid_angle_sampling() {
+        let triangle = right_triangle();
+        let reference = Point3f::new(0.5, 0.5, 1.0);
+        let solid_angle = spherical_triangle_area(&triangle.vertices, &reference);
+
+        let n = 32;
+        for i in 0..n {
+            for j in 0..n {
+                let u = Point2f::new((i as f64 + 0.5) / n as f64, (j as f64 + 0.5) / n as f64);
+                let sample = triangle.sample(&reference, &u).unwrap();
+                let p = sample.interaction.p;
+                assert!(p.z.abs() < 1e-12 && p.x >= -1e-12 && p.y >= -1e-12);
+                assert!(p.x + p.y <= 2.0 + 1e-9);
+                assert!((sample.pdf - 1.0 / solid_angle).abs() < 1e-9);
+
+                let wi = p - reference;
+                assert!((sample.interaction.wo + wi.normalize()).length() < 1e-9);
+                assert!((triangle.pdf(&reference, &wi) - sample.pdf).abs() < 1e-9);
+            }
+        }
+        assert_eq!(triangle.pdf(&reference, &Vec3f::new(0.0, 0.0, 1.0)), 0.0);
+
+        // Far away, the triangle is sampled by area
+        let far = Point3f::new(0.5, 0.5, 1000.0);
+        let sample = triangle.sample(&far, &Point2f::new(0.5, 0.5)).unwrap();
+        let distance_sq = (far - sample.interaction.p).length_sq();
+        let cos_theta = (far - sample.interaction.p).normalize().z;
+        assert!((sample.pdf - distance_sq / (cos_theta * triangle.area())).abs() < 1e-6);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
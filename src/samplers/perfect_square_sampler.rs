@@ -2,14 +2,21 @@
 // BEGIN INTERFACE //
 /////////////////////
 
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
 use crate::core::{sample::Sample, sampler::Sampler};
 
 /// Implemntation of [Sampler] that samples evenly within a pixel
 pub struct PerfectSquareSampler {
     samples_per_row: usize,
     samples_per_col: usize,
+    samples_per_pixel: usize,
     samples: usize,
     current_sample: usize,
+
+    /// The values handed out for the current sample, seeded with its index
+    rng: SmallRng,
 }
 
 //////////////////////////
@@ -25,8 +32,10 @@ impl PerfectSquareSampler {
         Self {
             samples_per_row,
             samples_per_col,
+            samples_per_pixel: sqrt_samples_per_pixel * sqrt_samples_per_pixel,
             samples: samples_per_row * samples_per_col,
             current_sample: 0,
+            rng: SmallRng::seed_from_u64(0),
         }
     }
 }
@@ -39,12 +48,21 @@ impl Sampler for PerfectSquareSampler {
             let x =
                 (self.current_sample % self.samples_per_row) as f64 / self.samples_per_row as f64;
 
+            self.rng = SmallRng::seed_from_u64(self.current_sample as u64);
             self.current_sample += 1;
             Some(Sample::new(x, y))
         } else {
             None
         }
     }
+
+    fn samples_per_pixel(&self) -> usize {
+        self.samples_per_pixel
+    }
+
+    fn get_1d(&mut self) -> f64 {
+        self.rng.gen()
+    }
 }
 
 ////////////////////////
@@ -52,6 +70,43 @@ impl Sampler for PerfectSquareSampler {
 // BEGIN TESTS        //
 ////////////////////////
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_belong_to_samples() {
+        let mut sampler = PerfectSquareSampler::new(2, 2, 1);
+        let mut values = Vec::new();
+        while sampler.next_sample().is_some() {
+            let value = sampler.get_1d();
+            assert!((0.0..1.0).contains(&value));
+            values.push(value);
+        }
+        assert_eq!(values.len(), 4);
+
+        // The same sample gets the same values, however many were drawn before
+        let mut sampler = PerfectSquareSampler::new(2, 2, 1);
+        sampler.next_sample();
+        sampler.get_2d();
+        sampler.next_sample();
+        assert_eq!(sampler.get_1d(), values[1]);
+        assert_ne!(values[0], values[1]);
+    }
+
+    #[test]
+    fn counts_samples_per_pixel() {
+        let mut sampler = PerfectSquareSampler::new(2, 3, 4);
+        assert_eq!(sampler.samples_per_pixel(), 16);
+
+        let mut count = 0;
+        while sampler.next_sample().is_some() {
+            count += 1;
+        }
+        assert_eq!(count, 2 * 3 * sampler.samples_per_pixel());
+    }
+}
+
 ///////////////
 // END TESTS //
 ///////////////
@@ -0,0 +1,89 @@
+use crate::core::interaction::Interaction;
+use crate::core::texture::{Texture, TextureMapping2D};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Alternates between two [Texture]s on a grid of unit squares in texture space.
+pub struct CheckerboardTexture<'a, T> {
+    mapping: &'a dyn TextureMapping2D,
+
+    /// Used where the sum of the square's coordinates is even
+    even: &'a dyn Texture<T>,
+
+    /// Used where the sum of the square's coordinates is odd
+    odd: &'a dyn Texture<T>,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<'a, T> CheckerboardTexture<'a, T> {
+    pub fn new(
+        mapping: &'a dyn TextureMapping2D,
+        even: &'a dyn Texture<T>,
+        odd: &'a dyn Texture<T>,
+    ) -> Self {
+        Self { mapping, even, odd }
+    }
+}
+
+impl<'a, T> Texture<T> for CheckerboardTexture<'a, T> {
+    fn evaluate(&self, interaction: &Interaction) -> T {
+        let st = self.mapping.map(interaction);
+        let square = st.x.floor() as i64 + st.y.floor() as i64;
+        if square.rem_euclid(2) == 0 {
+            self.even.evaluate(interaction)
+        } else {
+            self.odd.evaluate(interaction)
+        }
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::{Point3f, Vec3f};
+    use crate::textures::constant_texture::ConstantTexture;
+    use crate::textures::planar_mapping::PlanarMapping;
+
+    #[test]
+    fn alternates() {
+        let mapping = PlanarMapping::new(
+            Vec3f::new(1.0, 0.0, 0.0),
+            Vec3f::new(0.0, 1.0, 0.0),
+            0.0,
+            0.0,
+        );
+        let (black, white) = (ConstantTexture::new(0.0), ConstantTexture::new(1.0));
+        let texture = CheckerboardTexture::new(&mapping, &black, &white);
+
+        let at = |x: f64, y: f64| {
+            let n = Vec3f::new(0.0, 0.0, 1.0);
+            texture.evaluate(&Interaction::new_on_surface(
+                Point3f::new(x, y, 0.0),
+                1.0,
+                n,
+                n,
+            ))
+        };
+        assert_eq!(at(0.5, 0.5), 0.0);
+        assert_eq!(at(1.5, 0.5), 1.0);
+        assert_eq!(at(1.5, 1.5), 0.0);
+        // Negative coordinates continue the pattern
+        assert_eq!(at(-0.5, 0.5), 1.0);
+        assert_eq!(at(-0.5, -0.5), 0.0);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,38 @@
+use crate::core::interaction::Interaction;
+use crate::core::texture::Texture;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// A [Texture] that evaluates to the same value everywhere.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConstantTexture<T> {
+    value: T,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<T> ConstantTexture<T> {
+    pub const fn new(value: T) -> Self {
+        Self { value }
+    }
+}
+
+impl<T: Copy> Texture<T> for ConstantTexture<T> {
+    fn evaluate(&self, _interaction: &Interaction) -> T {
+        self.value
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+///////////////
+// END TESTS //
+///////////////
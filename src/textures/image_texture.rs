@@ -0,0 +1,100 @@
+use std::ops::{Add, Mul};
+
+use crate::core::interaction::Interaction;
+use crate::core::mipmap::{FilterMode, MipMap};
+use crate::core::texture::{Texture, TextureMapping2D};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Looks a [MipMap] up at the texture coordinates of a hit,
+/// filtered over the area of the pixel in texture space.
+pub struct ImageTexture<'a, T> {
+    mipmap: MipMap<T>,
+    mapping: &'a dyn TextureMapping2D,
+    filter: FilterMode,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<'a, T> ImageTexture<'a, T> {
+    /// Filters trilinearly unless told otherwise.
+    pub fn new(mipmap: MipMap<T>, mapping: &'a dyn TextureMapping2D) -> Self {
+        Self {
+            mipmap,
+            mapping,
+            filter: FilterMode::Trilinear,
+        }
+    }
+
+    pub fn with_filter(self, filter: FilterMode) -> Self {
+        Self { filter, ..self }
+    }
+}
+
+impl<'a, T: Copy + Default + Add<Output = T> + Mul<f64, Output = T>> Texture<T>
+    for ImageTexture<'a, T>
+{
+    fn evaluate(&self, interaction: &Interaction) -> T {
+        let c = self.mapping.map_with_differentials(interaction);
+        self.mipmap.filter(&c.st, &c.dst_dx, &c.dst_dy, self.filter)
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::image::{Image, WrapMode};
+    use crate::core::vector::{Color3f, Point3f, Vec3f};
+    use crate::textures::planar_mapping::PlanarMapping;
+
+    #[test]
+    fn follows_mapping() {
+        let red = Color3f::new(1.0, 0.0, 0.0);
+        let green = Color3f::new(0.0, 1.0, 0.0);
+        let image = Image::new(2, 2, vec![red, green, green, red]);
+        // One repetition every two units
+        let mapping = PlanarMapping::new(
+            Vec3f::new(0.5, 0.0, 0.0),
+            Vec3f::new(0.0, 0.5, 0.0),
+            0.0,
+            0.0,
+        );
+
+        for wrap in [WrapMode::Repeat, WrapMode::Mirror] {
+            let texture = ImageTexture::new(MipMap::new(image.clone(), wrap), &mapping)
+                .with_filter(FilterMode::Ewa {
+                    max_anisotropy: 8.0,
+                });
+
+            let at = |x: f64, y: f64| {
+                let n = Vec3f::new(0.0, 0.0, 1.0);
+                texture.evaluate(&Interaction::new_on_surface(
+                    Point3f::new(x, y, 0.0),
+                    1.0,
+                    n,
+                    n,
+                ))
+            };
+            assert_eq!(at(0.5, 0.5), red);
+            assert_eq!(at(1.5, 0.5), green);
+            assert_eq!(at(0.5, 1.5), green);
+            // The next tile over is flipped when mirroring
+            let expected = if wrap == WrapMode::Repeat { red } else { green };
+            assert_eq!(at(2.5, 0.5), expected);
+        }
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
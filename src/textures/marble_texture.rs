@@ -0,0 +1,100 @@
+use std::f64::consts::PI;
+
+use crate::core::interaction::Interaction;
+use crate::core::texture::{Texture, TextureMapping3D};
+use crate::core::vector::Color3f;
+use crate::math::noise::turbulence;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Solid marble: bands along z, one per unit, bent into veins by turbulence.
+pub struct MarbleTexture<'a> {
+    mapping: &'a dyn TextureMapping3D,
+    light: &'a dyn Texture<Color3f>,
+    dark: &'a dyn Texture<Color3f>,
+
+    /// How far the bands are bent, 0.0 leaving them straight
+    variation: f64,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+const OCTAVES: usize = 6;
+const ROUGHNESS: f64 = 0.5;
+
+impl<'a> MarbleTexture<'a> {
+    pub fn new(
+        mapping: &'a dyn TextureMapping3D,
+        light: &'a dyn Texture<Color3f>,
+        dark: &'a dyn Texture<Color3f>,
+        variation: f64,
+    ) -> Self {
+        Self {
+            mapping,
+            light,
+            dark,
+            variation,
+        }
+    }
+}
+
+impl<'a> Texture<Color3f> for MarbleTexture<'a> {
+    fn evaluate(&self, interaction: &Interaction) -> Color3f {
+        let p = self.mapping.map(interaction);
+        let phase = p.z + self.variation * turbulence(&p, OCTAVES, ROUGHNESS);
+        let t = 0.5 + 0.5 * (2.0 * PI * phase).sin();
+        self.dark.evaluate(interaction) * (1.0 - t) + self.light.evaluate(interaction) * t
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::{Point3f, Vec3f};
+    use crate::textures::constant_texture::ConstantTexture;
+    use crate::textures::point_mapping::PointMapping;
+
+    #[test]
+    fn bands_between_colors() {
+        let mapping = PointMapping::new(Point3f::default(), 1.0);
+        let light = ConstantTexture::new(Color3f::new(0.9, 0.9, 0.85));
+        let dark = ConstantTexture::new(Color3f::new(0.2, 0.2, 0.3));
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let at = |texture: &MarbleTexture, x: f64, z: f64| {
+            texture.evaluate(&Interaction::new_on_surface(
+                Point3f::new(x, 0.3, z),
+                1.0,
+                n,
+                n,
+            ))
+        };
+
+        // Straight bands only change along z
+        let straight = MarbleTexture::new(&mapping, &light, &dark, 0.0);
+        assert!((at(&straight, 0.0, 0.25) - Color3f::new(0.9, 0.9, 0.85)).length() < 1e-12);
+        assert!((at(&straight, 5.0, 0.75) - Color3f::new(0.2, 0.2, 0.3)).length() < 1e-12);
+
+        let veined = MarbleTexture::new(&mapping, &light, &dark, 2.0);
+        for i in 0..1000 {
+            let (x, z) = (i as f64 * 0.031, i as f64 * 0.017);
+            let color = at(&veined, x, z);
+            assert_eq!(color, at(&veined, x, z));
+            assert!(color.x >= 0.2 - 1e-12 && color.x <= 0.9 + 1e-12);
+            assert!(color.z >= 0.3 - 1e-12 && color.z <= 0.85 + 1e-12);
+        }
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
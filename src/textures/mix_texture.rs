@@ -0,0 +1,66 @@
+use std::ops::{Add, Mul};
+
+use crate::core::interaction::Interaction;
+use crate::core::texture::Texture;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Linearly blends between two [Texture]s by a third.
+pub struct MixTexture<'a, T> {
+    a: &'a dyn Texture<T>,
+    b: &'a dyn Texture<T>,
+
+    /// How much of `b` to use, 0.0 giving only `a`
+    amount: &'a dyn Texture<f64>,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<'a, T> MixTexture<'a, T> {
+    pub fn new(a: &'a dyn Texture<T>, b: &'a dyn Texture<T>, amount: &'a dyn Texture<f64>) -> Self {
+        Self { a, b, amount }
+    }
+}
+
+impl<'a, T: Add<Output = T> + Mul<f64, Output = T>> Texture<T> for MixTexture<'a, T> {
+    fn evaluate(&self, interaction: &Interaction) -> T {
+        let amount = self.amount.evaluate(interaction);
+        self.a.evaluate(interaction) * (1.0 - amount) + self.b.evaluate(interaction) * amount
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::{Color3f, Point3f, Vec3f};
+    use crate::textures::constant_texture::ConstantTexture;
+
+    #[test]
+    fn blends() {
+        let red = ConstantTexture::new(Color3f::new(1.0, 0.0, 0.0));
+        let blue = ConstantTexture::new(Color3f::new(0.0, 0.0, 1.0));
+        let amount = ConstantTexture::new(0.25);
+        let texture = MixTexture::new(&red, &blue, &amount);
+
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let interaction = Interaction::new_on_surface(Point3f::default(), 1.0, n, n);
+        assert_eq!(
+            texture.evaluate(&interaction),
+            Color3f::new(0.75, 0.0, 0.25)
+        );
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
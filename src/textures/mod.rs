@@ -0,0 +1,13 @@
+pub mod checkerboard_texture;
+pub mod constant_texture;
+pub mod image_texture;
+pub mod marble_texture;
+pub mod mix_texture;
+pub mod noise_texture;
+pub mod planar_mapping;
+pub mod point_mapping;
+pub mod scale_texture;
+pub mod spherical_mapping;
+pub mod uv_mapping;
+pub mod wood_texture;
+pub mod worley_texture;
@@ -0,0 +1,104 @@
+use crate::core::interaction::Interaction;
+use crate::core::texture::{Texture, TextureMapping3D};
+use crate::math::noise::{fbm, perlin, turbulence};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Solid Perlin noise in [0.0, 1.0], for clouds, rust, dirt or bumpy roughness.
+pub struct NoiseTexture<'a> {
+    mapping: &'a dyn TextureMapping3D,
+    noise: Noise,
+}
+
+/// Which flavour of Perlin noise to use.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Noise {
+    /// A single octave of smooth noise
+    Perlin,
+
+    /// Octaves of finer noise layered on, each `roughness` times as strong as the last
+    Fbm { octaves: usize, roughness: f64 },
+
+    /// Like [Noise::Fbm] but folded about zero, giving creases and billows
+    Turbulence { octaves: usize, roughness: f64 },
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<'a> NoiseTexture<'a> {
+    pub fn new(mapping: &'a dyn TextureMapping3D, noise: Noise) -> Self {
+        Self { mapping, noise }
+    }
+}
+
+impl<'a> Texture<f64> for NoiseTexture<'a> {
+    fn evaluate(&self, interaction: &Interaction) -> f64 {
+        let p = self.mapping.map(interaction);
+        let v = match self.noise {
+            // Signed noise is recentered about 0.5
+            Noise::Perlin => 0.5 + 0.5 * perlin(&p),
+            Noise::Fbm { octaves, roughness } => 0.5 + 0.5 * fbm(&p, octaves, roughness),
+            Noise::Turbulence { octaves, roughness } => turbulence(&p, octaves, roughness),
+        };
+        v.clamp(0.0, 1.0)
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::{Point3f, Vec3f};
+    use crate::textures::point_mapping::PointMapping;
+
+    #[test]
+    fn deterministic_and_in_range() {
+        let mapping = PointMapping::new(Point3f::default(), 3.0);
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+
+        for noise in [
+            Noise::Perlin,
+            Noise::Fbm {
+                octaves: 5,
+                roughness: 0.5,
+            },
+            Noise::Turbulence {
+                octaves: 5,
+                roughness: 0.6,
+            },
+        ] {
+            let (a, b) = (
+                NoiseTexture::new(&mapping, noise),
+                NoiseTexture::new(&mapping, noise),
+            );
+            let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+            for i in 0..2000 {
+                let p = Point3f::new(
+                    (i as f64 * 0.37).sin(),
+                    (i as f64 * 0.11).cos(),
+                    i as f64 * 0.01,
+                );
+                let interaction = Interaction::new_on_surface(p, 1.0, n, n);
+                let v = a.evaluate(&interaction);
+                assert_eq!(v, b.evaluate(&interaction));
+                min = min.min(v);
+                max = max.max(v);
+            }
+            assert!(0.0 <= min && max <= 1.0);
+            assert!(max - min > 0.3, "{:?} is too flat", noise);
+        }
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,84 @@
+use crate::core::interaction::Interaction;
+use crate::core::texture::TextureMapping2D;
+use crate::core::vector::{Point2f, Vec3f};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Projects the hit onto two axes, like a slide projector aimed at the scene.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlanarMapping {
+    s: Vec3f,
+    t: Vec3f,
+
+    /// Offsets added to the projections
+    ds: f64,
+    dt: f64,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl PlanarMapping {
+    /// The length of `s` and `t` sets how often the texture repeats per unit distance.
+    pub fn new(s: Vec3f, t: Vec3f, ds: f64, dt: f64) -> Self {
+        Self { s, t, ds, dt }
+    }
+}
+
+impl TextureMapping2D for PlanarMapping {
+    fn map(&self, interaction: &Interaction) -> Point2f {
+        let p = interaction.p;
+        Point2f::new(self.ds + p.dot(&self.s), self.dt + p.dot(&self.t))
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::Point3f;
+
+    #[test]
+    fn projects_onto_axes() {
+        let mapping = PlanarMapping::new(
+            Vec3f::new(2.0, 0.0, 0.0),
+            Vec3f::new(0.0, 0.0, 1.0),
+            0.5,
+            0.0,
+        );
+        let n = Vec3f::new(0.0, 1.0, 0.0);
+        let interaction = Interaction::new_on_surface(Point3f::new(1.0, 7.0, 3.0), 1.0, n, n);
+        assert_eq!(mapping.map(&interaction), Point2f::new(2.5, 3.0));
+    }
+
+    #[test]
+    fn differentials() {
+        let mapping = PlanarMapping::new(
+            Vec3f::new(2.0, 0.0, 0.0),
+            Vec3f::new(0.0, 0.0, 1.0),
+            0.5,
+            0.0,
+        );
+        let n = Vec3f::new(0.0, 1.0, 0.0);
+        let mut interaction = Interaction::new_on_surface(Point3f::new(1.0, 0.0, 1.0), 1.0, n, n);
+        interaction.dpdx = Vec3f::new(0.01, 0.0, 0.0);
+        interaction.dpdy = Vec3f::new(0.0, 0.0, 0.03);
+
+        let c = mapping.map_with_differentials(&interaction);
+        assert_eq!(c.st, Point2f::new(2.5, 1.0));
+        assert!((c.dst_dx.x - 0.02).abs() < 1e-12 && c.dst_dx.y.abs() < 1e-12);
+        assert!(c.dst_dy.x.abs() < 1e-12 && (c.dst_dy.y - 0.03).abs() < 1e-12);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,58 @@
+use crate::core::interaction::Interaction;
+use crate::core::texture::TextureMapping3D;
+use crate::core::vector::Point3f;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Looks solid textures up at the hit point, relative to `origin` and scaled.
+/// With `origin` at the center of a primitive, the texture sticks to it as it is moved,
+/// as if evaluated in object space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointMapping {
+    origin: Point3f,
+
+    /// Multiplies distances, so larger values give finer patterns
+    scale: f64,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl PointMapping {
+    pub fn new(origin: Point3f, scale: f64) -> Self {
+        Self { origin, scale }
+    }
+}
+
+impl TextureMapping3D for PointMapping {
+    fn map(&self, interaction: &Interaction) -> Point3f {
+        (interaction.p - self.origin) * self.scale
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::Vec3f;
+
+    #[test]
+    fn relative_to_origin() {
+        let mapping = PointMapping::new(Point3f::new(1.0, 2.0, 3.0), 2.0);
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let interaction = Interaction::new_on_surface(Point3f::new(1.5, 2.0, 4.0), 1.0, n, n);
+        assert_eq!(mapping.map(&interaction), Point3f::new(1.0, 0.0, 2.0));
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
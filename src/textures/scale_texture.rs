@@ -0,0 +1,58 @@
+use std::ops::Mul;
+
+use crate::core::interaction::Interaction;
+use crate::core::texture::Texture;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Multiplies a [Texture] by a scalar one, for example to darken a color with a mask.
+pub struct ScaleTexture<'a, T> {
+    texture: &'a dyn Texture<T>,
+    scale: &'a dyn Texture<f64>,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<'a, T> ScaleTexture<'a, T> {
+    pub fn new(texture: &'a dyn Texture<T>, scale: &'a dyn Texture<f64>) -> Self {
+        Self { texture, scale }
+    }
+}
+
+impl<'a, T: Mul<f64, Output = T>> Texture<T> for ScaleTexture<'a, T> {
+    fn evaluate(&self, interaction: &Interaction) -> T {
+        self.texture.evaluate(interaction) * self.scale.evaluate(interaction)
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::{Point3f, Vec3f};
+    use crate::textures::constant_texture::ConstantTexture;
+
+    #[test]
+    fn scales() {
+        let texture = ConstantTexture::new(0.8);
+        let scale = ConstantTexture::new(0.5);
+        let scaled = ScaleTexture::new(&texture, &scale);
+
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let interaction = Interaction::new_on_surface(Point3f::default(), 1.0, n, n);
+        assert_eq!(scaled.evaluate(&interaction), 0.4);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
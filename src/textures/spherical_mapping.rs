@@ -0,0 +1,73 @@
+use std::f64::consts::PI;
+
+use crate::core::interaction::Interaction;
+use crate::core::texture::TextureMapping2D;
+use crate::core::vector::{Point2f, Point3f};
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Maps the direction from `center` to the hit onto `(phi / 2pi, theta / pi)`,
+/// wrapping a texture around a sphere like a globe.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SphericalMapping {
+    center: Point3f,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl SphericalMapping {
+    pub fn new(center: Point3f) -> Self {
+        Self { center }
+    }
+}
+
+impl TextureMapping2D for SphericalMapping {
+    fn map(&self, interaction: &Interaction) -> Point2f {
+        let d = (interaction.p - self.center).normalize();
+        let theta = d.z.clamp(-1.0, 1.0).acos();
+        let phi = d.y.atan2(d.x).rem_euclid(2.0 * PI);
+        Point2f::new(phi / (2.0 * PI), theta / PI)
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::Vec3f;
+
+    fn at(p: Point3f) -> Interaction<'static> {
+        Interaction::new_on_surface(p, 1.0, Vec3f::new(0.0, 0.0, 1.0), Vec3f::new(0.0, 0.0, 1.0))
+    }
+
+    #[test]
+    fn poles_and_equator() {
+        let mapping = SphericalMapping::new(Point3f::new(1.0, 1.0, 1.0));
+
+        let north = mapping.map(&at(Point3f::new(1.0, 1.0, 3.0)));
+        assert!(north.y.abs() < 1e-12);
+        let south = mapping.map(&at(Point3f::new(1.0, 1.0, -1.0)));
+        assert!((south.y - 1.0).abs() < 1e-12);
+
+        let equator = mapping.map(&at(Point3f::new(1.0, 2.0, 1.0)));
+        assert!((equator.x - 0.25).abs() < 1e-12);
+        assert!((equator.y - 0.5).abs() < 1e-12);
+
+        // Just below the seam wraps around to the end
+        let seam = mapping.map(&at(Point3f::new(2.0, 1.0 - 1e-9, 1.0)));
+        assert!(seam.x > 0.99 && seam.x < 1.0);
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,77 @@
+use crate::core::interaction::Interaction;
+use crate::core::texture::TextureMapping2D;
+use crate::core::vector::Point2f;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Uses the surface coordinates of the hit, scaled and offset,
+/// so textures follow the parameterization of the [Primitive](crate::core::primitive::Primitive).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UVMapping {
+    /// How often the texture repeats across the surface
+    su: f64,
+    sv: f64,
+
+    /// Offsets added after scaling
+    du: f64,
+    dv: f64,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl UVMapping {
+    pub fn new(su: f64, sv: f64, du: f64, dv: f64) -> Self {
+        Self { su, sv, du, dv }
+    }
+}
+
+impl Default for UVMapping {
+    fn default() -> Self {
+        Self::new(1.0, 1.0, 0.0, 0.0)
+    }
+}
+
+impl TextureMapping2D for UVMapping {
+    fn map(&self, interaction: &Interaction) -> Point2f {
+        Point2f::new(
+            self.su * interaction.uv.x + self.du,
+            self.sv * interaction.uv.y + self.dv,
+        )
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::{Point3f, Vec3f};
+
+    #[test]
+    fn scales_and_offsets() {
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let interaction = Interaction::new_on_surface(Point3f::default(), 1.0, n, n)
+            .with_uv(Point2f::new(0.25, 0.5));
+
+        assert_eq!(
+            UVMapping::default().map(&interaction),
+            Point2f::new(0.25, 0.5)
+        );
+        assert_eq!(
+            UVMapping::new(4.0, 2.0, 0.5, -1.0).map(&interaction),
+            Point2f::new(1.5, 0.0)
+        );
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,103 @@
+use crate::core::interaction::Interaction;
+use crate::core::texture::{Texture, TextureMapping3D};
+use crate::core::vector::{Color3f, Point3f};
+use crate::math::noise::fbm;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Solid wood: growth rings around the z axis, one per unit, wobbled by noise.
+/// Each ring darkens gradually from early to late wood, then starts over sharply.
+pub struct WoodTexture<'a> {
+    mapping: &'a dyn TextureMapping3D,
+    light: &'a dyn Texture<Color3f>,
+    dark: &'a dyn Texture<Color3f>,
+
+    /// How far the rings wobble, 0.0 giving perfect circles
+    grain: f64,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+const OCTAVES: usize = 4;
+const ROUGHNESS: f64 = 0.5;
+
+/// Stretches the noise along the trunk, so the wobble follows the grain.
+const GRAIN_STRETCH: f64 = 0.1;
+
+impl<'a> WoodTexture<'a> {
+    pub fn new(
+        mapping: &'a dyn TextureMapping3D,
+        light: &'a dyn Texture<Color3f>,
+        dark: &'a dyn Texture<Color3f>,
+        grain: f64,
+    ) -> Self {
+        Self {
+            mapping,
+            light,
+            dark,
+            grain,
+        }
+    }
+}
+
+impl<'a> Texture<Color3f> for WoodTexture<'a> {
+    fn evaluate(&self, interaction: &Interaction) -> Color3f {
+        let p = self.mapping.map(interaction);
+        let stretched = Point3f::new(p.x, p.y, p.z * GRAIN_STRETCH);
+        let radius =
+            (p.x * p.x + p.y * p.y).sqrt() + self.grain * fbm(&stretched, OCTAVES, ROUGHNESS);
+        let t = radius.rem_euclid(1.0);
+        self.light.evaluate(interaction) * (1.0 - t) + self.dark.evaluate(interaction) * t
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::Vec3f;
+    use crate::textures::constant_texture::ConstantTexture;
+    use crate::textures::point_mapping::PointMapping;
+
+    #[test]
+    fn rings_between_colors() {
+        let mapping = PointMapping::new(Point3f::default(), 1.0);
+        let light = ConstantTexture::new(Color3f::new(0.8, 0.6, 0.4));
+        let dark = ConstantTexture::new(Color3f::new(0.4, 0.2, 0.1));
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+        let at = |texture: &WoodTexture, p: Point3f| {
+            texture.evaluate(&Interaction::new_on_surface(p, 1.0, n, n))
+        };
+
+        // Perfect rings only change with the distance from the axis
+        let perfect = WoodTexture::new(&mapping, &light, &dark, 0.0);
+        let a = at(&perfect, Point3f::new(1.3, 0.0, 0.0));
+        let b = at(&perfect, Point3f::new(0.0, -1.3, 7.0));
+        assert!((a - b).length() < 1e-12);
+        assert!(
+            (at(&perfect, Point3f::new(2.0, 0.0, 0.0)) - Color3f::new(0.8, 0.6, 0.4)).length()
+                < 1e-12
+        );
+
+        let grained = WoodTexture::new(&mapping, &light, &dark, 0.3);
+        for i in 0..1000 {
+            let p = Point3f::new(i as f64 * 0.011, (i as f64 * 0.3).cos(), i as f64 * 0.05);
+            let color = at(&grained, p);
+            assert_eq!(color, at(&grained, p));
+            assert!(color.x >= 0.4 - 1e-12 && color.x <= 0.8 + 1e-12);
+        }
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////
@@ -0,0 +1,88 @@
+use crate::core::interaction::Interaction;
+use crate::core::texture::{Texture, TextureMapping3D};
+use crate::math::noise::worley;
+
+/////////////////////
+// BEGIN INTERFACE //
+/////////////////////
+
+/// Solid cellular noise in [0.0, 1.0], for cobblestones, scales, cracked mud or rock.
+pub struct WorleyTexture<'a> {
+    mapping: &'a dyn TextureMapping3D,
+    feature: WorleyFeature,
+}
+
+/// Which distances to the random feature points the texture shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorleyFeature {
+    /// The distance to the closest point, giving dark cell centers fading out
+    F1,
+
+    /// How much further the second closest point is, giving dark lines between cells
+    F2MinusF1,
+}
+
+//////////////////////////
+// END INTERFACE        //
+// BEGIN IMPLEMENTATION //
+//////////////////////////
+
+impl<'a> WorleyTexture<'a> {
+    pub fn new(mapping: &'a dyn TextureMapping3D, feature: WorleyFeature) -> Self {
+        Self { mapping, feature }
+    }
+}
+
+impl<'a> Texture<f64> for WorleyTexture<'a> {
+    fn evaluate(&self, interaction: &Interaction) -> f64 {
+        let (f1, f2) = worley(&self.mapping.map(interaction));
+        let v = match self.feature {
+            WorleyFeature::F1 => f1,
+            WorleyFeature::F2MinusF1 => f2 - f1,
+        };
+        v.clamp(0.0, 1.0)
+    }
+}
+
+////////////////////////
+// END IMPLEMENTATION //
+// BEGIN TESTS        //
+////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vector::{Point3f, Vec3f};
+    use crate::textures::point_mapping::PointMapping;
+
+    #[test]
+    fn deterministic_and_in_range() {
+        let mapping = PointMapping::new(Point3f::default(), 4.0);
+        let n = Vec3f::new(0.0, 0.0, 1.0);
+
+        for feature in [WorleyFeature::F1, WorleyFeature::F2MinusF1] {
+            let texture = WorleyTexture::new(&mapping, feature);
+            let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+            for i in 0..2000 {
+                let p = Point3f::new(i as f64 * 0.013, (i as f64 * 0.7).sin(), 0.5);
+                let interaction = Interaction::new_on_surface(p, 1.0, n, n);
+                let v = texture.evaluate(&interaction);
+                assert_eq!(v, texture.evaluate(&interaction));
+                min = min.min(v);
+                max = max.max(v);
+            }
+            assert!(0.0 <= min && max <= 1.0);
+            assert!(
+                min < 0.1 && max > 0.4,
+                "{:?} spans [{}, {}]",
+                feature,
+                min,
+                max
+            );
+        }
+    }
+}
+
+///////////////
+// END TESTS //
+///////////////